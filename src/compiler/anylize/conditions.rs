@@ -0,0 +1,35 @@
+use super::*;
+
+/// Returns true if both actions result in the same value, like `a.b` and `a.b` or `a + 1` and `a + 1`
+pub fn same_value(a: &Action, b: &Action) -> bool {
+  match (&a.type_, &b.type_) {
+    (ActionType::VarRef(a), ActionType::VarRef(b)) => a == b,
+    (ActionType::StaticString(a), ActionType::StaticString(b)) => a.content == b.content,
+    (ActionType::StaticBoolean(a), ActionType::StaticBoolean(b)) => a.0 == b.0,
+    (ActionType::StaticNumber(a), ActionType::StaticNumber(b)) => match (&a.type_, &b.type_) {
+      (NumberType::Int(a), NumberType::Int(b)) => a == b,
+      (NumberType::Float(a), NumberType::Float(b)) => a == b,
+      _ => false,
+    },
+    (ActionType::Operation(a), ActionType::Operation(b)) => {
+      a.operator == b.operator && same_value(&a.left, &b.left) && same_value(&a.right, &b.right)
+    }
+    (ActionType::Unary(a), ActionType::Unary(b)) => {
+      a.operator == b.operator && same_value(&a.action, &b.action)
+    }
+    (ActionType::Tuple(a), ActionType::Tuple(b)) => {
+      a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_value(a, b))
+    }
+    // Function calls can return something else every time they are called
+    _ => false,
+  }
+}
+
+/// Returns the result of comparing a value with itself `a == a`, None if the operator doesn't compare
+pub fn self_comparison(operator: &Operator) -> Option<bool> {
+  match operator {
+    Operator::Equal | Operator::LessOrEqual | Operator::GreaterOrEqual => Some(true),
+    Operator::NotEqual | Operator::Less | Operator::Greater => Some(false),
+    _ => None,
+  }
+}
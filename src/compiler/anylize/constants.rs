@@ -0,0 +1,249 @@
+use super::*;
+use files::Span;
+use tokenize::{ActionOperation, ActionUnary, Boolean, Number, Operator, String_, UnaryOperator};
+
+/// A value that is known at compile time
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+  Int(i64),
+  Float(f64),
+  String(String),
+  Bool(bool),
+}
+
+impl ConstValue {
+  /// Converts the value back into an action so it can be inlined in the generated code
+  pub fn into_action(self, location: Span) -> Action {
+    let type_ = match self {
+      Self::Int(value) => Number {
+        type_: NumberType::Int(value),
+      }
+      .into(),
+      Self::Float(value) => Number {
+        type_: NumberType::Float(value),
+      }
+      .into(),
+      Self::String(content) => String_ { content }.into(),
+      Self::Bool(value) => Boolean(value).into(),
+    };
+    Action {
+      type_,
+      location,
+      attributes: vec![],
+    }
+  }
+}
+
+/// The error of evaluating a constant,
+/// None means the error was already reported while evaluating another constant
+type EvalError = Option<(AnylizeErrAndWarns, Span)>;
+
+/// Evaluates the global constants and the enum values
+pub struct ConstEvaluator<'a> {
+  vars: &'a HashMap<String, Variable>,
+  /// The results of the constants that are already evaluated, None if the constant contains an error
+  results: HashMap<String, Option<ConstValue>>,
+  /// The constants we are evaluating right now, used to detect cycles
+  evaluating: Vec<String>,
+  /// The enum whose values we are evaluating, its fields can be referenced without the enum name `high = low + 1`
+  enum_name: Option<String>,
+}
+
+impl<'a> ConstEvaluator<'a> {
+  pub fn new(vars: &'a HashMap<String, Variable>) -> Self {
+    Self {
+      vars,
+      results: HashMap::new(),
+      evaluating: vec![],
+      enum_name: None,
+    }
+  }
+  /// Returns all values that could be evaluated
+  pub fn values(self) -> HashMap<String, ConstValue> {
+    let mut res = HashMap::new();
+    for (name, value) in self.results {
+      if let Some(value) = value {
+        res.insert(name, value);
+      }
+    }
+    res
+  }
+  /// Evaluates a global constant, location is where the constant is referenced
+  pub fn const_value(&mut self, name: &str, location: &Span) -> Result<ConstValue, EvalError> {
+    if let Some(result) = self.results.get(name) {
+      return result.clone().ok_or(None);
+    }
+
+    let var = match self.vars.get(name) {
+      Some(var) => var,
+      None => return Err(Some((AnylizeErrAndWarns::NotConstant, location.clone()))),
+    };
+    if let Some(index) = self.evaluating.iter().position(|item| item == name) {
+      let mut chain = self.evaluating[index..].to_vec();
+      chain.push(name.to_string());
+      return Err(Some((
+        AnylizeErrAndWarns::ConstCycle(chain.join(" -> ")),
+        var.location.clone(),
+      )));
+    }
+
+    self.evaluating.push(name.to_string());
+    let res = self.evaluate(&var.action);
+    self.evaluating.pop();
+
+    self.results.insert(name.to_string(), res.clone().ok());
+    res
+  }
+  /// Sets the value of something that can be referenced by name, like an enum field `Foo.bar`,
+  /// None if the value contains an error
+  pub fn set_value(&mut self, name: String, value: Option<ConstValue>) {
+    self.results.insert(name, value);
+  }
+  /// Sets the enum whose values are evaluated next, None once all enum values are evaluated
+  pub fn set_enum(&mut self, enum_name: Option<String>) {
+    self.enum_name = enum_name;
+  }
+  /// Folds an action into a single value
+  pub fn evaluate(&mut self, action: &Action) -> Result<ConstValue, EvalError> {
+    match &action.type_ {
+      ActionType::StaticString(value) => Ok(ConstValue::String(value.content.clone())),
+      ActionType::StaticNumber(value) => Ok(match value.type_ {
+        NumberType::Int(value) => ConstValue::Int(value),
+        NumberType::Float(value) => ConstValue::Float(value),
+      }),
+      ActionType::StaticBoolean(value) => Ok(ConstValue::Bool(value.0)),
+      ActionType::VarRef(name) => {
+        // The fields of the enum we are evaluating shadow the constants
+        let field = match &self.enum_name {
+          Some(enum_name) => self.results.get(&format!("{}.{}", enum_name, name)),
+          None => None,
+        };
+        match field {
+          Some(result) => result.clone().ok_or(None),
+          None => self.const_value(name, &action.location),
+        }
+      }
+      ActionType::Operation(operation) => self.evaluate_operation(operation, &action.location),
+      ActionType::Unary(unary) => self.evaluate_unary(unary, &action.location),
+      ActionType::FunctionCall(_) | ActionType::Propagate(_) | ActionType::Catch(_) => Err(Some((
+        AnylizeErrAndWarns::ConstFunctionCall,
+        action.location.clone(),
+      ))),
+      _ => Err(Some((
+        AnylizeErrAndWarns::NotConstant,
+        action.location.clone(),
+      ))),
+    }
+  }
+  fn evaluate_unary(
+    &mut self,
+    unary: &ActionUnary,
+    location: &Span,
+  ) -> Result<ConstValue, EvalError> {
+    let res = match (&unary.operator, self.evaluate(&unary.action)?) {
+      (UnaryOperator::Negate, ConstValue::Int(value)) => value.checked_neg().map(ConstValue::Int),
+      (UnaryOperator::Negate, ConstValue::Float(value)) => Some(ConstValue::Float(-value)),
+      (UnaryOperator::Not, ConstValue::Bool(value)) => Some(ConstValue::Bool(!value)),
+      _ => {
+        return Err(Some((
+          AnylizeErrAndWarns::InvalidConstOperation(unary.operator.to_str()),
+          location.clone(),
+        )))
+      }
+    };
+    res.ok_or_else(|| Some((AnylizeErrAndWarns::ConstOverflow, location.clone())))
+  }
+  fn evaluate_operation(
+    &mut self,
+    operation: &ActionOperation,
+    location: &Span,
+  ) -> Result<ConstValue, EvalError> {
+    let left = self.evaluate(&operation.left)?;
+    let right = self.evaluate(&operation.right)?;
+    let error = |err: AnylizeErrAndWarns| -> Result<ConstValue, EvalError> {
+      Err(Some((err, location.clone())))
+    };
+    let invalid = AnylizeErrAndWarns::InvalidConstOperation(operation.operator.to_str());
+
+    match (left, right) {
+      (ConstValue::Int(left), ConstValue::Int(right)) => {
+        let res = match operation.operator {
+          Operator::Add => left.checked_add(right),
+          Operator::Subtract => left.checked_sub(right),
+          Operator::Multiply => left.checked_mul(right),
+          Operator::Divide | Operator::Remainder if right == 0 => {
+            return error(AnylizeErrAndWarns::DivisionByZero)
+          }
+          Operator::Divide => left.checked_div(right),
+          Operator::Remainder => left.checked_rem(right),
+          Operator::And | Operator::Or => return error(invalid),
+          _ => return Ok(ConstValue::Bool(compare(&operation.operator, left, right))),
+        };
+        match res {
+          Some(res) => Ok(ConstValue::Int(res)),
+          None => error(AnylizeErrAndWarns::ConstOverflow),
+        }
+      }
+      (ConstValue::Int(left), ConstValue::Float(right)) => {
+        float_operation(&operation.operator, left as f64, right).or_else(error)
+      }
+      (ConstValue::Float(left), ConstValue::Int(right)) => {
+        float_operation(&operation.operator, left, right as f64).or_else(error)
+      }
+      (ConstValue::Float(left), ConstValue::Float(right)) => {
+        float_operation(&operation.operator, left, right).or_else(error)
+      }
+      (ConstValue::String(left), ConstValue::String(right)) => match operation.operator {
+        Operator::Add => Ok(ConstValue::String(left + &right)),
+        Operator::Subtract
+        | Operator::Multiply
+        | Operator::Divide
+        | Operator::Remainder
+        | Operator::And
+        | Operator::Or => error(invalid),
+        _ => Ok(ConstValue::Bool(compare(&operation.operator, left, right))),
+      },
+      (ConstValue::Bool(left), ConstValue::Bool(right)) => match operation.operator {
+        Operator::And => Ok(ConstValue::Bool(left && right)),
+        Operator::Or => Ok(ConstValue::Bool(left || right)),
+        Operator::Equal => Ok(ConstValue::Bool(left == right)),
+        Operator::NotEqual => Ok(ConstValue::Bool(left != right)),
+        _ => error(invalid),
+      },
+      _ => error(invalid),
+    }
+  }
+}
+
+/// Applies one of the comparison operators
+fn compare<T: PartialOrd>(operator: &Operator, left: T, right: T) -> bool {
+  match operator {
+    Operator::Equal => left == right,
+    Operator::NotEqual => left != right,
+    Operator::Less => left < right,
+    Operator::LessOrEqual => left <= right,
+    Operator::Greater => left > right,
+    _ => left >= right,
+  }
+}
+
+fn float_operation(
+  operator: &Operator,
+  left: f64,
+  right: f64,
+) -> Result<ConstValue, AnylizeErrAndWarns> {
+  Ok(ConstValue::Float(match operator {
+    Operator::Add => left + right,
+    Operator::Subtract => left - right,
+    Operator::Multiply => left * right,
+    Operator::Divide | Operator::Remainder if right == 0.0 => {
+      return Err(AnylizeErrAndWarns::DivisionByZero)
+    }
+    Operator::Divide => left / right,
+    Operator::Remainder => left % right,
+    Operator::And | Operator::Or => {
+      return Err(AnylizeErrAndWarns::InvalidConstOperation(operator.to_str()))
+    }
+    _ => return Ok(ConstValue::Bool(compare(operator, left, right))),
+  }))
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+/// Returns true if the end of the actions can never be reached because every path returns, throws or loops forever
+pub fn always_returns(actions: &Actions) -> bool {
+  never_ends(actions, false)
+}
+
+/// Same as always_returns but a break or continue also leaves the actions,
+/// used for blocks that must not continue like the body of a catch that is used as value
+pub fn always_leaves(actions: &Actions) -> bool {
+  never_ends(actions, true)
+}
+
+/// jumps tells if a break or continue counts as leaving the actions
+fn never_ends(actions: &Actions, jumps: bool) -> bool {
+  actions
+    .actions
+    .iter()
+    .any(|action| action_never_ends(action, jumps))
+}
+
+fn action_never_ends(action: &Action, jumps: bool) -> bool {
+  match &action.type_ {
+    ActionType::Return(_) | ActionType::Throw(_) => true,
+    ActionType::Break(_) | ActionType::Continue(_) => jumps,
+    ActionType::If(data) => match &data.else_body {
+      // Without an else the if might be skipped
+      Some(else_body) => {
+        never_ends(&data.if_.body, jumps)
+          && data
+            .else_ifs
+            .iter()
+            .all(|else_if| never_ends(&else_if.body, jumps))
+          && never_ends(else_body, jumps)
+      }
+      None => false,
+    },
+    ActionType::Match(data) => {
+      data
+        .arms
+        .iter()
+        .any(|arm| matches!(arm.pattern, MatchPattern::Default))
+        && data.arms.iter().all(|arm| never_ends(&arm.body, jumps))
+    }
+    // A loop without a break never ends, while loops are left out as go doesn't see them as the end of a function
+    ActionType::Loop(data) => loop_never_ends(data),
+    _ => false,
+  }
+}
+
+/// Returns true if nothing inside the loop breaks out of it
+pub fn loop_never_ends(data: &ActionLoop) -> bool {
+  !actions_break_out(&data.actions, &data.label, false)
+}
+
+/// Returns true if one of the actions breaks out of the loop with label,
+/// nested is true inside loops within the loop where only a break with the label leaves our loop
+fn actions_break_out(actions: &Actions, label: &Option<String>, nested: bool) -> bool {
+  actions
+    .actions
+    .iter()
+    .any(|action| breaks_out(action, label, nested))
+}
+
+fn breaks_out(action: &Action, label: &Option<String>, nested: bool) -> bool {
+  match &action.type_ {
+    ActionType::Break(None) => !nested,
+    ActionType::Break(Some(break_label)) => label.as_ref() == Some(break_label),
+    ActionType::For(data) => actions_break_out(&data.actions, label, true),
+    ActionType::While(data) => actions_break_out(&data.actions, label, true),
+    ActionType::Loop(data) => actions_break_out(&data.actions, label, true),
+    ActionType::If(data) => {
+      actions_break_out(&data.if_.body, label, nested)
+        || data
+          .else_ifs
+          .iter()
+          .any(|else_if| actions_break_out(&else_if.body, label, nested))
+        || data
+          .else_body
+          .as_ref()
+          .is_some_and(|body| actions_break_out(body, label, nested))
+    }
+    ActionType::Match(data) => data
+      .arms
+      .iter()
+      .any(|arm| actions_break_out(&arm.body, label, nested)),
+    // The body of a catch can be part of a variable `let a = foo() catch err { break }`
+    ActionType::Catch(catch) => actions_break_out(&catch.body, label, nested),
+    ActionType::Variable(var) => breaks_out(&var.action, label, nested),
+    ActionType::VariableTuple(var) => breaks_out(&var.action, label, nested),
+    ActionType::Assigment(data) => breaks_out(&data.action, label, nested),
+    _ => false,
+  }
+}
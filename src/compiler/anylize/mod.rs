@@ -1,22 +1,37 @@
+pub mod conditions;
+pub mod constants;
+pub mod flow;
+pub mod scope;
+pub mod types;
+pub mod usage;
 pub mod utils;
 
 #[cfg(test)]
 mod tests;
 
 use super::*;
+use conditions::{same_value, self_comparison};
+use constants::{ConstEvaluator, ConstValue};
 use core::fmt::Display;
-use files::{File, Path};
+use files::File;
+use flow::{always_leaves, always_returns, loop_never_ends};
+use scope::{Scopes, Symbol, SymbolKind, SymbolTable};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::rc::Rc;
+use tokenize::attributes::{attribute_targets, binding, deprecated_attribute};
 use tokenize::{
-  Action, ActionType, Actions, Enum, Function, GlobalType, Import, Keywords, Struct, Type,
-  TypeType, VarType, Variable,
+  Action, ActionFor, ActionFunctionCall, ActionLoop, ActionOperation, ActionType, ActionUnary,
+  ActionWhile, Actions, Attribute, Enum, Function, FunctionArg, GlobalType, Import, Keywords,
+  MatchPattern, NumberType, Operator, Struct, StructField, Type, TypeId, TypeRef, TypeSymbol,
+  TypeType, UnaryOperator, VarType, Variable, VariableTuple,
 };
-use utils::{is_pascal_case, is_snake_case, GetLocation, GetName};
-
-trait AddToAnylizeResults {
-  fn add(self, add_to: &mut AnylizeResults);
-}
+use types::{
+  qualify_type, recursive_types, resolve_aliases, resolve_type_refs, same_type, type_symbols,
+  underlying_type,
+};
+use usage::referenced_names;
+use utils::{is_pascal_case, is_reserved_name, is_snake_case, GetAttributes, GetLocation, GetName};
 
 #[derive(Clone)]
 pub enum AnylizeErrAndWarns {
@@ -25,6 +40,18 @@ pub enum AnylizeErrAndWarns {
   NameShouldBeSnakeCase,  // some_var_name
   EmptyEnum,
   UnreachableCode,
+  UnusedLabel,
+  UnusedVariable(String),
+  UnusedArgument(String),
+  UnusedImport(String),
+  UnusedFunction(String),
+  /// The value the condition always has
+  ConstantCondition(bool),
+  RepeatedCondition,
+  /// The result of comparing the value with itself
+  SelfComparison(bool),
+  /// The name of the deprecated item and the optional message of `#[deprecated("use bar")]`
+  Deprecated(String, Option<String>),
 
   // Errors
   ContinueNotAllowed,
@@ -34,10 +61,69 @@ pub enum AnylizeErrAndWarns {
   NameAlreadyExists,
   AlreadyDefined,
   KeywordAsName,
+  ReservedName,
   VariableRefDoesNotExist,
   FunctionDoesNotExist,
   VariableAlreadyDeclared,
   Inmutable,
+  /// The amound of values expected and the amound of values found
+  ArityMismatch(usize, usize),
+  TupleNotAllowed,
+  EnumFieldDoesNotExist,
+  EnumValueNotAllowed,
+  ThrowNotAllowed,
+  PropagateNotAllowed,
+  UnhandledError,
+  NothingToHandle,
+  ErrorHandlingNotAllowed,
+  CatchWithoutValue,
+  MainCannotThrow,
+  LabelDoesNotExist,
+  LabelAlreadyDefined,
+  ArgumentDoesNotExist(String),
+  ArgumentAlreadySet(String),
+  MissingArgument(String),
+  VariadicArgumentNotLast,
+  VariadicArgumentWithDefault,
+  VariadicArgumentByName(String),
+  SpreadWithoutVariadicArgument,
+  SpreadWithVariadicValues,
+  NotConstant,
+  ConstFunctionCall,
+  /// The chain of constants that reference each other `a -> b -> a`
+  ConstCycle(String),
+  /// The chain of files that import each other `main.tp -> a.tp -> b.tp -> a.tp`
+  ImportCycle(String),
+  /// The chain of types that contain each other `A -> B -> A`
+  RecursiveType(String),
+  /// The chain of aliases that refer to each other `A -> B -> A`
+  RecursiveAlias(String),
+  DivisionByZero,
+  StepNotPositive,
+  ConstOverflow,
+  InvalidConstOperation(&'static str),
+  ExpectedNumber(String),
+  UnknownAttribute(String),
+  UnknownTarget(String),
+  InvalidAttributeArgs(String),
+  AttributeNotAllowed(String),
+  /// The target an extern function has no `#[bind(..)]` for
+  MissingBinding(&'static str),
+  ExternWithoutBinding,
+  /// The expected type and the type found
+  TypeMismatch(String, String),
+  DeferNotAllowed,
+  DeferWithoutCall,
+  DeferThrows,
+  /// The return type of the function
+  MissingReturn(String),
+  MissingReturnValue(String),
+  ReturnValueNotExpected,
+  NoValue,
+  TypeDoesNotExist(String),
+  ModuleDoesNotExist(String),
+  /// The alias of the imported module and the name of the item that doesn't exist in it
+  MemberDoesNotExist(String, String),
 }
 
 impl AnylizeErrAndWarns {
@@ -46,18 +132,77 @@ impl AnylizeErrAndWarns {
       Self::NameShouldBePascalCase
       | Self::NameShouldBeSnakeCase
       | Self::EmptyEnum
-      | Self::UnreachableCode => true,
+      | Self::UnreachableCode
+      | Self::UnusedLabel
+      | Self::UnusedVariable(_)
+      | Self::UnusedArgument(_)
+      | Self::UnusedImport(_)
+      | Self::UnusedFunction(_)
+      | Self::ConstantCondition(_)
+      | Self::RepeatedCondition
+      | Self::SelfComparison(_)
+      | Self::Deprecated(_, _) => true,
       Self::NoName
       | Self::BreakNotAllowed
       | Self::ContinueNotAllowed
       | Self::NameAlreadyExists
       | Self::AlreadyDefined
       | Self::KeywordAsName
+      | Self::ReservedName
       | Self::NamingNotAllowed
       | Self::VariableRefDoesNotExist
       | Self::FunctionDoesNotExist
       | Self::VariableAlreadyDeclared
-      | Self::Inmutable => false,
+      | Self::Inmutable
+      | Self::ArityMismatch(_, _)
+      | Self::TupleNotAllowed
+      | Self::EnumFieldDoesNotExist
+      | Self::EnumValueNotAllowed
+      | Self::ThrowNotAllowed
+      | Self::PropagateNotAllowed
+      | Self::UnhandledError
+      | Self::NothingToHandle
+      | Self::ErrorHandlingNotAllowed
+      | Self::CatchWithoutValue
+      | Self::MainCannotThrow
+      | Self::LabelDoesNotExist
+      | Self::LabelAlreadyDefined
+      | Self::ArgumentDoesNotExist(_)
+      | Self::ArgumentAlreadySet(_)
+      | Self::MissingArgument(_)
+      | Self::VariadicArgumentNotLast
+      | Self::VariadicArgumentWithDefault
+      | Self::VariadicArgumentByName(_)
+      | Self::SpreadWithoutVariadicArgument
+      | Self::SpreadWithVariadicValues
+      | Self::NotConstant
+      | Self::ConstFunctionCall
+      | Self::ConstCycle(_)
+      | Self::ImportCycle(_)
+      | Self::RecursiveType(_)
+      | Self::RecursiveAlias(_)
+      | Self::DivisionByZero
+      | Self::StepNotPositive
+      | Self::ConstOverflow
+      | Self::InvalidConstOperation(_)
+      | Self::ExpectedNumber(_)
+      | Self::UnknownAttribute(_)
+      | Self::UnknownTarget(_)
+      | Self::InvalidAttributeArgs(_)
+      | Self::AttributeNotAllowed(_)
+      | Self::MissingBinding(_)
+      | Self::ExternWithoutBinding
+      | Self::TypeMismatch(_, _)
+      | Self::DeferNotAllowed
+      | Self::DeferWithoutCall
+      | Self::DeferThrows
+      | Self::MissingReturn(_)
+      | Self::MissingReturnValue(_)
+      | Self::ReturnValueNotExpected
+      | Self::NoValue
+      | Self::TypeDoesNotExist(_)
+      | Self::ModuleDoesNotExist(_)
+      | Self::MemberDoesNotExist(_, _) => false,
     }
   }
 }
@@ -76,6 +221,32 @@ impl Display for AnylizeErrAndWarns {
       ),
       Self::EmptyEnum => write!(f, "Empty enum"),
       Self::UnreachableCode => write!(f, "Unreachable code"),
+      Self::UnusedLabel => write!(f, "This label is never used"),
+      Self::UnusedVariable(name) => write!(
+        f,
+        "The variable {} is never used, prefix it with _ to ignore this",
+        name
+      ),
+      Self::UnusedArgument(name) => write!(
+        f,
+        "The argument {} is never used, prefix it with _ to ignore this",
+        name
+      ),
+      Self::UnusedImport(name) => write!(f, "The import {} is never used", name),
+      Self::ConstantCondition(value) => write!(f, "This condition is always {}", value),
+      Self::RepeatedCondition => {
+        write!(f, "This condition is already checked by an earlier branch")
+      }
+      Self::SelfComparison(value) => {
+        write!(f, "Comparing a value with itself is always {}", value)
+      }
+      Self::UnusedFunction(name) => write!(
+        f,
+        "The function {} is never used, prefix it with _ to ignore this",
+        name
+      ),
+      Self::Deprecated(name, Some(message)) => write!(f, "{} is deprecated: {}", name, message),
+      Self::Deprecated(name, None) => write!(f, "{} is deprecated", name),
 
       // Errors
       Self::BreakNotAllowed => write!(f, "Break not allowed here"),
@@ -85,16 +256,149 @@ impl Display for AnylizeErrAndWarns {
       Self::NameAlreadyExists => write!(f, "Name already exsits"),
       Self::NamingNotAllowed => write!(f, "A name is not allowed here"),
       Self::KeywordAsName => write!(f, "Using a language keyword is not allowed here"),
+      Self::ReservedName => write!(
+        f,
+        "Names starting with __ are reserved for the code generated by the compiler"
+      ),
       Self::VariableRefDoesNotExist => write!(f, "The variable referenced doesn't exist"),
       Self::FunctionDoesNotExist => write!(f, "This function doesn't exist"),
       Self::VariableAlreadyDeclared => write!(f, "Variable already declared"),
       Self::Inmutable => write!(f, "Data in un mutatable"),
+      Self::ArityMismatch(expected, found) => {
+        write!(f, "Expected {} value(s) but found {}", expected, found)
+      }
+      Self::TupleNotAllowed => write!(
+        f,
+        "Tuples can only be returned from functions and destructured into variables"
+      ),
+      Self::EnumFieldDoesNotExist => write!(f, "This enum field doesn't exist"),
+      Self::EnumValueNotAllowed => write!(f, "Enum values are not allowed in enums with data"),
+      Self::ThrowNotAllowed => write!(
+        f,
+        "Throw is only allowed inside functions that throw (add throws to the function)"
+      ),
+      Self::PropagateNotAllowed => write!(
+        f,
+        "Errors can only be propagated inside functions that throw (add throws to the function)"
+      ),
+      Self::UnhandledError => write!(
+        f,
+        "This function can fail, handle the error using ? or catch"
+      ),
+      Self::NothingToHandle => write!(f, "This can't fail so there is no error to handle"),
+      Self::ErrorHandlingNotAllowed => write!(
+        f,
+        "Errors can only be handled on statements, variable values and return values"
+      ),
+      Self::CatchWithoutValue => write!(
+        f,
+        "The catch has no value for the variable, end it with return, throw, break or continue"
+      ),
+      Self::MainCannotThrow => write!(f, "The main function cannot throw"),
+      Self::LabelDoesNotExist => write!(f, "There is no loop with this label around here"),
+      Self::LabelAlreadyDefined => write!(f, "This label is already used in this function"),
+      Self::ArgumentDoesNotExist(name) => write!(f, "There is no argument named {}", name),
+      Self::ArgumentAlreadySet(name) => write!(f, "The argument {} is already set", name),
+      Self::MissingArgument(name) => write!(f, "Missing a value for the argument {}", name),
+      Self::VariadicArgumentNotLast => write!(f, "Only the last argument can be variadic"),
+      Self::VariadicArgumentWithDefault => {
+        write!(f, "A variadic argument can't have a default value")
+      }
+      Self::VariadicArgumentByName(name) => write!(
+        f,
+        "The variadic argument {} can't be set by name, use a spread `...{}` instead",
+        name, name
+      ),
+      Self::SpreadWithoutVariadicArgument => {
+        write!(f, "Spreading a value requires a variadic argument")
+      }
+      Self::NotConstant => write!(
+        f,
+        "Constants can only contain static values, operators and other constants"
+      ),
+      Self::ConstFunctionCall => write!(f, "Functions can't be called inside a constant"),
+      Self::ConstCycle(chain) => write!(f, "Constants reference each other {}", chain),
+      Self::ImportCycle(chain) => write!(f, "Files import each other {}", chain),
+      Self::RecursiveType(chain) => write!(
+        f,
+        "The type contains itself {}, only an array of the type can be used inside of it",
+        chain
+      ),
+      Self::RecursiveAlias(chain) => write!(
+        f,
+        "The alias refers to itself {}, use a new type like `type A []A` instead",
+        chain
+      ),
+      Self::DivisionByZero => write!(f, "Division by zero"),
+      Self::StepNotPositive => write!(f, "The step of a range must be bigger than 0"),
+      Self::ConstOverflow => write!(f, "The result of this constant overflows"),
+      Self::InvalidConstOperation(operator) => {
+        write!(f, "The operator {} can't be used on these values", operator)
+      }
+      Self::ExpectedNumber(found) => write!(f, "Expected a number, found {}", found),
+      Self::SpreadWithVariadicValues => write!(
+        f,
+        "A spread can't be combined with other values for the variadic argument"
+      ),
+      Self::UnknownAttribute(name) => write!(f, "Unknown attribute {}", name),
+      Self::UnknownTarget(name) => write!(
+        f,
+        "Unknown target {}, the supported targets are go and js",
+        name
+      ),
+      Self::InvalidAttributeArgs(name) => {
+        write!(f, "Invalid arguments for the attribute {}", name)
+      }
+      Self::AttributeNotAllowed(name) => write!(f, "The attribute {} is not allowed here", name),
+      Self::MissingBinding(target) => write!(
+        f,
+        "This extern function has no binding for {} add one like #[bind({}, \"foo\")]",
+        target, target
+      ),
+      Self::DeferNotAllowed => write!(
+        f,
+        "Defer is only allowed directly inside a function body and not inside loops, ifs or matches"
+      ),
+      Self::DeferWithoutCall => write!(f, "Only function calls can be deferred"),
+      Self::DeferThrows => write!(
+        f,
+        "Functions that can fail can't be deferred, there is no place to handle their error"
+      ),
+      Self::MissingReturn(type_) => write!(
+        f,
+        "This function can reach its end without returning a value of type {}",
+        type_
+      ),
+      Self::MissingReturnValue(type_) => {
+        write!(f, "This function has to return a value of type {}", type_)
+      }
+      Self::TypeDoesNotExist(name) => write!(f, "The type {} doesn't exist", name),
+      Self::ModuleDoesNotExist(name) => write!(f, "There is no imported module named {}", name),
+      Self::MemberDoesNotExist(module, name) => {
+        write!(f, "The module {} has no item named {}", module, name)
+      }
+      Self::ReturnValueNotExpected => write!(
+        f,
+        "This function doesn't return a value, add a return type to the function to return one"
+      ),
+      Self::NoValue => write!(
+        f,
+        "This function doesn't return anything so it can't be used as a value"
+      ),
+      Self::TypeMismatch(expected, found) => write!(f, "Expected {}, found {}", expected, found),
+      Self::ExternWithoutBinding => write!(
+        f,
+        "Extern functions need a binding like #[bind(go, \"strings.ToUpper\", \"strings\")]"
+      ),
     }
   }
 }
 
 pub struct AnylizeResults {
-  file: File,
+  /// The opened files by their id, a message is shown in the file its location points to
+  files: HashMap<usize, File>,
+  /// Report unused items as errors instead of warnings
+  deny_unused: bool,
   pub warnings: Vec<LocationError>,
   pub errors: Vec<LocationError>,
 }
@@ -108,40 +412,316 @@ pub struct AnilizedTokens {
   pub enums: HashMap<String, Enum>,
   pub types: HashMap<String, GlobalType>,
   pub imports: HashMap<String, Import>,
+  /// The folded values of the global constants and enum fields, by the name used to reference them
+  pub consts: HashMap<String, ConstValue>,
+  /// The imported modules by the alias of their import
+  pub modules: HashMap<String, Rc<AnilizedTokens>>,
+  /// The symbols declared inside the functions by the name of the function
+  pub symbols: HashMap<String, SymbolTable>,
+  /// The locations of the divisions of two integers, these need to be truncated in languages without integer division
+  pub integer_divisions: Vec<Span>,
 }
 
-#[derive(Debug)]
-struct SimpleAnilizedTokens<'a> {
-  pub functions: &'a HashMap<String, Function>,
-  pub vars: &'a HashMap<String, Variable>,
-  pub structs: &'a HashMap<String, Struct>,
-  pub enums: &'a HashMap<String, Enum>,
-  pub types: &'a HashMap<String, GlobalType>,
-  pub imports: &'a HashMap<String, Import>,
+impl AnilizedTokens {
+  /// Returns the file these tokens are made from
+  pub fn file(&self) -> &File {
+    &self.file
+  }
+  /// Returns the id of a struct, enum or global type declared in this file
+  pub fn type_id(&self, name: impl Into<String>) -> TypeId {
+    TypeId {
+      file_id: self.file.id,
+      name: name.into(),
+    }
+  }
+  /// Splits a reference to an item of an imported module like `utils.foo` into the alias, the module and the name of the item,
+  /// returns None if the name doesn't start with the alias of an import
+  pub fn module_ref<'b>(&self, name: &'b str) -> Option<(&'b str, &AnilizedTokens, &'b str)> {
+    let (alias, name) = name.split_once('.')?;
+    Some((alias, self.modules.get(alias)?, name))
+  }
+  /// Splits a reference to an enum field like `Shape.circle` or `utils.Shape.circle` into the enum and the field name,
+  /// returns None if the name doesn't reference an enum
+  pub fn enum_field_ref<'b>(&self, name: &'b str) -> Option<(&Enum, &'b str)> {
+    let (tokens, name) = match self.module_ref(name) {
+      Some((_, module, name)) => (module, name),
+      None => (self, name),
+    };
+    let (enum_name, field_name) = name.split_once('.')?;
+    Some((tokens.enums.get(enum_name)?, field_name))
+  }
+  /// Returns the type of the enum an enum field reference points to,
+  /// enums of imported modules are referenced using the alias of the import
+  pub fn enum_type(&self, name: &str) -> Option<TypeType> {
+    let (enum_, _) = self.enum_field_ref(name)?;
+    let enum_name = enum_.name.clone()?;
+    Some(TypeType::TypeRef(match self.module_ref(name) {
+      Some((alias, module, _)) => TypeRef {
+        name: format!("{}.{}", alias, enum_name),
+        symbol: Some(TypeSymbol::Import(alias.to_string())),
+        id: Some(module.type_id(&enum_name)),
+      },
+      None => TypeRef {
+        id: Some(self.type_id(&enum_name)),
+        name: enum_name,
+        symbol: Some(TypeSymbol::Enum),
+      },
+    }))
+  }
+  /// Returns the function with name, this can be a function of an imported module `utils.foo`.
+  /// The types of an imported function refer to the module using the alias of the import
+  pub fn function(&self, name: &str) -> Option<Function> {
+    let (alias, module, name) = match self.module_ref(name) {
+      Some(module_ref) => module_ref,
+      None => return self.functions.get(name).cloned(),
+    };
+    let mut function = module.functions.get(name)?.clone();
+    for arg in &mut function.args {
+      arg.type_ = qualify_type(&arg.type_, alias);
+    }
+    function.res = function.res.map(|res| qualify_type(&res, alias));
+    Some(function)
+  }
+  /// Returns the global constant of an imported module `utils.max`,
+  /// the type of the constant refers to the module using the alias of the import
+  pub fn module_var(&self, name: &str) -> Option<Variable> {
+    let (alias, module, name) = self.module_ref(name)?;
+    let mut var = module.vars.get(name)?.clone();
+    var.data_type = var.data_type.map(|type_| qualify_type(&type_, alias));
+    Some(var)
+  }
+  /// Returns the error for a name that doesn't point to anything,
+  /// for a name like `utils.foo` this tells if the module or the item inside of it doesn't exist
+  pub fn missing_ref_error(&self, name: &str, fallback: AnylizeErrAndWarns) -> AnylizeErrAndWarns {
+    let (alias, member) = match name.split_once('.') {
+      Some(parts) => parts,
+      None => return fallback,
+    };
+    if self.modules.contains_key(alias) {
+      AnylizeErrAndWarns::MemberDoesNotExist(alias.to_string(), member.to_string())
+    } else if self.enums.contains_key(alias)
+      || self.structs.contains_key(alias)
+      || self.types.contains_key(alias)
+    {
+      fallback
+    } else {
+      AnylizeErrAndWarns::ModuleDoesNotExist(alias.to_string())
+    }
+  }
+  /// Returns the arguments of the called function or enum field,
+  /// returns None if there is nothing with this name to call
+  pub fn call_params(&self, name: &str) -> Option<Vec<FunctionArg>> {
+    if let Some((enum_, field_name)) = self.enum_field_ref(name) {
+      let mut res: Vec<FunctionArg> = vec![];
+      for data in &enum_.field(field_name)?.payload {
+        res.push(FunctionArg {
+          name: data.name.clone(),
+          type_: data.type_.clone(),
+          default: None,
+          variadic: false,
+          location: data.location.clone(),
+        });
+      }
+      return Some(res);
+    }
+
+    Some(self.function(name)?.args)
+  }
+  /// Returns the arguments of a function call in the order of the function arguments,
+  /// named arguments are moved to their place and missing arguments are filled in with their default value.
+  /// The values of a variadic argument are placed at the end, a spread `foo(...bar)` is not included
+  pub fn ordered_arguments(
+    &self,
+    call: &ActionFunctionCall,
+  ) -> Result<Vec<Action>, AnylizeErrAndWarns> {
+    let mut params = match self.call_params(&call.name) {
+      Some(params) => params,
+      None => return Ok(call.arguments.clone()),
+    };
+    let variadic = match params.last() {
+      Some(param) if param.variadic => params.pop(),
+      _ => None,
+    };
+
+    let mut tail: Vec<Action> = vec![];
+    if call.arguments.len() > params.len() {
+      if variadic.is_none() {
+        return Err(AnylizeErrAndWarns::ArityMismatch(
+          params.len(),
+          call.arguments.len(),
+        ));
+      }
+      tail = call.arguments[params.len()..].to_vec();
+    }
+
+    if call.spread.is_some() {
+      if variadic.is_none() {
+        return Err(AnylizeErrAndWarns::SpreadWithoutVariadicArgument);
+      }
+      if !tail.is_empty() {
+        return Err(AnylizeErrAndWarns::SpreadWithVariadicValues);
+      }
+    }
+
+    let mut res: Vec<Option<Action>> = vec![None; params.len()];
+    for (i, argument) in call.arguments.iter().take(params.len()).enumerate() {
+      res[i] = Some(argument.clone());
+    }
+
+    for (name, argument) in &call.named_arguments {
+      let index = match params.iter().position(|param| &param.name == name) {
+        Some(index) => index,
+        None => {
+          return Err(match &variadic {
+            Some(variadic) if &variadic.name == name => {
+              AnylizeErrAndWarns::VariadicArgumentByName(name.clone())
+            }
+            _ => AnylizeErrAndWarns::ArgumentDoesNotExist(name.clone()),
+          })
+        }
+      };
+      if res[index].is_some() {
+        return Err(AnylizeErrAndWarns::ArgumentAlreadySet(name.clone()));
+      }
+      res[index] = Some(argument.clone());
+    }
+
+    let mut arguments: Vec<Action> = vec![];
+    for (argument, param) in res.into_iter().zip(params) {
+      match argument.or(param.default) {
+        Some(argument) => arguments.push(argument),
+        None => return Err(AnylizeErrAndWarns::MissingArgument(param.name)),
+      }
+    }
+    arguments.append(&mut tail);
+    Ok(arguments)
+  }
+  /// The arguments are passed in the order of the function arguments, when a named argument that calls a function
+  /// is written before a named argument that comes earlier the functions would be called in the wrong order.
+  /// In that case the arguments that call functions are replaced by the temporaries `__arg0`, `__arg1`, ..
+  /// and the temporaries are returned in the order they are written with their type and value,
+  /// the targets evaluate these before the call
+  pub fn bind_arguments(&self, call: &mut ActionFunctionCall) -> Vec<(String, Type, Action)> {
+    let params = match self.call_params(&call.name) {
+      Some(params) if !params.is_empty() => params,
+      _ => return vec![],
+    };
+
+    let mut last_call_index: Option<usize> = None;
+    let mut out_of_order = false;
+    for (name, argument) in &call.named_arguments {
+      if !contains_call(argument) {
+        continue;
+      }
+      let index = params.iter().position(|param| &param.name == name);
+      if last_call_index > index {
+        out_of_order = true;
+      }
+      last_call_index = index;
+    }
+    if !out_of_order {
+      return vec![];
+    }
+
+    let mut temporaries: Vec<(String, Type, Action)> = vec![];
+    let mut bind = |argument: &mut Action, type_: &Type| {
+      if !contains_call(argument) {
+        return;
+      }
+      let name = format!("__arg{}", temporaries.len());
+      let temporary = Action {
+        location: argument.location.clone(),
+        type_: ActionType::VarRef(name.clone()),
+        attributes: vec![],
+      };
+      let value = std::mem::replace(argument, temporary);
+      temporaries.push((name, type_.clone(), value));
+    };
+    for (i, argument) in call.arguments.iter_mut().enumerate() {
+      // The values after the arguments go to the variadic argument
+      bind(argument, &params[i.min(params.len() - 1)].type_);
+    }
+    for (name, argument) in &mut call.named_arguments {
+      if let Some(param) = params.iter().find(|param| &param.name == name) {
+        bind(argument, &param.type_);
+      }
+    }
+    temporaries
+  }
+  /// Returns the native symbol and the import of the extern function name for lang
+  pub fn extern_binding(&self, name: &str, lang: &Lang) -> Option<(String, Option<String>)> {
+    let function = self.function(name)?;
+    if !function.external {
+      return None;
+    }
+    let binding = binding(&function.attributes, lang.name())?;
+    Some((binding.symbol.to_string(), binding.import.map(String::from)))
+  }
+  /// Removes the items and statements that are not build for lang
+  pub fn retain_target(&mut self, lang: &Lang) {
+    self
+      .functions
+      .retain(|_, function| lang.matches(&function.attributes));
+    self.vars.retain(|_, var| lang.matches(&var.attributes));
+    self
+      .structs
+      .retain(|_, struct_| lang.matches(&struct_.attributes));
+    self
+      .enums
+      .retain(|_, enum_| lang.matches(&enum_.attributes));
+    self
+      .types
+      .retain(|_, type_| lang.matches(&type_.attributes));
+    for function in self.functions.values_mut() {
+      function
+        .body
+        .retain(&|action: &Action| lang.matches(&action.attributes));
+    }
+  }
 }
 
-impl<'a> fmt::Debug for AnilizedTokens {
+impl fmt::Debug for AnilizedTokens {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    let simple = SimpleAnilizedTokens {
-      functions: &self.functions,
-      vars: &self.vars,
-      structs: &self.structs,
-      enums: &self.enums,
-      types: &self.types,
-      imports: &self.imports,
-    };
-    writeln!(f, "{:#?}", simple)
+    f.debug_struct("AnilizedTokens")
+      .field("functions", &self.functions)
+      .field("vars", &self.vars)
+      .field("structs", &self.structs)
+      .field("enums", &self.enums)
+      .field("types", &self.types)
+      .field("imports", &self.imports)
+      .finish()
   }
 }
 
 pub fn anilize_tokens(
   compiler: &mut Compiler,
-  tokenizer: Tokenizer,
+  mut tokenizer: Tokenizer,
+  modules: HashMap<String, Rc<AnilizedTokens>>,
 ) -> (AnilizedTokens, AnylizeResults) {
+  let files = compiler
+    .opened_files
+    .values()
+    .map(|file| (file.id, file.clone()))
+    .collect();
+  let mut anilized_res = AnylizeResults::new(files, compiler.options.deny_unused);
+
+  // The attributes are checked before the items for other targets are removed
+  anilized_res.check_all_attributes(&tokenizer);
+  let lang = compiler.options.lang.clone();
+  tokenizer.functions = filter_targets(tokenizer.functions, &lang);
+  tokenizer.vars = filter_targets(tokenizer.vars, &lang);
+  tokenizer.structs = filter_targets(tokenizer.structs, &lang);
+  tokenizer.enums = filter_targets(tokenizer.enums, &lang);
+  tokenizer.types = filter_targets(tokenizer.types, &lang);
+  if let Some(lang) = &lang {
+    for function in &mut tokenizer.functions {
+      function
+        .body
+        .retain(&|action: &Action| lang.matches(&action.attributes));
+    }
+  }
   let file = tokenizer.file;
 
-  let mut anilized_res = AnylizeResults::new(file.clone());
-
   let mut used_keys: HashSet<String> = HashSet::new();
 
   let imports = array_into_hash_map(
@@ -194,12 +774,43 @@ pub fn anilize_tokens(
     enums,
     types,
     imports,
+    consts: HashMap::new(),
+    modules,
+    symbols: HashMap::new(),
+    integer_divisions: vec![],
   };
   anilized_res.check_anilized_tokens(compiler, &mut res);
 
   (res, anilized_res)
 }
 
+/// Removes the items that are not build for the target language,
+/// without a target language the items of all targets are kept but only the first item with a name
+fn filter_targets<T>(items: Vec<T>, lang: &Option<Lang>) -> Vec<T>
+where
+  T: GetName + GetAttributes,
+{
+  let mut targeted_names: HashSet<String> = HashSet::new();
+  items
+    .into_iter()
+    .filter(|item| match lang {
+      Some(lang) => lang.matches(item.attributes()),
+      None if attribute_targets(item.attributes()).is_none() => true,
+      None => match item.name() {
+        Some(name) => targeted_names.insert(name),
+        None => true,
+      },
+    })
+    .collect()
+}
+
+#[derive(PartialEq)]
+enum AttributesOn {
+  Item,
+  ExternFunction,
+  Statement,
+}
+
 enum SnakeOrPascal {
   Snake,
   Pascal,
@@ -233,6 +844,11 @@ where
       continue;
     }
 
+    if is_reserved_name(&name) {
+      anilized_res.add(AnylizeErrAndWarns::ReservedName, &item.location());
+      continue;
+    }
+
     if let SnakeOrPascal::Snake = name_should_be {
       if !is_snake_case(&name) {
         anilized_res.add(AnylizeErrAndWarns::NameShouldBeSnakeCase, &item.location());
@@ -249,66 +865,93 @@ where
 }
 
 impl AnylizeResults {
-  fn new(file: File) -> Self {
+  fn new(files: HashMap<usize, File>, deny_unused: bool) -> Self {
     Self {
-      file,
+      files,
+      deny_unused,
       warnings: vec![],
       errors: vec![],
     }
   }
-  fn add(&mut self, item: AnylizeErrAndWarns, location: &CodeLocation) {
-    let error = self.file.must_error(item.clone(), location.clone());
+  /// Returns the error shown in the file the location points to
+  fn location_error(&self, item: AnylizeErrAndWarns, location: &Span) -> LocationError {
+    self.files[&location.file_id].must_error(item, location.clone())
+  }
+  fn add(&mut self, item: AnylizeErrAndWarns, location: &Span) {
+    let error = self.location_error(item.clone(), location);
     if item.is_warning() {
       self.warnings.push(error);
     } else {
       self.errors.push(error);
     }
   }
+  /// Adds a warning about something that is never used, this is an error if unused items are denied
+  fn add_unused(&mut self, item: AnylizeErrAndWarns, location: &Span) {
+    if self.deny_unused {
+      let error = self.location_error(item, location);
+      self.errors.push(error);
+    } else {
+      self.add(item, location);
+    }
+  }
 
   fn check_anilized_tokens(&mut self, compiler: &mut Compiler, data: &mut AnilizedTokens) {
-    if data.imports.len() > 0 {
-      let mut path = Path::from(&self.file.name);
-      path.pop(); // Remove the filename from the path
-
-      for (_, import) in data.imports.clone() {
-        let mut cloned_path = path.clone();
-        cloned_path.push(import.path.content);
-
-        compiler.add_work(Work::ParseFile(cloned_path))
-      }
-    }
+    self.resolve_types(data);
+    self.check_recursive_types(data);
+    data.consts = self.evaluate_consts(data);
 
     // Check the global functions
-    for (_, function) in data.functions.clone() {
+    let mut symbols: HashMap<String, SymbolTable> = HashMap::new();
+    let mut integer_divisions: Vec<Span> = vec![];
+    for (function_name, function) in data.functions.clone() {
       let mut check_state = CheckActionState::new(data);
+      check_state.return_type = function.res.clone();
+      check_state.throws = function.throws;
+
+      // Default values are filled in by the caller so they are checked before the arguments exist
+      for arg in &function.args {
+        if let Some(default) = &arg.default {
+          self.check_action(default.clone(), &mut check_state);
+        }
+      }
 
-      if function.args.len() > 0 {
+      if !function.args.is_empty() {
         // check the function arguments
         let mut used_arg_names: Vec<String> = vec![];
-        for (arg_name, arg_type) in function.args {
-          if used_arg_names.contains(&arg_name) {
-            // TODO: use the location of the name here
-            self.add(AnylizeErrAndWarns::AlreadyDefined, &function.location);
-            continue;
+        let args_len = function.args.len();
+        for (i, arg) in function.args.into_iter().enumerate() {
+          if arg.variadic && i + 1 != args_len {
+            self.add(AnylizeErrAndWarns::VariadicArgumentNotLast, &arg.location);
           }
-
-          if !is_snake_case(&arg_name) {
-            // TODO: use the location of the name here
+          if arg.variadic && arg.default.is_some() {
             self.add(
-              AnylizeErrAndWarns::NameShouldBeSnakeCase,
-              &function.location,
+              AnylizeErrAndWarns::VariadicArgumentWithDefault,
+              &arg.location,
             );
           }
-          used_arg_names.push(arg_name.clone());
-          check_state.vars.insert(
-            arg_name,
-            VariableDetials {
-              global: false,
-              mutatable: false,
-            },
-          );
 
-          self.check_type(arg_type);
+          if used_arg_names.contains(&arg.name) {
+            self.add(AnylizeErrAndWarns::AlreadyDefined, &arg.location);
+            continue;
+          }
+
+          if !is_snake_case(&arg.name) {
+            self.add(AnylizeErrAndWarns::NameShouldBeSnakeCase, &arg.location);
+          }
+          if is_reserved_name(&arg.name) {
+            self.add(AnylizeErrAndWarns::ReservedName, &arg.location);
+          }
+          used_arg_names.push(arg.name.clone());
+          let mut symbol = Symbol::new(&arg.name, SymbolKind::Argument, &arg.location);
+          // Inside the function a variadic argument is an array of the values passed
+          symbol.type_ = Some(if arg.variadic {
+            new_type(TypeType::Array(Box::new(arg.type_.clone())), &arg.location)
+          } else {
+            arg.type_.clone()
+          });
+          check_state.scopes.declare(symbol);
+
+          self.check_type(arg.type_);
         }
       }
 
@@ -320,30 +963,91 @@ impl AnylizeResults {
             &function.location,
           );
         }
+
+        if name == "main" && function.throws {
+          self.add(AnylizeErrAndWarns::MainCannotThrow, &function.location);
+        }
       }
 
-      for (var_name, _) in &data.vars {
-        check_state.vars.insert(
-          var_name.clone(),
-          VariableDetials {
-            global: true,
-            mutatable: false,
-          },
-        );
+      if function.external {
+        let has_bindings = function
+          .attributes
+          .iter()
+          .any(|attribute| attribute.name == "bind");
+        match &compiler.options.lang {
+          _ if !has_bindings => {
+            self.add(AnylizeErrAndWarns::ExternWithoutBinding, &function.location)
+          }
+          Some(lang) if binding(&function.attributes, lang.name()).is_none() => self.add(
+            AnylizeErrAndWarns::MissingBinding(lang.name()),
+            &function.location,
+          ),
+          _ => {}
+        }
+      }
+
+      match function.res.clone() {
+        // Returning multiple values is the only place a tuple type is allowed
+        Some(Type {
+          type_: TypeType::Tuple(types),
+          ..
+        }) => {
+          for type_ in types {
+            self.check_type(type_);
+          }
+        }
+        Some(res) => self.check_type(res),
+        None => {}
+      }
+
+      if let Some(res) = &function.res {
+        if !function.external && !always_returns(&function.body) {
+          self.add(
+            AnylizeErrAndWarns::MissingReturn(res.to_string()),
+            &function.location,
+          );
+        }
+      }
+
+      self.check_labels(&function.body);
+      check_state.function_scope = true;
+      for action in function.body.actions {
+        self.check_statement(action, &mut check_state);
       }
 
-      self.check_actions(function.body, &mut check_state)
+      let table = check_state.scopes.into_table();
+      // Extern functions have no body that could use the arguments
+      if !function.external {
+        for symbol in &table.symbols {
+          if !symbol.references.is_empty() || symbol.name.starts_with('_') {
+            continue;
+          }
+          self.add_unused(
+            if let SymbolKind::Argument = symbol.kind {
+              AnylizeErrAndWarns::UnusedArgument(symbol.name.clone())
+            } else {
+              AnylizeErrAndWarns::UnusedVariable(symbol.name.clone())
+            },
+            &symbol.location,
+          );
+        }
+      }
+      symbols.insert(function_name, table);
+      integer_divisions.append(&mut check_state.integer_divisions);
     }
+    data.symbols = symbols;
+    data.integer_divisions = integer_divisions;
 
     // Check the global enums
     for (_, enum_) in data.enums.clone() {
-      if enum_.fields.len() == 0 {
+      if enum_.fields.is_empty() {
         // TODO: Use the location of the fields here instaid of the enum
         self.add(AnylizeErrAndWarns::EmptyEnum, &enum_.location);
         continue;
       }
 
       // Check the enum fields
+      let has_payload = enum_.has_payload();
       let mut used_field_names: Vec<String> = vec![];
       for field in enum_.fields {
         if used_field_names.contains(&field.name) {
@@ -354,6 +1058,11 @@ impl AnylizeResults {
         if !is_snake_case(&field.name) {
           self.add(AnylizeErrAndWarns::NameShouldBeSnakeCase, &field.location);
         }
+        if has_payload && field.value.is_some() {
+          self.add(AnylizeErrAndWarns::EnumValueNotAllowed, &field.location);
+        }
+
+        self.check_fields(field.payload);
       }
     }
 
@@ -361,105 +1070,852 @@ impl AnylizeResults {
     for (_, struct_) in data.structs.clone() {
       self.check_struct(struct_, false);
     }
-  }
 
-  fn check_type(&mut self, type_: Type) {
-    match type_.type_ {
-      TypeType::Struct(struct_) => self.check_struct(struct_, true),
-      TypeType::Array(array_type) => self.check_type(*array_type),
-      _ => {}
+    // Check the global types and the types of the constants
+    for (_, global_type) in data.types.clone() {
+      self.check_type(global_type.type_);
     }
+    for (_, var) in data.vars.clone() {
+      if let Some(data_type) = var.data_type {
+        self.check_type(data_type);
+      }
+    }
+
+    // Only the functions of the file we compile are checked as imported files have their functions used by the importer
+    self.check_unused_items(data, compiler.compiling.is_empty());
   }
 
-  fn check_struct(&mut self, struct_: Struct, is_inline: bool) {
-    let mut used_names: Vec<String> = vec![];
-    for field in struct_.fields {
-      if used_names.contains(&field.name) {
-        // Check if the struct name issn't already used
-        self.add(AnylizeErrAndWarns::NameAlreadyExists, &field.location);
-        continue;
-      }
-      used_names.push(field.name.clone());
-      if !is_snake_case(&field.name) {
-        // Check if the struct field is snake case
-        self.add(AnylizeErrAndWarns::NameShouldBeSnakeCase, &field.location);
-      }
+  /// Reports the imports and functions that are never referenced
+  fn check_unused_items(&mut self, data: &AnilizedTokens, check_functions: bool) {
+    let names = referenced_names(data);
 
-      self.check_type(field.type_);
+    for (alias, import) in &data.imports {
+      let prefix = format!("{}.", alias);
+      if !names.iter().any(|name| name.starts_with(&prefix)) {
+        self.add_unused(
+          AnylizeErrAndWarns::UnusedImport(alias.clone()),
+          &import.location,
+        );
+      }
     }
 
-    if let Some(name) = &struct_.name {
-      if is_inline {
-        self.add(AnylizeErrAndWarns::NamingNotAllowed, &struct_.location);
-        return;
+    if check_functions {
+      for (name, function) in &data.functions {
+        if name != "main" && !name.starts_with('_') && !names.contains(name) {
+          self.add_unused(
+            AnylizeErrAndWarns::UnusedFunction(name.clone()),
+            &function.location,
+          );
+        }
       }
+    }
+  }
 
-      // check if the struct name is in snake case
-      if !is_pascal_case(name) {
-        self.add(
-          AnylizeErrAndWarns::NameShouldBePascalCase,
-          &struct_.location,
-        );
+  /// Looks up what every type reference points to and reports the references to types that don't exist
+  fn resolve_types(&mut self, data: &mut AnilizedTokens) {
+    let symbols = type_symbols(data);
+    let mut unknown: Vec<Type> = vec![];
+    let mut resolve = |type_: &mut Type| resolve_type_refs(type_, &symbols, &mut unknown);
+
+    for function in data.functions.values_mut() {
+      for arg in &mut function.args {
+        resolve(&mut arg.type_);
       }
-    } else if !is_inline {
-      self.add(AnylizeErrAndWarns::NoName, &struct_.location);
-      return;
+      if let Some(res) = &mut function.res {
+        resolve(res);
+      }
+      // Variables are only declared as statements so they are always directly inside a block
+      function.body.walk_blocks_mut(&mut |block| {
+        for action in &mut block.actions {
+          if let ActionType::Variable(Variable {
+            data_type: Some(data_type),
+            ..
+          }) = &mut action.type_
+          {
+            resolve(data_type);
+          }
+          if let ActionType::VariableTuple(VariableTuple {
+            data_type: Some(data_type),
+            ..
+          }) = &mut action.type_
+          {
+            resolve(data_type);
+          }
+        }
+      });
+    }
+    for var in data.vars.values_mut() {
+      if let Some(data_type) = &mut var.data_type {
+        resolve(data_type);
+      }
+    }
+    for struct_ in data.structs.values_mut() {
+      for field in &mut struct_.fields {
+        resolve(&mut field.type_);
+      }
+    }
+    for enum_ in data.enums.values_mut() {
+      for field in &mut enum_.fields {
+        for data in &mut field.payload {
+          resolve(&mut data.type_);
+        }
+      }
+    }
+    for global_type in data.types.values_mut() {
+      resolve(&mut global_type.type_);
     }
-  }
 
-  fn check_actions(&mut self, actions: Actions, state: &mut CheckActionState) {
-    let mut new_state = state.clone();
-    for action in actions.actions {
-      self.check_action(action, &mut new_state)
+    for type_ in unknown {
+      let name = type_.to_string();
+      self.add(
+        data.missing_ref_error(&name, AnylizeErrAndWarns::TypeDoesNotExist(name.clone())),
+        &type_.location,
+      );
     }
   }
 
-  fn check_action(&mut self, action: Action, state: &mut CheckActionState) {
-    // TODO: Disallow some things when this is a inline action
+  /// Reports the structs and types that contain themselves, the error is placed on the first type of the cycle
+  fn check_recursive_types(&mut self, data: &AnilizedTokens) {
+    for cycle in recursive_types(data) {
+      let location = match data.structs.get(&cycle[0]) {
+        Some(struct_) => struct_.location.clone(),
+        None => data.types[&cycle[0]].location.clone(),
+      };
+      // Arrays don't help for aliases as they can't refer to themselves at all
+      let only_aliases = cycle
+        .iter()
+        .all(|name| data.types.get(name).is_some_and(|type_| type_.alias));
+      let chain = cycle.join(" -> ");
+      self.add(
+        if only_aliases {
+          AnylizeErrAndWarns::RecursiveAlias(chain)
+        } else {
+          AnylizeErrAndWarns::RecursiveType(chain)
+        },
+        &location,
+      );
+    }
+  }
 
-    if state.unreachable_code {
-      self.add(AnylizeErrAndWarns::UnreachableCode, &action.location);
+  /// Checks the attributes of all items and statements, including the ones for other targets
+  fn check_all_attributes(&mut self, tokenizer: &Tokenizer) {
+    for function in &tokenizer.functions {
+      self.check_attributes(
+        &function.attributes,
+        if function.external {
+          AttributesOn::ExternFunction
+        } else {
+          AttributesOn::Item
+        },
+      );
+      function
+        .body
+        .walk(&mut |action| self.check_attributes(&action.attributes, AttributesOn::Statement));
+    }
+    for var in &tokenizer.vars {
+      self.check_attributes(&var.attributes, AttributesOn::Item);
+    }
+    for struct_ in &tokenizer.structs {
+      self.check_attributes(&struct_.attributes, AttributesOn::Item);
+    }
+    for enum_ in &tokenizer.enums {
+      self.check_attributes(&enum_.attributes, AttributesOn::Item);
     }
+    for type_ in &tokenizer.types {
+      self.check_attributes(&type_.attributes, AttributesOn::Item);
+    }
+  }
 
-    match action.type_ {
-      ActionType::Variable(var) => {
-        if let Some(already_defined_var) = state.vars.get(&var.name) {
-          if !already_defined_var.global {
+  fn check_attributes(&mut self, attributes: &[Attribute], on: AttributesOn) {
+    for attribute in attributes {
+      match attribute.name.as_str() {
+        "target" if attribute.args.is_empty() => self.add(
+          AnylizeErrAndWarns::InvalidAttributeArgs(attribute.name.clone()),
+          &attribute.location,
+        ),
+        "target" => {
+          for arg in &attribute.args {
+            if Lang::from_name(arg).is_none() {
+              self.add(
+                AnylizeErrAndWarns::UnknownTarget(arg.clone()),
+                &attribute.location,
+              );
+            }
+          }
+        }
+        "deprecated" if on == AttributesOn::Statement => self.add(
+          AnylizeErrAndWarns::AttributeNotAllowed(attribute.name.clone()),
+          &attribute.location,
+        ),
+        "deprecated" if attribute.args.len() > 1 => self.add(
+          AnylizeErrAndWarns::InvalidAttributeArgs(attribute.name.clone()),
+          &attribute.location,
+        ),
+        "deprecated" => {}
+        "bind" if on != AttributesOn::ExternFunction => self.add(
+          AnylizeErrAndWarns::AttributeNotAllowed(attribute.name.clone()),
+          &attribute.location,
+        ),
+        "bind" if attribute.args.len() < 2 || attribute.args.len() > 3 => self.add(
+          AnylizeErrAndWarns::InvalidAttributeArgs(attribute.name.clone()),
+          &attribute.location,
+        ),
+        "bind" => {
+          if Lang::from_name(&attribute.args[0]).is_none() {
             self.add(
-              AnylizeErrAndWarns::VariableAlreadyDeclared,
-              &action.location,
+              AnylizeErrAndWarns::UnknownTarget(attribute.args[0].clone()),
+              &attribute.location,
             );
-            return;
           }
         }
+        _ => self.add(
+          AnylizeErrAndWarns::UnknownAttribute(attribute.name.clone()),
+          &attribute.location,
+        ),
+      }
+    }
+  }
 
-        state.vars.insert(
-          var.name,
-          VariableDetials {
-            global: false,
-            mutatable: if let VarType::Let = var.var_type {
-              true
-            } else {
-              false
-            },
+  /// Warns about using an item with a `#[deprecated]` attribute
+  fn check_deprecated(&mut self, name: &str, attributes: &[Attribute], location: &Span) {
+    if let Some(attribute) = deprecated_attribute(attributes) {
+      self.add(
+        AnylizeErrAndWarns::Deprecated(name.to_string(), attribute.args.first().cloned()),
+        location,
+      );
+    }
+  }
+
+  /// Folds the global constants and enum values into their values
+  fn evaluate_consts(&mut self, data: &AnilizedTokens) -> HashMap<String, ConstValue> {
+    let mut evaluator = ConstEvaluator::new(&data.vars);
+
+    // Enum values can use constants so we evaluate the constants first,
+    // the names are sorted so errors are always reported in the same order
+    let mut vars: Vec<(&String, &Variable)> = data.vars.iter().collect();
+    vars.sort_by_key(|(name, _)| *name);
+    for (name, var) in vars {
+      if let Err(Some((err, location))) = evaluator.const_value(name, &var.location) {
+        self.add(err, &location);
+      }
+    }
+
+    // A field can use the values of the fields above it
+    for (enum_name, enum_) in &data.enums {
+      evaluator.set_enum(Some(enum_name.clone()));
+      let is_string = enum_.fields.iter().any(|field| {
+        matches!(
+          field.value,
+          Some(Action {
+            type_: ActionType::StaticString(_),
+            ..
+          })
+        )
+      });
+      let mut previous: Option<ConstValue> = None;
+      for field in &enum_.fields {
+        let name = format!("{}.{}", enum_name, field.name);
+        let value = match &field.value {
+          Some(value) => {
+            let res = evaluator.evaluate(value);
+            if let Err(Some((err, location))) = &res {
+              self.add(err.clone(), location);
+            }
+            res.ok()
+          }
+          // The fields of string enums without a value are their name,
+          // other fields without a value continue from the field above them
+          None if is_string => Some(ConstValue::String(field.name.clone())),
+          None => match &previous {
+            None => Some(ConstValue::Int(0)),
+            Some(ConstValue::Int(value)) => Some(ConstValue::Int(value + 1)),
+            Some(ConstValue::Float(value)) => Some(ConstValue::Float(value + 1.0)),
+            Some(ConstValue::String(_)) => Some(ConstValue::String(field.name.clone())),
+            Some(ConstValue::Bool(_)) => None,
           },
+        };
+        evaluator.set_value(name, value.clone());
+        previous = value;
+      }
+    }
+    evaluator.set_enum(None);
+
+    evaluator.values()
+  }
+  fn check_type(&mut self, type_: Type) {
+    match type_.type_ {
+      TypeType::Struct(struct_) => self.check_struct(struct_, true),
+      TypeType::Array(array_type) => self.check_type(*array_type),
+      TypeType::Tuple(_) => self.add(AnylizeErrAndWarns::TupleNotAllowed, &type_.location),
+      _ => {}
+    }
+  }
+
+  fn check_struct(&mut self, struct_: Struct, is_inline: bool) {
+    self.check_fields(struct_.fields);
+
+    if let Some(name) = &struct_.name {
+      if is_inline {
+        self.add(AnylizeErrAndWarns::NamingNotAllowed, &struct_.location);
+        return;
+      }
+
+      // check if the struct name is in snake case
+      if !is_pascal_case(name) {
+        self.add(
+          AnylizeErrAndWarns::NameShouldBePascalCase,
+          &struct_.location,
         );
+      }
+    } else if !is_inline {
+      self.add(AnylizeErrAndWarns::NoName, &struct_.location);
+    }
+  }
+
+  fn check_fields(&mut self, fields: Vec<StructField>) {
+    let mut used_names: Vec<String> = vec![];
+    for field in fields {
+      if used_names.contains(&field.name) {
+        // Check if the field name issn't already used
+        self.add(AnylizeErrAndWarns::NameAlreadyExists, &field.location);
+        continue;
+      }
+      used_names.push(field.name.clone());
+      if !is_snake_case(&field.name) {
+        // Check if the field is snake case
+        self.add(AnylizeErrAndWarns::NameShouldBeSnakeCase, &field.location);
+      }
+
+      self.check_type(field.type_);
+    }
+  }
+
+  /// Checks the actions of a block, the variables declared inside the block only exist within it
+  fn check_actions(&mut self, actions: Actions, state: &mut CheckActionState) {
+    // Only the actions directly inside the function body are in the function scope
+    let function_scope = state.function_scope;
+    let unreachable_code = state.unreachable_code;
+    let unreachable_reported = state.unreachable_reported;
+    state.function_scope = false;
+    state.scopes.push();
+    for action in actions.actions {
+      self.check_statement(action, state);
+    }
+    state.scopes.pop();
+    state.function_scope = function_scope;
+    state.unreachable_code = unreachable_code;
+    state.unreachable_reported = unreachable_reported;
+  }
+  /// Checks an action that is a statement of a block,
+  /// the first statement that can never run gets a warning and the code after and inside of it doesn't
+  fn check_statement(&mut self, action: Action, state: &mut CheckActionState) {
+    if state.unreachable_code && !state.unreachable_reported {
+      self.add(AnylizeErrAndWarns::UnreachableCode, &action.location);
+      state.unreachable_reported = true;
+    }
+    self.check_action_handling_errors(action, state, true)
+  }
+
+  /// Declares a local variable in the innermost scope, locals can shadow globals but not other locals or arguments.
+  /// Returns false if the name is already used
+  fn declare_local(&mut self, symbol: Symbol, state: &mut CheckActionState) -> bool {
+    if symbol.name == "_" {
+      // The value is ignored
+      return true;
+    }
+    if is_reserved_name(&symbol.name) {
+      self.add(AnylizeErrAndWarns::ReservedName, &symbol.location);
+      return false;
+    }
+    if state.scopes.get(&symbol.name).is_some() {
+      self.add(
+        AnylizeErrAndWarns::VariableAlreadyDeclared,
+        &symbol.location,
+      );
+      return false;
+    }
+    state.scopes.declare(symbol);
+    true
+  }
+
+  fn check_loop_body(
+    &mut self,
+    actions: Actions,
+    label: Option<String>,
+    state: &mut CheckActionState,
+  ) {
+    let inside_a_loop = state.inside_a_loop;
+    let labels_len = state.loop_labels.len();
+    state.inside_a_loop = true;
+    if let Some(label) = label {
+      state.loop_labels.push(label);
+    }
+    self.check_actions(actions, state);
+    state.loop_labels.truncate(labels_len);
+    state.inside_a_loop = inside_a_loop;
+  }
+
+  /// Checks the labels of all loops inside a function,
+  /// go doesn't allow the same label twice inside a function and labels that are never used
+  fn check_labels(&mut self, body: &Actions) {
+    let mut defined: Vec<(String, Span)> = vec![];
+    let mut used: Vec<String> = vec![];
+    body.walk(&mut |action| match &action.type_ {
+      ActionType::For(ActionFor {
+        label: Some(label), ..
+      })
+      | ActionType::While(ActionWhile {
+        label: Some(label), ..
+      })
+      | ActionType::Loop(ActionLoop {
+        label: Some(label), ..
+      }) => defined.push((label.clone(), action.location.clone())),
+      ActionType::Break(Some(label)) | ActionType::Continue(Some(label)) => {
+        used.push(label.clone())
+      }
+      _ => {}
+    });
 
-        self.check_action(*var.action, state);
+    let mut used_labels: Vec<&String> = vec![];
+    for (label, location) in &defined {
+      if used_labels.contains(&label) {
+        self.add(AnylizeErrAndWarns::LabelAlreadyDefined, location);
+        continue;
+      }
+      used_labels.push(label);
+
+      if !used.contains(label) {
+        self.add(AnylizeErrAndWarns::UnusedLabel, location);
+      }
+    }
+  }
+
+  /// Checks an action on a place where handling the error of a function call is allowed,
+  /// `allow_catch` is false for places where catch can't be used like the return value
+  fn check_action_handling_errors(
+    &mut self,
+    action: Action,
+    state: &mut CheckActionState,
+    allow_catch: bool,
+  ) {
+    match action.type_ {
+      ActionType::Propagate(call) => {
+        if !state.throws {
+          self.add(AnylizeErrAndWarns::PropagateNotAllowed, &action.location);
+        }
+        self.check_handled_call(*call, state);
+      }
+      ActionType::Catch(catch) if allow_catch => {
+        self.check_handled_call(*catch.action, state);
+
+        state.scopes.push();
+        if let Some(name) = catch.error_name {
+          if !is_snake_case(&name) {
+            self.add(AnylizeErrAndWarns::NameShouldBeSnakeCase, &action.location);
+          }
+          let mut symbol = Symbol::new(name, SymbolKind::CatchError, &action.location);
+          // The error is passed around as a message
+          symbol.type_ = Some(new_type(TypeType::String, &action.location));
+          self.declare_local(symbol, state);
+        }
+        self.check_actions(catch.body, state);
+        state.scopes.pop();
+      }
+      _ => self.check_action(action, state),
+    }
+  }
+
+  /// Checks that the body of a catch used as value never continues, when the call fails there is no value to continue with
+  fn check_catch_value(&mut self, action: &Action) {
+    if let ActionType::Catch(catch) = &action.type_ {
+      if !always_leaves(&catch.body) {
+        self.add(AnylizeErrAndWarns::CatchWithoutValue, &action.location);
+      }
+    }
+  }
+
+  /// Checks a function call of which the error is handled by the caller
+  fn check_handled_call(&mut self, call: Action, state: &mut CheckActionState) {
+    let throws = match &call.type_ {
+      ActionType::FunctionCall(data) => match state.anilized_tokens.function(&data.name) {
+        Some(function) => function.throws,
+        // The function call check will report this
+        None => true,
+      },
+      _ => false,
+    };
+    if !throws {
+      self.add(AnylizeErrAndWarns::NothingToHandle, &call.location);
+    }
+
+    state.error_handled = true;
+    self.check_action(call, state);
+    state.error_handled = false;
+  }
+
+  /// Checks if the ordered arguments and the spread of a function call match the types of the function arguments
+  fn check_argument_types(
+    &mut self,
+    call: &ActionFunctionCall,
+    arguments: &[Action],
+    state: &CheckActionState,
+  ) {
+    let params = match state.anilized_tokens.call_params(&call.name) {
+      Some(params) => params,
+      None => return,
+    };
+    for (i, argument) in arguments.iter().enumerate() {
+      let param = match params.get(i).or(params.last()) {
+        Some(param) if i < params.len() || param.variadic => param,
+        _ => break,
+      };
+      self.check_value_type(&param.type_, argument, state);
+    }
+
+    if let (Some(spread), Some(variadic)) =
+      (&call.spread, params.last().filter(|param| param.variadic))
+    {
+      // Only arrays can be spread
+      let array_type = Type {
+        location: variadic.type_.location.clone(),
+        type_: TypeType::Array(Box::new(variadic.type_.clone())),
+      };
+      self.check_value_type(&array_type, spread, state);
+    }
+  }
+  /// Checks if the value of action can be used where a value of type expected is expected,
+  /// the items of a tuple are checked one by one so the error points to the wrong item
+  fn check_value_type(&mut self, expected: &Type, action: &Action, state: &CheckActionState) {
+    if !self.check_has_value(action, state) {
+      return;
+    }
+    if let ActionType::Tuple(items) = &action.type_ {
+      if let TypeType::Tuple(types) = resolve_aliases(expected, state.anilized_tokens).type_ {
+        if types.len() == items.len() {
+          for (type_, item) in types.iter().zip(items.iter()) {
+            self.check_value_type(type_, item, state);
+          }
+          return;
+        }
+      }
+    }
+
+    if let Err(found) = value_fits_type(action, expected, state) {
+      self.add(
+        AnylizeErrAndWarns::TypeMismatch(expected.to_string(), found.to_string()),
+        &action.location,
+      );
+    }
+  }
+  /// Checks if the action results in a value, calls to functions without a return type don't
+  fn check_has_value(&mut self, action: &Action, state: &CheckActionState) -> bool {
+    if action_arity(action, state) == Some(0) {
+      self.add(AnylizeErrAndWarns::NoValue, &action.location);
+      false
+    } else {
+      true
+    }
+  }
+  /// Checks if both sides of an operator have the same type, `&&` and `||` only work on booleans
+  fn check_operands(&mut self, operation: &ActionOperation, state: &CheckActionState) {
+    let (left, right) = (&*operation.left, &*operation.right);
+    match operation.operator {
+      Operator::And | Operator::Or => {
+        let bool_type = new_type(TypeType::Bool, &left.location);
+        self.check_value_type(&bool_type, left, state);
+        self.check_value_type(&bool_type, right, state);
+      }
+      _ => {
+        // Static numbers fit multiple types so the other side decides the type
+        if let Some(type_) = typed_action_type(left, state) {
+          self.check_value_type(&type_, right, state);
+        } else if let Some(type_) = typed_action_type(right, state) {
+          self.check_value_type(&type_, left, state);
+        }
+      }
+    }
+  }
+  /// Checks if the value of an unary operator has the right type, `!` only works on booleans and `-` on numbers
+  fn check_unary_operand(&mut self, unary: &ActionUnary, state: &CheckActionState) {
+    let operand = &*unary.action;
+    match unary.operator {
+      UnaryOperator::Not => {
+        self.check_value_type(&new_type(TypeType::Bool, &operand.location), operand, state)
+      }
+      UnaryOperator::Negate => {
+        if !self.check_has_value(operand, state) {
+          return;
+        }
+        if let Some(found) = action_type(operand, state) {
+          if !is_number_type(&underlying_type(&found, state.anilized_tokens)) {
+            self.add(
+              AnylizeErrAndWarns::ExpectedNumber(found.to_string()),
+              &operand.location,
+            );
+          }
+        }
+      }
+    }
+  }
+  /// Warns about comparing a value with itself `a == a`,
+  /// floats are left out as `a != a` is the way to check for NaN
+  fn check_self_comparison(
+    &mut self,
+    operation: &ActionOperation,
+    location: &Span,
+    state: &CheckActionState,
+  ) {
+    let result = match self_comparison(&operation.operator) {
+      Some(result) if same_value(&operation.left, &operation.right) => result,
+      _ => return,
+    };
+    let left_type = action_type(&operation.left, state)
+      .map(|type_| underlying_type(&type_, state.anilized_tokens).type_);
+    let is_float = matches!(left_type, Some(TypeType::F32) | Some(TypeType::F64));
+    if !is_float {
+      self.add(AnylizeErrAndWarns::SelfComparison(result), location);
+    }
+  }
+  /// Warns about a condition that is always true or false and returns its value
+  fn check_constant_condition(
+    &mut self,
+    condition: &Action,
+    state: &CheckActionState,
+  ) -> Option<bool> {
+    let constant = constant_condition(condition, state);
+    if let Some(value) = constant {
+      self.add(
+        AnylizeErrAndWarns::ConstantCondition(value),
+        &condition.location,
+      );
+    }
+    constant
+  }
+  /// Checks the body of an if branch, unreachable is true if the branch can never run
+  fn check_branch(&mut self, actions: Actions, unreachable: bool, state: &mut CheckActionState) {
+    let unreachable_code = state.unreachable_code;
+    state.unreachable_code |= unreachable;
+    self.check_actions(actions, state);
+    state.unreachable_code = unreachable_code;
+  }
+  /// Checks if the condition of an if or while is a boolean
+  fn check_condition(&mut self, condition: &Action, state: &CheckActionState) {
+    self.check_value_type(
+      &new_type(TypeType::Bool, &condition.location),
+      condition,
+      state,
+    );
+  }
+  fn check_action(&mut self, action: Action, state: &mut CheckActionState) {
+    // TODO: Disallow some things when this is a inline action
+
+    match action.type_ {
+      ActionType::Variable(var) => {
+        if state.scopes.get(&var.name).is_some() {
+          self.add(
+            AnylizeErrAndWarns::VariableAlreadyDeclared,
+            &action.location,
+          );
+          return;
+        }
+
+        // Variables without a type get the type of their value if we know it
+        let found_type = action_type(&var.action, state);
+        match &var.data_type {
+          Some(expected) => {
+            self.check_type(expected.clone());
+            self.check_value_type(expected, &var.action, state);
+          }
+          None => {
+            self.check_has_value(&var.action, state);
+            if let Some(found) = &found_type {
+              if contains_tuple(found) {
+                self.add(AnylizeErrAndWarns::TupleNotAllowed, &var.action.location);
+              }
+            }
+          }
+        }
+
+        // The value is checked before the variable exists so it can't refer to itself
+        let mut symbol = Symbol::new(var.name, SymbolKind::Variable, &action.location);
+        symbol.mutatable = matches!(var.var_type, VarType::Let);
+        symbol.type_ = var.data_type.or(found_type);
+        self.check_catch_value(&var.action);
+        self.check_action_handling_errors(*var.action, state, true);
+        self.declare_local(symbol, state);
+      }
+      ActionType::VariableTuple(var) => {
+        let value_arity_matches = match action_arity(&var.action, state) {
+          Some(found) if found != var.names.len() => {
+            self.add(
+              AnylizeErrAndWarns::ArityMismatch(var.names.len(), found),
+              &action.location,
+            );
+            false
+          }
+          _ => true,
+        };
+
+        if let Some(Type {
+          type_: TypeType::Tuple(types),
+          ..
+        }) = &var.data_type
+        {
+          for type_ in types {
+            self.check_type(type_.clone());
+          }
+        }
+        if let Some(expected) = &var.data_type {
+          let found = type_arity(expected, state.anilized_tokens);
+          if found != var.names.len() {
+            self.add(
+              AnylizeErrAndWarns::ArityMismatch(var.names.len(), found),
+              &expected.location,
+            );
+          } else if value_arity_matches {
+            self.check_value_type(expected, &var.action, state);
+          }
+        }
+
+        // The names get the types of the annotation or otherwise the types of the value if we know them
+        let types = match var
+          .data_type
+          .clone()
+          .or_else(|| action_type(&var.action, state))
+          .map(|type_| resolve_aliases(&type_, state.anilized_tokens).type_)
+        {
+          Some(TypeType::Tuple(types)) => types,
+          _ => vec![],
+        };
+
+        self.check_catch_value(&var.action);
+        self.check_action_handling_errors(*var.action, state, true);
+
+        for (i, name) in var.names.into_iter().enumerate() {
+          if name != "_" && !is_snake_case(&name) {
+            self.add(AnylizeErrAndWarns::NameShouldBeSnakeCase, &var.location);
+          }
+
+          let mut symbol = Symbol::new(name, SymbolKind::Variable, &var.location);
+          symbol.mutatable = matches!(var.var_type, VarType::Let);
+          symbol.type_ = types.get(i).cloned();
+          self.declare_local(symbol, state);
+        }
       }
       ActionType::Return(data) => {
-        // TODO: Check if this function actually expects response data
-        if let Some(action) = data {
-          self.check_action(*action, state);
+        match (data, &state.return_type) {
+          (Some(data), Some(return_type)) => {
+            let expected = type_arity(return_type, state.anilized_tokens);
+            match action_arity(&data, state) {
+              Some(found) if expected != found => self.add(
+                AnylizeErrAndWarns::ArityMismatch(expected, found),
+                &data.location,
+              ),
+              _ => self.check_value_type(return_type, &data, state),
+            }
+
+            self.check_action_handling_errors(*data, state, false);
+          }
+          (Some(data), None) => {
+            // Returning the result of a function that returns nothing is allowed
+            if action_arity(&data, state) != Some(0) {
+              self.add(AnylizeErrAndWarns::ReturnValueNotExpected, &data.location);
+            }
+            self.check_action_handling_errors(*data, state, false);
+          }
+          (None, Some(return_type)) => self.add(
+            AnylizeErrAndWarns::MissingReturnValue(return_type.to_string()),
+            &action.location,
+          ),
+          (None, None) => {}
+        }
+
+        state.unreachable_code = true;
+      }
+      ActionType::Defer(data) => {
+        if !state.function_scope {
+          self.add(AnylizeErrAndWarns::DeferNotAllowed, &action.location);
+        }
+        // A ? or catch on the deferred call is reported together with the call
+        let location = data.location.clone();
+        let (call, handled) = match data.type_ {
+          ActionType::Propagate(call) => (*call, true),
+          ActionType::Catch(catch) => (*catch.action, true),
+          type_ => (
+            Action {
+              type_,
+              location: data.location,
+              attributes: data.attributes,
+            },
+            false,
+          ),
+        };
+        let throws = match &call.type_ {
+          ActionType::FunctionCall(call) => match state.anilized_tokens.function(&call.name) {
+            Some(function) => function.throws,
+            None => false,
+          },
+          _ => false,
+        };
+        match &call.type_ {
+          ActionType::FunctionCall(call)
+            if state.anilized_tokens.enum_field_ref(&call.name).is_none() => {}
+          _ => self.add(AnylizeErrAndWarns::DeferWithoutCall, &call.location),
+        }
+        // Errors can't be handled inside a defer so the function can't throw
+        if throws {
+          self.add(AnylizeErrAndWarns::DeferThrows, &call.location);
+        } else if handled {
+          self.add(AnylizeErrAndWarns::ErrorHandlingNotAllowed, &location);
+        }
+        state.error_handled = throws;
+        self.check_action(call, state);
+        state.error_handled = false;
+      }
+      ActionType::Throw(data) => {
+        if !state.throws {
+          self.add(AnylizeErrAndWarns::ThrowNotAllowed, &action.location);
         }
+        self.check_action(*data, state);
 
         state.unreachable_code = true;
       }
+      // The call is seen as handled so only the error handling is reported
+      ActionType::Propagate(call) => {
+        self.add(
+          AnylizeErrAndWarns::ErrorHandlingNotAllowed,
+          &action.location,
+        );
+        state.error_handled = true;
+        self.check_action(*call, state);
+        state.error_handled = false;
+      }
+      ActionType::Catch(catch) => {
+        self.add(
+          AnylizeErrAndWarns::ErrorHandlingNotAllowed,
+          &action.location,
+        );
+        state.error_handled = true;
+        self.check_action(*catch.action, state);
+        state.error_handled = false;
+      }
       ActionType::Assigment(data) => {
-        if let Some(var) = state.vars.get(&data.name) {
+        if let Some(var) = state.scopes.get(&data.name) {
           if !var.mutatable {
             self.add(AnylizeErrAndWarns::Inmutable, &action.location);
           }
+          if let Some(expected) = &var.type_ {
+            self.check_value_type(expected, &data.action, state);
+          }
+          if let Some(var) = state.scopes.get_mut(&data.name) {
+            var.assignments.push(action.location.clone());
+          }
+        } else if let Some(var) = state.anilized_tokens.vars.get(&data.name) {
+          // Global variables are constants
+          self.add(AnylizeErrAndWarns::Inmutable, &action.location);
+          if let Some(expected) = &var.data_type {
+            self.check_value_type(expected, &data.action, state);
+          }
         } else {
           self.add(
             AnylizeErrAndWarns::VariableRefDoesNotExist,
@@ -469,24 +1925,80 @@ impl AnylizeResults {
         self.check_action(*data.action, state);
       }
       ActionType::FunctionCall(data) => {
-        if !state.anilized_tokens.functions.contains_key(&data.name) {
-          self.add(AnylizeErrAndWarns::FunctionDoesNotExist, &action.location);
+        let error_handled = state.error_handled;
+        state.error_handled = false;
+
+        if let Some((enum_, field_name)) = state.anilized_tokens.enum_field_ref(&data.name) {
+          // This creates an enum field with data `Shape.circle(1.0)`
+          if enum_.field(field_name).is_none() {
+            self.add(AnylizeErrAndWarns::EnumFieldDoesNotExist, &action.location);
+          }
+          self.check_deprecated(&data.name, &enum_.attributes, &action.location);
+        } else {
+          match state.anilized_tokens.function(&data.name) {
+            Some(function) => {
+              if function.throws && !error_handled {
+                self.add(AnylizeErrAndWarns::UnhandledError, &action.location);
+              }
+              self.check_deprecated(&data.name, &function.attributes, &action.location);
+            }
+            None => self.add(
+              state
+                .anilized_tokens
+                .missing_ref_error(&data.name, AnylizeErrAndWarns::FunctionDoesNotExist),
+              &action.location,
+            ),
+          }
+        }
+
+        match state.anilized_tokens.ordered_arguments(&data) {
+          Ok(arguments) => {
+            self.check_argument_types(&data, &arguments, state);
+          }
+          Err(err) => self.add(err, &action.location),
         }
 
         for argument in &data.arguments {
           // TODO make sure these actions are checked inline and check if they match the expted function type
           self.check_action(argument.clone(), state);
         }
+        for (_, argument) in data.named_arguments {
+          self.check_action(argument, state);
+        }
+        if let Some(spread) = data.spread {
+          self.check_action(*spread, state);
+        }
       }
       ActionType::VarRef(var_name) => {
-        if !state.vars.contains_key(&var_name) {
-          self.add(
-            AnylizeErrAndWarns::VariableRefDoesNotExist,
-            &action.location,
-          );
+        if let Some((enum_, field_name)) = state.anilized_tokens.enum_field_ref(&var_name) {
+          match enum_.field(field_name) {
+            Some(field) if !field.payload.is_empty() => self.add(
+              AnylizeErrAndWarns::ArityMismatch(field.payload.len(), 0),
+              &action.location,
+            ),
+            Some(_) => {}
+            None => self.add(AnylizeErrAndWarns::EnumFieldDoesNotExist, &action.location),
+          }
+          self.check_deprecated(&var_name, &enum_.attributes, &action.location);
           return;
         }
-        // TODO: Check if the variable matches the expected type here if we expect some kind of type like function calls arguments
+
+        if let Some(var) = state.scopes.get_mut(&var_name) {
+          var.references.push(action.location.clone());
+          return;
+        }
+        match state.anilized_tokens.vars.get(&var_name) {
+          Some(var) => self.check_deprecated(&var_name, &var.attributes, &action.location),
+          None => match state.anilized_tokens.module_var(&var_name) {
+            Some(var) => self.check_deprecated(&var_name, &var.attributes, &action.location),
+            None => self.add(
+              state
+                .anilized_tokens
+                .missing_ref_error(&var_name, AnylizeErrAndWarns::VariableRefDoesNotExist),
+              &action.location,
+            ),
+          },
+        }
       }
       ActionType::StaticBoolean(_) => {
         // TODO: check this
@@ -497,88 +2009,504 @@ impl AnylizeResults {
       ActionType::StaticNumber(_) => {
         // TODO: check this
       }
-      ActionType::Break => {
+      ActionType::Tuple(items) => {
+        for item in items {
+          self.check_action(item, state);
+        }
+      }
+      ActionType::Range(range) => {
+        // TODO: Check if these are numbers
+        self.check_action(*range.start, state);
+        self.check_action(*range.end, state);
+        if let Some(step) = range.step {
+          // Ranges only count up so a step of 0 or less never reaches the end
+          let not_positive = match constant_value(&step, state) {
+            Some(ConstValue::Int(value)) => value <= 0,
+            Some(ConstValue::Float(value)) => value <= 0.0,
+            _ => false,
+          };
+          if not_positive {
+            self.add(AnylizeErrAndWarns::StepNotPositive, &step.location);
+          }
+          self.check_action(*step, state);
+        }
+      }
+      ActionType::Break(label) => {
         if !state.inside_a_loop {
           self.add(AnylizeErrAndWarns::BreakNotAllowed, &action.location)
+        } else if let Some(label) = label {
+          if !state.loop_labels.contains(&label) {
+            self.add(AnylizeErrAndWarns::LabelDoesNotExist, &action.location)
+          }
         }
 
         state.unreachable_code = true;
       }
-      ActionType::Continue => {
+      ActionType::Continue(label) => {
         if !state.inside_a_loop {
           self.add(AnylizeErrAndWarns::ContinueNotAllowed, &action.location)
+        } else if let Some(label) = label {
+          if !state.loop_labels.contains(&label) {
+            self.add(AnylizeErrAndWarns::LabelDoesNotExist, &action.location)
+          }
         }
 
         state.unreachable_code = true;
       }
       ActionType::For(data) => {
         // TODO: Check if the variable matches the expected type
+        let item_type = loop_item_type(&data.list, state);
         self.check_action(*data.list, state);
 
-        if let Some(var) = state.vars.get(&data.item_name) {
-          if !var.global {
-            self.add(
-              AnylizeErrAndWarns::VariableAlreadyDeclared,
-              &action.location,
-            );
-          }
+        // The loop variables only exist inside the loop
+        state.scopes.push();
+        if let Some(index_name) = data.index_name {
+          let mut symbol = Symbol::new(index_name, SymbolKind::LoopVariable, &action.location);
+          symbol.type_ = Some(new_type(TypeType::Int, &action.location));
+          self.declare_local(symbol, state);
         }
+        let mut symbol = Symbol::new(data.item_name, SymbolKind::LoopVariable, &action.location);
+        symbol.type_ = item_type;
+        self.declare_local(symbol, state);
 
-        state.inside_a_loop = true;
-        self.check_actions(data.actions, state);
+        self.check_loop_body(data.actions, data.label, state);
+        state.scopes.pop();
       }
       ActionType::While(data) => {
-        // TODO: Check if the variable matches the expected type here (bool)
+        self.check_condition(&data.true_value, state);
+        let constant = self.check_constant_condition(&data.true_value, state);
         self.check_action(*data.true_value, state);
 
-        state.inside_a_loop = true;
-        self.check_actions(data.actions, state);
+        // The body of `while false {}` never runs
+        let unreachable_code = state.unreachable_code;
+        state.unreachable_code |= constant == Some(false);
+        self.check_loop_body(data.actions, data.label, state);
+        state.unreachable_code = unreachable_code;
       }
-      ActionType::Loop(actions) => {
-        state.inside_a_loop = true;
-        self.check_actions(actions, state);
+      ActionType::Loop(data) => {
+        let never_ends = loop_never_ends(&data);
+        self.check_loop_body(data.actions, data.label, state);
+        if never_ends {
+          state.unreachable_code = true;
+        }
       }
       ActionType::If(data) => {
-        // TODO: We can check a lot of things here like if we can never reach else ifs or else, and there are meany more
-
-        // TODO: Check if the variable matches the expected type here
-        self.check_action(*data.if_.check, state);
-        self.check_actions(data.if_.body, state);
+        // True once a condition is always true, the branches after it can never run
+        let mut always_taken = false;
+        let mut checks: Vec<Action> = vec![];
+        for branch in std::iter::once(data.if_).chain(data.else_ifs) {
+          if always_taken {
+            self.add(AnylizeErrAndWarns::UnreachableCode, &branch.check.location);
+          } else if checks.iter().any(|check| same_value(check, &branch.check)) {
+            self.add(
+              AnylizeErrAndWarns::RepeatedCondition,
+              &branch.check.location,
+            );
+          }
 
-        for else_if in data.else_ifs {
-          // TODO: Check if the variable matches the expected type here
-          self.check_action(*else_if.check, state);
-          self.check_actions(else_if.body, state);
+          self.check_condition(&branch.check, state);
+          let constant = self.check_constant_condition(&branch.check, state);
+          checks.push((*branch.check).clone());
+          self.check_action(*branch.check, state);
+          // The branch is already reported as unreachable by its condition
+          let unreachable_reported = state.unreachable_reported;
+          state.unreachable_reported |= always_taken;
+          self.check_branch(branch.body, always_taken || constant == Some(false), state);
+          state.unreachable_reported = unreachable_reported;
+          always_taken |= constant == Some(true);
         }
 
         if let Some(else_body) = data.else_body {
-          self.check_actions(else_body, state);
+          self.check_branch(else_body, always_taken, state);
+        }
+      }
+      ActionType::Operation(data) => {
+        self.check_operands(&data, state);
+        self.check_self_comparison(&data, &action.location, state);
+        if data.operator == Operator::Divide && is_integer_division(&data, state) {
+          state.integer_divisions.push(action.location.clone());
+        }
+        self.check_action(*data.left, state);
+        self.check_action(*data.right, state);
+      }
+      ActionType::Unary(data) => {
+        self.check_unary_operand(&data, state);
+        self.check_action(*data.action, state);
+      }
+      ActionType::Match(data) => {
+        self.check_action(*data.value, state);
+
+        let mut matches_everything = false;
+        for arm in data.arms {
+          if matches_everything {
+            self.add(AnylizeErrAndWarns::UnreachableCode, &arm.location);
+          }
+
+          state.scopes.push();
+          match arm.pattern {
+            MatchPattern::Default => matches_everything = true,
+            MatchPattern::Destructure(field_ref, names) => {
+              match state.anilized_tokens.enum_field_ref(&field_ref) {
+                Some((enum_, field_name)) => match enum_.field(field_name) {
+                  Some(field) if field.payload.len() != names.len() => self.add(
+                    AnylizeErrAndWarns::ArityMismatch(field.payload.len(), names.len()),
+                    &arm.location,
+                  ),
+                  Some(_) => {}
+                  None => self.add(AnylizeErrAndWarns::EnumFieldDoesNotExist, &arm.location),
+                },
+                None => self.add(AnylizeErrAndWarns::EnumFieldDoesNotExist, &arm.location),
+              }
+
+              for name in names {
+                self.declare_local(
+                  Symbol::new(name, SymbolKind::MatchBinding, &arm.location),
+                  state,
+                );
+              }
+            }
+            MatchPattern::Value(value) => self.check_action(*value, state),
+          }
+
+          self.check_actions(arm.body, state);
+          state.scopes.pop();
         }
       }
     }
   }
 }
 
-#[derive(Clone)]
+/// Returns the value of a condition that is always the same, like `false` or `DEBUG && 1 > 2`,
+/// conditions that use local variables or function calls return None
+fn constant_condition(condition: &Action, state: &CheckActionState) -> Option<bool> {
+  match constant_value(condition, state)? {
+    ConstValue::Bool(value) => Some(value),
+    _ => None,
+  }
+}
+
+/// Returns the value of an action that is always the same like `2` or `SIZE * 2`,
+/// actions that use local variables or function calls return None
+fn constant_value(action: &Action, state: &CheckActionState) -> Option<ConstValue> {
+  let mut uses_locals = false;
+  action.walk(&mut |action| {
+    if let ActionType::VarRef(name) = &action.type_ {
+      if state.scopes.get(name).is_some() {
+        uses_locals = true;
+      }
+    }
+  });
+  if uses_locals {
+    return None;
+  }
+
+  ConstEvaluator::new(&state.anilized_tokens.vars)
+    .evaluate(action)
+    .ok()
+}
+
+/// Returns the type of the items of the list a for loop loops over
+fn loop_item_type(list: &Action, state: &CheckActionState) -> Option<Type> {
+  if let ActionType::Range(_) = list.type_ {
+    return Some(new_type(TypeType::Int, &list.location));
+  }
+  match resolve_aliases(&action_type(list, state)?, state.anilized_tokens).type_ {
+    TypeType::Array(item_type) => Some(*item_type),
+    _ => None,
+  }
+}
+
+/// Returns the amound of values a type contains, tuples contain multiple values
+fn type_arity(type_: &Type, tokens: &AnilizedTokens) -> usize {
+  if let TypeType::Tuple(types) = resolve_aliases(type_, tokens).type_ {
+    types.len()
+  } else {
+    1
+  }
+}
+
+/// Returns true if the type is or contains a tuple, like an array of tuples
+fn contains_tuple(type_: &Type) -> bool {
+  match &type_.type_ {
+    TypeType::Tuple(_) => true,
+    TypeType::Array(item_type) => contains_tuple(item_type),
+    _ => false,
+  }
+}
+
+/// Returns false if the action is a static value that doesn't fit into the type,
+/// for other actions we can't know the type yet so true is returned
+fn static_value_fits_type(action: &Action, type_: &Type) -> bool {
+  match (&action.type_, &type_.type_) {
+    (_, TypeType::TypeRef(_)) => true,
+    (ActionType::StaticString(_), TypeType::String)
+    | (ActionType::StaticString(_), TypeType::Char) => true,
+    (ActionType::StaticNumber(_), TypeType::F32) | (ActionType::StaticNumber(_), TypeType::F64) => {
+      true
+    }
+    (ActionType::StaticNumber(number), _) => match number.type_ {
+      NumberType::Int(_) => is_int_type(type_),
+      NumberType::Float(_) => false,
+    },
+    (ActionType::StaticBoolean(_), TypeType::Bool) => true,
+    (ActionType::StaticString(_), _) | (ActionType::StaticBoolean(_), _) => false,
+    _ => true,
+  }
+}
+
+/// Returns true if the type is one of the intager types
+fn is_int_type(type_: &Type) -> bool {
+  matches!(
+    type_.type_,
+    TypeType::Int
+      | TypeType::I8
+      | TypeType::I16
+      | TypeType::I32
+      | TypeType::I64
+      | TypeType::UInt
+      | TypeType::U8
+      | TypeType::U16
+      | TypeType::U32
+      | TypeType::U64
+  )
+}
+
+/// Returns true if the type is one of the intager or float types
+fn is_number_type(type_: &Type) -> bool {
+  match type_.type_ {
+    TypeType::F32 | TypeType::F64 => true,
+    _ => is_int_type(type_),
+  }
+}
+
+/// Returns true if both sides of a division are intagers, the result is rounded towards zero
+fn is_integer_division(operation: &ActionOperation, state: &CheckActionState) -> bool {
+  [&operation.left, &operation.right]
+    .iter()
+    .all(|operand| match action_type(operand, state) {
+      Some(type_) => is_int_type(&underlying_type(&type_, state.anilized_tokens)),
+      None => false,
+    })
+}
+
+/// Returns true if the action or an action inside of it calls a function
+fn contains_call(action: &Action) -> bool {
+  let mut res = false;
+  action.walk(&mut |action| {
+    if let ActionType::FunctionCall(_) = action.type_ {
+      res = true;
+    }
+  });
+  res
+}
+
+/// Creates a type that isn't written in the code like the type of a static value
+fn new_type(type_: TypeType, location: &Span) -> Type {
+  Type {
+    location: location.clone(),
+    type_,
+  }
+}
+
+/// Returns the type of the value an action results in,
+/// if we don't know this None is returned
+fn action_type(action: &Action, state: &CheckActionState) -> Option<Type> {
+  let tokens = state.anilized_tokens;
+  match &action.type_ {
+    ActionType::VarRef(name) | ActionType::FunctionCall(ActionFunctionCall { name, .. })
+      if tokens.enum_field_ref(name).is_some() =>
+    {
+      Some(new_type(tokens.enum_type(name)?, &action.location))
+    }
+    ActionType::VarRef(name) => match state.scopes.get(name) {
+      Some(var) => var.type_.clone(),
+      // Constants without a type get the type of their value
+      None if untyped_const(name, state).is_some() => action_type(
+        &untyped_const(name, state)?.into_action(action.location.clone()),
+        state,
+      ),
+      None => match tokens.vars.get(name) {
+        Some(var) => var.data_type.clone(),
+        None => tokens.module_var(name)?.data_type,
+      },
+    },
+    ActionType::FunctionCall(call) => tokens.function(&call.name)?.res,
+    ActionType::Propagate(call) => action_type(call, state),
+    ActionType::Catch(catch) => action_type(&catch.action, state),
+    ActionType::StaticString(_) => Some(new_type(TypeType::String, &action.location)),
+    ActionType::StaticBoolean(_) => Some(new_type(TypeType::Bool, &action.location)),
+    ActionType::StaticNumber(number) => Some(new_type(
+      match number.type_ {
+        NumberType::Int(_) => TypeType::Int,
+        NumberType::Float(_) => TypeType::F64,
+      },
+      &action.location,
+    )),
+    ActionType::Tuple(items) => {
+      let mut types: Vec<Type> = vec![];
+      for item in items {
+        types.push(action_type(item, state)?);
+      }
+      Some(new_type(TypeType::Tuple(types), &action.location))
+    }
+    ActionType::Operation(data) if !data.operator.is_arithmetic() => {
+      Some(new_type(TypeType::Bool, &action.location))
+    }
+    ActionType::Operation(data) if is_untyped_number(&data.left, state) => {
+      action_type(&data.right, state)
+    }
+    ActionType::Operation(data) => action_type(&data.left, state),
+    ActionType::Unary(data) => match data.operator {
+      UnaryOperator::Not => Some(new_type(TypeType::Bool, &action.location)),
+      UnaryOperator::Negate => action_type(&data.action, state),
+    },
+    _ => None,
+  }
+}
+
+/// Returns true if the action only contains static numbers `1 + 2`,
+/// these don't have a type of their own as they fit into every number type they can be converted into
+fn is_untyped_number(action: &Action, state: &CheckActionState) -> bool {
+  match &action.type_ {
+    ActionType::StaticNumber(_) => true,
+    ActionType::VarRef(name) => matches!(
+      untyped_const(name, state),
+      Some(ConstValue::Int(_)) | Some(ConstValue::Float(_))
+    ),
+    ActionType::Operation(data) if data.operator.is_arithmetic() => {
+      is_untyped_number(&data.left, state) && is_untyped_number(&data.right, state)
+    }
+    ActionType::Unary(data) if data.operator == UnaryOperator::Negate => {
+      is_untyped_number(&data.action, state)
+    }
+    _ => false,
+  }
+}
+
+/// Returns the value of a global constant without a type like `const a = 7`,
+/// locals with the same name hide the constant
+fn untyped_const(name: &str, state: &CheckActionState) -> Option<ConstValue> {
+  if state.scopes.get(name).is_some() {
+    return None;
+  }
+  let tokens = state.anilized_tokens;
+  let (tokens, name) = match tokens.module_ref(name) {
+    Some((_, module, name)) => (module, name),
+    None => (tokens, name),
+  };
+  match tokens.vars.get(name)?.data_type {
+    Some(_) => None,
+    None => tokens.consts.get(name).cloned(),
+  }
+}
+
+/// Returns the type of an action if it has a type of its own, static numbers are skipped
+fn typed_action_type(action: &Action, state: &CheckActionState) -> Option<Type> {
+  if is_untyped_number(action, state) {
+    None
+  } else {
+    action_type(action, state)
+  }
+}
+
+/// Checks if the value of an action can be used where a value of type expected is expected,
+/// if it doesn't fit the type found is returned
+fn value_fits_type(
+  action: &Action,
+  expected: &Type,
+  state: &CheckActionState,
+) -> Result<(), Box<Type>> {
+  let found = match action_type(action, state) {
+    Some(found) => found,
+    None => return Ok(()),
+  };
+
+  let fits = match &action.type_ {
+    ActionType::StaticString(_) | ActionType::StaticNumber(_) | ActionType::StaticBoolean(_) => {
+      static_value_fits_type(action, &underlying_type(expected, state.anilized_tokens))
+    }
+    ActionType::VarRef(name) if is_untyped_number(action, state) => {
+      match untyped_const(name, state) {
+        Some(value) => static_value_fits_type(
+          &value.into_action(action.location.clone()),
+          &underlying_type(expected, state.anilized_tokens),
+        ),
+        None => true,
+      }
+    }
+    ActionType::Operation(data) if is_untyped_number(action, state) => {
+      value_fits_type(&data.left, expected, state).is_ok()
+        && value_fits_type(&data.right, expected, state).is_ok()
+    }
+    ActionType::Unary(data) if is_untyped_number(action, state) => {
+      value_fits_type(&data.action, expected, state).is_ok()
+    }
+    _ => same_type(expected, &found, state.anilized_tokens),
+  };
+
+  if fits {
+    Ok(())
+  } else {
+    Err(Box::new(found))
+  }
+}
+
+/// Returns the amound of values an action results in,
+/// if we don't know this None is returned
+fn action_arity(action: &Action, state: &CheckActionState) -> Option<usize> {
+  match &action.type_ {
+    ActionType::Tuple(items) => Some(items.len()),
+    ActionType::FunctionCall(call) => {
+      let function = state.anilized_tokens.function(&call.name)?;
+      Some(match &function.res {
+        Some(res) => type_arity(res, state.anilized_tokens),
+        None => 0,
+      })
+    }
+    ActionType::Propagate(call) => action_arity(call, state),
+    ActionType::Catch(catch) => action_arity(&catch.action, state),
+    // Other actions like variable references have as many values as their type
+    _ => {
+      Some(action_type(action, state).map_or(1, |type_| type_arity(&type_, state.anilized_tokens)))
+    }
+  }
+}
+
 struct CheckActionState<'a> {
   inside_a_loop: bool,
+  /// The labels of the loops we are inside of
+  loop_labels: Vec<String>,
   unreachable_code: bool,
-  vars: HashMap<String, VariableDetials>,
+  /// True if the unreachable code we are inside of is already reported, only the first statement of dead code gets a warning
+  unreachable_reported: bool,
+  /// The response type of the function we are checking
+  return_type: Option<Type>,
+  /// True if the function we are checking can throw errors
+  throws: bool,
+  /// True if the error of the function call we are about to check is handled
+  error_handled: bool,
+  /// True if we are checking the actions directly inside the function body and not inside a loop or if
+  function_scope: bool,
+  /// The arguments and local variables of the blocks we are inside of, globals are looked up in anilized_tokens
+  scopes: Scopes,
+  /// The locations of the divisions of two integers inside the function
+  integer_divisions: Vec<Span>,
   anilized_tokens: &'a AnilizedTokens,
 }
 
-#[derive(Clone)]
-struct VariableDetials {
-  global: bool,
-  mutatable: bool,
-}
-
 impl<'a> CheckActionState<'a> {
   fn new(anilized_tokens: &'a AnilizedTokens) -> Self {
     Self {
       inside_a_loop: false,
+      loop_labels: vec![],
       unreachable_code: false,
-      vars: HashMap::new(),
+      unreachable_reported: false,
+      return_type: None,
+      throws: false,
+      error_handled: false,
+      function_scope: false,
+      scopes: Scopes::new(),
+      integer_divisions: vec![],
       anilized_tokens,
     }
   }
@@ -0,0 +1,122 @@
+use super::*;
+
+/// What declared a symbol
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolKind {
+  Argument,
+  Variable,
+  /// The item or index of a for loop `for i, item in list {}`
+  LoopVariable,
+  /// The error of a catch `foo() catch err {}`
+  CatchError,
+  /// A variable of a destructured enum field `Shape.circle(radius) => {}`
+  MatchBinding,
+}
+
+/// A name declared inside a function
+#[derive(Debug, Clone)]
+pub struct Symbol {
+  pub name: String,
+  pub kind: SymbolKind,
+  /// Where the symbol is declared
+  pub location: Span,
+  pub mutatable: bool,
+  /// The type of the symbol if we know it
+  pub type_: Option<Type>,
+  /// The places where the value of the symbol is read
+  pub references: Vec<Span>,
+  /// The places where a new value is assigned to the symbol
+  pub assignments: Vec<Span>,
+}
+
+impl Symbol {
+  pub fn new(name: impl Into<String>, kind: SymbolKind, location: &Span) -> Self {
+    Self {
+      name: name.into(),
+      kind,
+      location: location.clone(),
+      mutatable: false,
+      type_: None,
+      references: vec![],
+      assignments: vec![],
+    }
+  }
+}
+
+/// All symbols declared inside a function, this is kept after checking so editors can find what a name refers to
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+  pub symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+  /// Returns the symbol that is declared, read or assigned at the byte offset,
+  /// the span of a declaration or assignment can contain other symbols so the symbol with the smallest span wins
+  // Currently dead because there is no editor integration yet that looks up symbols
+  #[allow(dead_code)]
+  pub fn symbol_at(&self, position: usize) -> Option<&Symbol> {
+    self
+      .symbols
+      .iter()
+      .filter_map(|symbol| {
+        let size = std::iter::once(&symbol.location)
+          .chain(&symbol.references)
+          .chain(&symbol.assignments)
+          .filter(|span| span.start <= position && position < span.end)
+          .map(|span| span.end - span.start)
+          .min()?;
+        Some((size, symbol))
+      })
+      .min_by_key(|(size, _)| *size)
+      .map(|(_, symbol)| symbol)
+  }
+}
+
+/// The stack of blocks we are inside of while checking a function,
+/// every block maps the names declared in it to their symbol in the symbol table
+pub struct Scopes {
+  table: SymbolTable,
+  stack: Vec<HashMap<String, usize>>,
+}
+
+impl Scopes {
+  /// Creates the scope of a function that contains its arguments
+  pub fn new() -> Self {
+    Self {
+      table: SymbolTable::default(),
+      stack: vec![HashMap::new()],
+    }
+  }
+  pub fn push(&mut self) {
+    self.stack.push(HashMap::new());
+  }
+  pub fn pop(&mut self) {
+    self.stack.pop();
+  }
+  /// Adds a symbol to the innermost scope, a symbol with the same name in an outer scope is shadowed
+  pub fn declare(&mut self, symbol: Symbol) {
+    let index = self.table.symbols.len();
+    if let Some(scope) = self.stack.last_mut() {
+      scope.insert(symbol.name.clone(), index);
+    }
+    self.table.symbols.push(symbol);
+  }
+  /// Returns the index of the symbol a name refers to, looking from the innermost scope outwards
+  pub fn lookup(&self, name: &str) -> Option<usize> {
+    self
+      .stack
+      .iter()
+      .rev()
+      .find_map(|scope| scope.get(name).copied())
+  }
+  pub fn get(&self, name: &str) -> Option<&Symbol> {
+    Some(&self.table.symbols[self.lookup(name)?])
+  }
+  pub fn get_mut(&mut self, name: &str) -> Option<&mut Symbol> {
+    let index = self.lookup(name)?;
+    Some(&mut self.table.symbols[index])
+  }
+  pub fn into_table(self) -> SymbolTable {
+    self.table
+  }
+}
@@ -0,0 +1,177 @@
+use super::*;
+
+#[test]
+fn test_attributes_on_items() {
+  parse_str(
+    r#"
+      #[deprecated]
+      const a = 1
+
+      #[deprecated("use bar")]
+      fn foo() {}
+
+      #[deprecated] #[target(go, js)]
+      struct Foo {}
+
+      #[target(js)]
+      enum Bar {
+        a
+        b
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_attributes_on_statements() {
+  parse_str(
+    r#"
+      fn foo() {
+        #[target(go)]
+        let a = 1
+        #[target(js)]
+        let b = 2
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_attributes_invalid() {
+  parse_str_fail(
+    r#"
+      #[inline]
+      fn foo() {}
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      #[target(python)]
+      fn foo() {}
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      #[target]
+      fn foo() {}
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      #[deprecated("a", "b")]
+      fn foo() {}
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn foo() {
+        #[deprecated]
+        let a = 1
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      #[target(go)]
+      import "foo"
+    "#,
+  );
+}
+
+#[test]
+fn test_attributes_deprecated_warning() {
+  parse_str_warning(
+    r#"
+      #[deprecated]
+      fn foo() {}
+
+      fn bar() {
+        foo()
+      }
+    "#,
+  );
+  parse_str_warning(
+    r#"
+      #[deprecated]
+      const a = 1
+
+      fn bar() {
+        let b = a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_attributes_target_items() {
+  let code = r#"
+    #[target(go)]
+    fn log(message string) {
+      let go_message = message
+    }
+
+    #[target(js)]
+    fn log(message string) {
+      let js_message = message
+    }
+
+    #[target(js)]
+    const only_js = 1
+
+    fn main() {
+      log("hello")
+    }
+  "#;
+  let go = parse_str_to_lang(code, Lang::Go);
+  assert_eq!(go.matches("func log(").count(), 1);
+  assert!(go.contains("go_message"));
+  assert!(!go.contains("only_js"));
+
+  let js = parse_str_to_lang(code, Lang::JS);
+  assert_eq!(js.matches("function log(").count(), 1);
+  assert!(js.contains("js_message"));
+  assert!(js.contains("only_js"));
+}
+
+#[test]
+fn test_attributes_target_statements() {
+  let code = r#"
+    fn print(message string) {}
+
+    fn main() {
+      #[target(go)]
+      print("go")
+      #[target(js)]
+      print("js")
+      if true {
+        #[target(go)]
+        print("nested go")
+      }
+    }
+  "#;
+  let go = parse_str_to_lang(code, Lang::Go);
+  assert!(go.contains("\"go\""));
+  assert!(go.contains("\"nested go\""));
+  assert!(!go.contains("\"js\""));
+
+  let js = parse_str_to_lang(code, Lang::JS);
+  assert!(js.contains("\"js\""));
+  assert!(!js.contains("go\""));
+}
+
+#[test]
+fn test_attributes_other_target_not_checked() {
+  // The function for go doesn't exist when building js
+  parse_str_to_lang(
+    r#"
+      #[target(go)]
+      fn only_go() {}
+
+      fn main() {
+        #[target(go)]
+        only_go()
+      }
+    "#,
+    Lang::JS,
+  );
+}
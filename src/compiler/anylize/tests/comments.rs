@@ -81,3 +81,117 @@ fn test_multi_line_comment_direct_eof_2() {
 fn test_multi_line_comment_direct_eof_3() {
   parse_str("/* ");
 }
+
+#[test]
+fn test_doc_comments_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      /// The amount of items
+      ///
+      /// Must be positive
+      const size = 2
+
+      // Not a doc comment
+      /// Runs the program
+      fn main() {}
+
+      /// A point
+      struct Point {
+        /// The x position
+        x int
+        y int
+      }
+
+      /// The level
+      enum Level {
+        /// Low level
+        low
+        high
+      }
+
+      //// Not a doc comment either
+      fn other() {}
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("// The amount of items\n//\n// Must be positive\nconst size = 2"));
+  assert!(output.contains("// Runs the program\nfunc main() {"));
+  assert!(!output.contains("Not a doc comment"));
+  assert!(output.contains("// A point\ntype Point struct"));
+  assert!(output.contains("  // The x position\n  x int\n  y int"));
+  assert!(output.contains("// The level\ntype Level int"));
+  assert!(output.contains("  // Low level\n  LevelLow Level = 0"));
+  assert!(output.contains("\nfunc other() {"));
+}
+
+#[test]
+fn test_doc_comments_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      /// Runs the program
+      fn main() {}
+
+      /// The level
+      enum Level {
+        /// Low level
+        low
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("/**\n * Runs the program\n */\nfunction main() {"));
+  assert!(output.contains("/**\n * The level\n */\nconst Level = {"));
+  assert!(output.contains("  /**\n   * Low level\n   */\n  low: 0,"));
+}
+
+#[test]
+fn test_doc_comment_inside_function() {
+  parse_str(
+    r#"
+      fn main() {
+        /// Doc comments inside functions are ignored
+        let a = 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_doc_comments_only_directly_above() {
+  let output = parse_str_to_lang(
+    r#"
+      /*
+      /// Inside a multi-line comment
+      */
+      fn foo() {}
+
+      /// Separated by an empty line
+
+      fn bar() {}
+
+      fn baz() {} /// After code
+      fn main() {}
+
+      /// A number
+      #[target(go)]
+      type Number int
+    "#,
+    Lang::Go,
+  );
+  assert!(!output.contains("Inside a multi-line comment"));
+  assert!(!output.contains("Separated by an empty line"));
+  assert!(!output.contains("After code"));
+  assert!(output.contains("// A number\ntype Number int"));
+}
+
+#[test]
+fn test_doc_comment_end_escaped_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      /// Ends with */ alert(1) /*
+      fn main() {}
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("/**\n * Ends with *\\/ alert(1) /*\n */\nfunction main() {"));
+}
@@ -0,0 +1,232 @@
+use super::*;
+
+/// Returns the warnings of compiling the code
+fn warnings(contents: &str) -> Vec<String> {
+  let res = single_file_compile(String::from(contents));
+  let res = res.borrow();
+  if !res.errors.is_empty() {
+    panic!("{:?}", res.errors);
+  }
+  res.warnings.iter().map(|w| w.to_string()).collect()
+}
+
+fn assert_warning(contents: &str, message: &str) {
+  let warnings = warnings(contents);
+  if !warnings.iter().any(|w| w.contains(message)) {
+    panic!(
+      "Expected a warning containing {:?}\n{:?}",
+      message, warnings
+    );
+  }
+}
+
+#[test]
+fn test_constant_if_condition() {
+  assert_warning(
+    r#"
+      fn main() {
+        if false {}
+      }
+    "#,
+    "This condition is always false",
+  );
+  assert_warning(
+    r#"
+      const debug = true
+      fn main() {
+        if debug && 1 < 2 {}
+      }
+    "#,
+    "This condition is always true",
+  );
+}
+
+#[test]
+fn test_dead_branches() {
+  // The body of an if that is always false never runs
+  assert_warning(
+    r#"
+      fn foo() {}
+      fn main() {
+        if false {
+          foo()
+        }
+      }
+    "#,
+    "Unreachable code",
+  );
+  // After a condition that is always true the other branches never run
+  assert_warning(
+    r#"
+      fn foo() {}
+      fn main() {
+        if true {
+        } else {
+          foo()
+        }
+      }
+    "#,
+    "Unreachable code",
+  );
+}
+
+#[test]
+fn test_conditions_with_locals_are_not_constant() {
+  let warnings = warnings(
+    r#"
+      const limit = 10
+      fn main() {
+        let a = 1
+        if a > limit {
+        } else if a < 0 {
+        } else {
+        }
+      }
+    "#,
+  );
+  assert_eq!(warnings.len(), 0, "{:?}", warnings);
+}
+
+#[test]
+fn test_constant_while_condition() {
+  assert_warning(
+    r#"
+      fn foo() {}
+      fn main() {
+        while false {
+          foo()
+        }
+      }
+    "#,
+    "Unreachable code",
+  );
+}
+
+#[test]
+fn test_code_after_infinite_loop() {
+  assert_warning(
+    r#"
+      fn foo() {}
+      fn main() {
+        loop {
+          foo()
+        }
+        foo()
+      }
+    "#,
+    "Unreachable code",
+  );
+  let warnings = warnings(
+    r#"
+      fn foo() {}
+      fn main() {
+        loop {
+          break
+        }
+        foo()
+      }
+    "#,
+  );
+  assert_eq!(warnings.len(), 0, "{:?}", warnings);
+}
+
+#[test]
+fn test_unreachable_code_reported_once() {
+  let unreachable = |contents: &str| {
+    warnings(contents)
+      .into_iter()
+      .filter(|w| w.contains("Unreachable code"))
+      .count()
+  };
+  assert_eq!(
+    unreachable(
+      r#"
+        fn show(a int, b int) {}
+        fn main() {
+          while false {
+            show(1, 2)
+            show(3, 4)
+          }
+        }
+      "#,
+    ),
+    1
+  );
+  assert_eq!(
+    unreachable(
+      r#"
+        fn show(a int, b int) {}
+        fn main() {
+          loop {}
+          if true {
+            show(1, 2)
+          }
+          show(3, 4)
+        }
+      "#,
+    ),
+    1
+  );
+  assert_eq!(
+    unreachable(
+      r#"
+        fn show(a int, b int) {}
+        fn main() {
+          if true {
+          } else if 1 > 2 {
+            show(1, 2)
+          }
+        }
+      "#,
+    ),
+    1
+  );
+}
+
+#[test]
+fn test_repeated_condition() {
+  assert_warning(
+    r#"
+      fn main() {
+        let a = 1
+        if a > 1 {
+        } else if a < 0 {
+        } else if a > 1 {
+        }
+      }
+    "#,
+    "This condition is already checked by an earlier branch",
+  );
+}
+
+#[test]
+fn test_self_comparison() {
+  assert_warning(
+    r#"
+      fn main() {
+        let a = 1
+        if a == a {}
+      }
+    "#,
+    "Comparing a value with itself is always true",
+  );
+  assert_warning(
+    r#"
+      fn main() {
+        let a = 1
+        let b = a < a
+      }
+    "#,
+    "Comparing a value with itself is always false",
+  );
+  // Comparing a float with itself checks for NaN
+  let warnings = warnings(
+    r#"
+      fn main() {
+        let a: f64 = 1.5
+        if a != a {}
+      }
+    "#,
+  );
+  assert_eq!(warnings.len(), 0, "{:?}", warnings);
+}
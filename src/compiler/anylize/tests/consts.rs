@@ -0,0 +1,217 @@
+use super::*;
+
+#[test]
+fn test_const_operators() {
+  parse_str(
+    r#"
+      const size = 4 * 8 + 2
+      const half = size / 2
+      const name = "foo" + "bar"
+      const enabled = size > 10 && name != "baz"
+    "#,
+  );
+}
+
+#[test]
+fn test_const_function_call_fail() {
+  parse_str_fail(
+    r#"
+      fn size() int {
+        return 1
+      }
+      const foo = size()
+    "#,
+  );
+}
+
+#[test]
+fn test_const_unknown_ref_fail() {
+  parse_str_fail(
+    r#"
+      const foo = bar + 1
+    "#,
+  );
+}
+
+#[test]
+fn test_const_cycle_fail() {
+  parse_str_fail(
+    r#"
+      const a = b + 1
+      const b = c + 1
+      const c = a + 1
+    "#,
+  );
+}
+
+#[test]
+fn test_const_self_reference_fail() {
+  parse_str_fail(
+    r#"
+      const a = a
+    "#,
+  );
+}
+
+#[test]
+fn test_const_division_by_zero_fail() {
+  parse_str_fail(
+    r#"
+      const a = 10 / (2 - 2)
+    "#,
+  );
+}
+
+#[test]
+fn test_const_overflow_fail() {
+  parse_str_fail(
+    r#"
+      const a = 9223372036854775807 + 1
+    "#,
+  );
+}
+
+#[test]
+fn test_const_invalid_operation_fail() {
+  parse_str_fail(
+    r#"
+      const a = "foo" - 1
+    "#,
+  );
+}
+
+#[test]
+fn test_const_enum_value() {
+  parse_str(
+    r#"
+      const base = 10
+      enum Foo {
+        bar = base
+        baz = base + 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_const_enum_value_function_call_fail() {
+  parse_str_fail(
+    r#"
+      fn base() int {
+        return 1
+      }
+      enum Foo {
+        bar = base()
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_const_folded_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      const size = 4 * (8 + 2)
+      const half = size / 3
+      const name = "foo" + "bar"
+      const ratio = half / 2.0
+      const big = size >= 40
+      enum Foo {
+        bar = size + 1
+        baz = 2
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("const size = 40"));
+  assert!(output.contains("const half = 13"));
+  assert!(output.contains("const name = \"foobar\""));
+  assert!(output.contains("const ratio = 6.5"));
+  assert!(output.contains("const big = true"));
+  assert!(output.contains("FooBar Foo = 41"));
+}
+
+#[test]
+fn test_const_folded_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      const size = 2 + 3 * 4
+      enum Foo {
+        bar = size
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("const size = 14;"));
+  assert!(output.contains("bar: 14,"));
+}
+
+#[test]
+fn test_const_unary_operators() {
+  let output = parse_str_to_lang(
+    r#"
+      const low = -4
+      const high = -(low * 2)
+      const off = !(high > low)
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("const low = -4"));
+  assert!(output.contains("const high = 8"));
+  assert!(output.contains("const off = false"));
+}
+
+#[test]
+fn test_const_enum_sibling_value() {
+  let output = parse_str_to_lang(
+    r#"
+      const base = 10
+      enum Level {
+        low = base
+        high = low + 1
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("LevelHigh Level = 11"));
+  // Only the fields above a field can be used
+  parse_str_fail(
+    r#"
+      enum Level {
+        low = high
+        high = 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_untyped_const_type() {
+  // Constants without a type get the type of their value
+  let cases = vec![
+    "const a = 7\n fn main() {\n let s: string = a\n }",
+    "const a = 7\n fn f(s string) {}\n fn main() {\n f(a)\n }",
+    "const a = 7\n fn main() {\n if a {}\n }",
+    "const a = \"foo\"\n fn main() {\n let b = a\n let c: int = b\n }",
+    "const a = 1.5\n fn main() {\n let b: int = a\n }",
+  ];
+  for code in cases {
+    parse_str_fail_with_meta(code, code);
+  }
+
+  // Numbers keep working for every number type like static numbers do
+  parse_str(
+    r#"
+      const a = 7
+      const b = 1.5
+      const c = "foo"
+      fn main() {
+        let d: f64 = a
+        let e: u8 = a * 2
+        let f: f32 = b
+        let g: string = c
+        let h: bool = a > 2
+      }
+    "#,
+  );
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+#[test]
+fn test_defer() {
+  parse_str(
+    r#"
+      fn close(name string) {}
+
+      fn main() {
+        defer close("a")
+        let b = "b"
+        defer close(b)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_defer_only_in_function_scope() {
+  parse_str_fail(
+    r#"
+      fn close() {}
+
+      fn main() {
+        loop {
+          defer close()
+          break
+        }
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn close() {}
+
+      fn main() {
+        if true {
+          defer close()
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_defer_invalid() {
+  // Only function calls can be deferred
+  parse_str_fail(
+    r#"
+      fn main() {
+        defer "a"
+      }
+    "#,
+  );
+  // The function must exist
+  parse_str_fail(
+    r#"
+      fn main() {
+        defer close()
+      }
+    "#,
+  );
+  // Errors can't be handled inside a defer
+  parse_str_fail(
+    r#"
+      fn close() throws {}
+
+      fn main() {
+        defer close()
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_defer_output() {
+  let code = r#"
+    fn close(name string) {}
+
+    fn main() {
+      defer close("a")
+      defer close("b")
+    }
+  "#;
+  let go = parse_str_to_lang(code, Lang::Go);
+  assert!(go.contains("defer close(\"a\")"));
+  assert!(go.contains("defer close(\"b\")"));
+
+  let js = parse_str_to_lang(code, Lang::JS);
+  assert!(js.contains("const __defers = [];"));
+  assert!(js.contains("__defers.push(close.bind(null,\"a\"));"));
+  assert!(js.contains("} finally {"));
+  assert!(js.contains("__defers.pop()();"));
+}
+
+#[test]
+fn test_defer_arguments_evaluated_at_defer_js_output() {
+  let js = parse_str_to_lang(
+    r#"
+      fn show(a int, b int, parts ...string) {}
+      fn main() {
+        let a = 1
+        defer show(a, 2)
+        defer show(1, 2, "foo", "bar")
+        a = 2
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(js.contains("__defers.push(show.bind(null,a,2));"));
+  assert!(js.contains("__defers.push(show.bind(null,1,2,\"foo\",\"bar\"));"));
+}
@@ -116,3 +116,167 @@ fn test_enum_with_multiple_fields() {
     "#,
   );
 }
+
+#[test]
+fn test_enum_with_data() {
+  parse_str(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        rect(w f64, h f64)
+        none
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_with_data_and_value_fail() {
+  parse_str_fail(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none = 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_with_data_equal_names_fail() {
+  parse_str_fail(
+    r#"
+      enum Shape {
+        rect(w f64, w f64)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_constructor() {
+  parse_str(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none
+      }
+      fn main() {
+        let a = Shape.circle(1.5)
+        let b = Shape.none
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_constructor_arity_fail() {
+  parse_str_fail(
+    r#"
+      enum Shape {
+        rect(w f64, h f64)
+      }
+      fn main() {
+        let a = Shape.rect(1.5)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_constructor_unknown_field_fail() {
+  parse_str_fail(
+    r#"
+      enum Shape {
+        rect(w f64, h f64)
+      }
+      fn main() {
+        let a = Shape.square(1.5)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_enum_with_data_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none
+      }
+      fn main() {
+        let a = Shape.circle(1.5)
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("type Shape interface {"));
+  assert!(output.contains("type ShapeCircle struct {\n  radius float64\n}"));
+  assert!(output.contains("func (ShapeCircle) isShape() {}"));
+  assert!(output.contains("type ShapeNone struct {}"));
+  assert!(output.contains("a := ShapeCircle{1.5}"));
+}
+
+#[test]
+fn test_enum_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      enum Foo {
+        bar
+        baz
+      }
+      fn main() {
+        let a = Foo.baz
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("type Foo int"));
+  assert!(output.contains("const (\n  FooBar Foo = 0\n  FooBaz Foo = 1\n)"));
+  assert!(output.contains("a := FooBaz"));
+}
+
+#[test]
+fn test_enum_with_data_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none
+      }
+      fn main() {
+        let a = Shape.circle(1.5)
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("circle: (radius) => ({ tag: \"circle\", radius: radius }),"));
+  assert!(output.contains("none: { tag: \"none\" },"));
+  assert!(output.contains("let a = Shape.circle(1.5);"));
+}
+
+#[test]
+fn test_enum_implicit_values_continue_from_previous() {
+  let code = r#"
+    enum Color {
+      black
+      red = 5
+      green
+      blue
+      white = green + 10
+    }
+    fn main() {}
+  "#;
+  let tokens = parse_str(code);
+  assert_eq!(tokens.consts.get("Color.black"), Some(&ConstValue::Int(0)));
+  assert_eq!(tokens.consts.get("Color.green"), Some(&ConstValue::Int(6)));
+  assert_eq!(tokens.consts.get("Color.white"), Some(&ConstValue::Int(16)));
+
+  let output = parse_str_to_lang(code, Lang::Go);
+  assert!(output.contains(
+    "  ColorBlack Color = 0\n  ColorRed Color = 5\n  ColorGreen Color = 6\n  ColorBlue Color = 7\n  ColorWhite Color = 16\n"
+  ));
+  let output = parse_str_to_lang(code, Lang::JS);
+  assert!(output.contains("  black: 0,\n  red: 5,\n  green: 6,\n  blue: 7,\n  white: 16,\n"));
+}
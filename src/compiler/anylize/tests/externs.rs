@@ -0,0 +1,145 @@
+use super::*;
+
+#[test]
+fn test_extern_function() {
+  parse_str(
+    r#"
+      #[bind(go, "strings.ToUpper", "strings")]
+      #[bind(js, "toUpperCase", "./strings.js")]
+      extern fn upper(s string) string
+
+      #[bind(go, "println")]
+      #[bind(js, "console.log")]
+      extern fn log(message string)
+
+      fn main() {
+        let a = upper("a")
+        log(a)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_extern_function_invalid() {
+  // Extern functions don't have a body
+  parse_str_fail(
+    r#"
+      #[bind(go, "println")]
+      extern fn log(message string) {}
+    "#,
+  );
+  // Extern functions can't throw
+  parse_str_fail(
+    r#"
+      #[bind(go, "println")]
+      extern fn log(message string) throws
+    "#,
+  );
+  // Extern functions need a binding
+  parse_str_fail(
+    r#"
+      extern fn log(message string)
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      #[bind(python, "print")]
+      extern fn log(message string)
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      #[bind(go)]
+      extern fn log(message string)
+    "#,
+  );
+  // Only extern functions can have a binding
+  parse_str_fail(
+    r#"
+      #[bind(go, "println")]
+      fn log(message string) {}
+    "#,
+  );
+  // The arguments are checked like any other function
+  parse_str_fail(
+    r#"
+      #[bind(go, "println")]
+      extern fn log(message string)
+
+      fn main() {
+        log()
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_extern_function_missing_binding_for_target() {
+  let mut files = HashMap::new();
+  files.insert(
+    String::from("main.tp"),
+    String::from(
+      r#"
+        #[bind(go, "println")]
+        extern fn log(message string)
+      "#,
+    ),
+  );
+  let res = compile(files, Some(Lang::JS));
+  assert_eq!(res.borrow().errors.len(), 1);
+}
+
+#[test]
+fn test_extern_function_output() {
+  let code = r#"
+    #[bind(go, "strings.ToUpper", "strings")]
+    #[bind(js, "toUpperCase", "./strings.js")]
+    extern fn upper(s string) string
+
+    #[bind(go, "path.Join", "path")]
+    #[bind(js, "path.join", "path")]
+    extern fn join(parts ...string) string
+
+    fn main() {
+      let a = upper("a")
+      let b = join(a, "b")
+    }
+  "#;
+  let go = parse_str_to_lang(code, Lang::Go);
+  assert!(go.contains("\"path\""));
+  assert!(go.contains("\"strings\""));
+  assert!(go.contains("strings.ToUpper(\"a\")"));
+  assert!(go.contains("path.Join(a,\"b\")"));
+  assert!(!go.contains("func upper"));
+
+  let js = parse_str_to_lang(code, Lang::JS);
+  assert!(js.contains("import * as path from \"path\";"));
+  assert!(js.contains("import { toUpperCase } from \"./strings.js\";"));
+  assert!(js.contains("toUpperCase(\"a\")"));
+  assert!(js.contains("path.join(a,\"b\")"));
+  assert!(!js.contains("function upper"));
+}
+
+#[test]
+fn test_extern_function_expression_js_output() {
+  let js = parse_str_to_lang(
+    r#"
+      #[bind(go, "strings.ToUpper", "strings")]
+      #[bind(js, "(s) => s.toUpperCase()")]
+      extern fn upper(s string) string
+
+      #[bind(go, "println")]
+      #[bind(js, "console.log")]
+      extern fn print(s string)
+
+      fn main() {
+        print(upper("a"))
+      }
+    "#,
+    Lang::JS,
+  );
+  // A binding that isn't a name is wrapped so it can be called
+  assert!(js.contains("((s) => s.toUpperCase())(\"a\")"));
+  assert!(js.contains("console.log("));
+}
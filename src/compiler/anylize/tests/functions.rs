@@ -131,3 +131,289 @@ fn test_function_call_with_args() {
     "#,
   );
 }
+
+#[test]
+fn test_function_with_default_arg() {
+  parse_str(
+    r#"
+      fn connect(host string, port int = 80) {}
+      fn main() {
+        connect("localhost")
+        connect("localhost", 8080)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_default_arg_uses_other_arg_fail() {
+  parse_str_fail(
+    r#"
+      fn connect(host string, port int = host) {}
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_named_args() {
+  parse_str(
+    r#"
+      fn connect(host string, port int = 80, secure bool = false) {}
+      fn main() {
+        connect("localhost", secure: true)
+        connect(port: 8080, host: "localhost")
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_unknown_named_arg_fail() {
+  parse_str_fail(
+    r#"
+      fn connect(host string) {}
+      fn main() {
+        connect(hostname: "localhost")
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_named_arg_already_set_fail() {
+  parse_str_fail(
+    r#"
+      fn connect(host string) {}
+      fn main() {
+        connect("localhost", host: "localhost")
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_missing_arg_fail() {
+  parse_str_fail(
+    r#"
+      fn connect(host string, port int = 80) {}
+      fn main() {
+        connect(port: 8080)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_too_many_args_fail() {
+  parse_str_fail(
+    r#"
+      fn connect(host string) {}
+      fn main() {
+        connect("localhost", 80)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_positional_after_named_arg_fail() {
+  parse_str_fail(
+    r#"
+      fn connect(host string, port int) {}
+      fn main() {
+        connect(host: "localhost", 80)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_named_args_with_calls_order() {
+  parse_str(
+    r#"
+      fn port() int {
+        return 80
+      }
+      fn host() string {
+        return "localhost"
+      }
+      fn connect(host string, port int, secure bool = false) {}
+      fn main() {
+        connect(host: host(), port: port())
+        connect(port: port(), host: "localhost")
+        connect(secure: true, host: host(), port: 80)
+      }
+    "#,
+  );
+  // Out of order arguments that call functions are evaluated in the order they are written before the call
+  let code = r#"
+    fn port() int {
+      return 80
+    }
+    fn host() string {
+      return "localhost"
+    }
+    fn connect(host string, port int, secure bool = false) bool {
+      return secure
+    }
+    fn main() {
+      connect(port: port(), host: host())
+      let a = connect(port: port(), secure: true, host: host())
+    }
+  "#;
+  let output = parse_str_to_lang(code, Lang::Go);
+  assert!(output.contains(
+    "func(__arg0 int, __arg1 string) bool { return connect(__arg1,__arg0,false) }(port(), host())"
+  ));
+  assert!(output.contains(
+    "a := func(__arg0 int, __arg1 string) bool { return connect(__arg1,__arg0,true) }(port(), host())"
+  ));
+  let output = parse_str_to_lang(code, Lang::JS);
+  assert!(output.contains("((__arg0, __arg1) => connect(__arg1,__arg0,false))(port(), host());"));
+}
+
+#[test]
+fn test_function_call_named_args_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn connect(host string, port int = 80, secure bool = false) {}
+      fn main() {
+        connect("localhost", secure: true)
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("func connect(host string, port int, secure bool) {"));
+  assert!(output.contains("connect(\"localhost\",80,true)"));
+}
+
+#[test]
+fn test_function_call_named_args_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn connect(host string, port int = 80) {}
+      fn main() {
+        connect(port: 8080, host: "localhost")
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("function connect(host, port) {"));
+  assert!(output.contains("connect(\"localhost\",8080);"));
+}
+
+#[test]
+fn test_function_variadic_arg() {
+  parse_str(
+    r#"
+      fn log(prefix string, parts ...string) {
+        for part in parts {}
+      }
+      fn main() {
+        log("info")
+        log("info", "a", "b")
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_variadic_arg_not_last_fail() {
+  parse_str_fail(
+    r#"
+      fn log(parts ...string, prefix string) {}
+    "#,
+  );
+}
+
+#[test]
+fn test_function_variadic_arg_wrong_type_fail() {
+  parse_str_fail(
+    r#"
+      fn log(parts ...string) {}
+      fn main() {
+        log("a", 1)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_spread() {
+  parse_str(
+    r#"
+      fn log(prefix string, parts ...string) {}
+      fn log_all(prefix string, parts ...string) {
+        log(prefix, ...parts)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_spread_without_variadic_fail() {
+  parse_str_fail(
+    r#"
+      fn log(part string) {}
+      fn log_all(parts ...string) {
+        log(...parts)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_spread_with_variadic_values_fail() {
+  parse_str_fail(
+    r#"
+      fn log(parts ...string) {}
+      fn log_all(parts ...string) {
+        log("a", ...parts)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_call_spread_static_value_fail() {
+  parse_str_fail(
+    r#"
+      fn log(parts ...string) {}
+      fn main() {
+        log(..."a")
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_variadic_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn log(prefix string, parts ...string) {}
+      fn log_all(parts ...string) {
+        log("a", "b", "c")
+        log("a", ...parts)
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("func log(prefix string, parts ...string) {"));
+  assert!(output.contains("log(\"a\",\"b\",\"c\")"));
+  assert!(output.contains("log(\"a\",parts...)"));
+}
+
+#[test]
+fn test_function_variadic_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn log(prefix string, parts ...string) {}
+      fn log_all(parts ...string) {
+        log("a", ...parts)
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("function log(prefix, ...parts) {"));
+  assert!(output.contains("log(\"a\",...parts);"));
+}
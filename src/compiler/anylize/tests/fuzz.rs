@@ -0,0 +1,231 @@
+use super::*;
+use tokenize::{globals::MAX_DEPTH, Tokenizer};
+
+/// Programs that together use most of the language, the tests below cut and mutate these
+static CORPUS: [&str; 3] = [
+  r#"
+    import utils "./utils"
+
+    /// A shape
+    #[target(go)]
+    enum Shape {
+      circle(radius f64)
+      square(size f64, name string)
+    }
+
+    enum Level {
+      low = 1
+      high = low + 1
+    }
+
+    type Meters = f64
+    type Id int
+
+    struct User {
+      /// The name of the user
+      name string
+      friends []User
+      position struct {
+        x int
+        y int
+      }
+    }
+
+    const max_users: int = 10 * 2
+
+    #[bind(js, "console.log")]
+    extern fn log(value string)
+
+    fn area(shape Shape) f64 {
+      match shape {
+        Shape.circle(r) => return r * r
+        Shape.square(s, _) => {
+          return s * s
+        }
+        _ => return 0.0
+      }
+    }
+
+    fn parse(input string, parts ...string) (int, string) throws {
+      if input == "" {
+        throw "empty"
+      } else if input != "a" && true || false {
+        return 1, "a"
+      } else {
+        return 2, input
+      }
+    }
+  "#,
+  r#"
+    fn main() throws {
+      defer log("done")
+      let (a, b) = parse("a", "b", "c")?
+      let c: int = a % 3
+      let result = parse(input: "b") catch err {
+        log(err)
+      }
+      'outer: for i, item in items {
+        for j in 0..=10 step 2 {
+          if j >= 4 {
+            continue 'outer
+          }
+          break
+        }
+      }
+      while a < 10 {
+        a = a + 1
+      }
+      loop {
+        break
+      }
+      #[target(js)]
+      log(utils.name(...b))
+    }
+  "#,
+  "fn a(){let b=(1,(2,3))\n}/* comment */// end",
+];
+
+/// A small random number generator so the tests are the same on every run
+struct Random(u64);
+
+impl Random {
+  fn next(&mut self) -> u64 {
+    // xorshift64
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    self.0
+  }
+  fn below(&mut self, max: usize) -> usize {
+    (self.next() % max as u64) as usize
+  }
+}
+
+/// Tokenizes bytes, this must return a result and never panic
+fn tokenize(bytes: Vec<u8>) -> Result<Tokenizer, LocationError> {
+  let res = Tokenizer::tokenize(File::new(0, bytes, "main.tp"));
+  if let Err(err) = &res {
+    // Formatting the error must also work for every location
+    err.to_string();
+  }
+  res
+}
+
+#[test]
+fn test_fuzz_corpus_is_valid() {
+  for code in CORPUS.iter() {
+    if let Err(err) = tokenize(code.as_bytes().to_vec()) {
+      panic!("{}", err);
+    }
+  }
+}
+
+#[test]
+fn test_fuzz_half_typed_code() {
+  for code in CORPUS.iter() {
+    for end in 0..code.len() {
+      let _ = tokenize(code.as_bytes()[..end].to_vec());
+    }
+  }
+}
+
+#[test]
+fn test_fuzz_mutated_code() {
+  let mut random = Random(0x2545_f491_4f6c_dd1d);
+  for code in CORPUS.iter() {
+    for _ in 0..500 {
+      let mut bytes = code.as_bytes().to_vec();
+      for _ in 0..1 + random.below(4) {
+        let index = random.below(bytes.len());
+        match random.below(3) {
+          0 => {
+            bytes.remove(index);
+          }
+          1 => bytes.insert(index, random.next() as u8),
+          _ => {
+            let other = random.below(bytes.len());
+            bytes.swap(index, other);
+          }
+        }
+      }
+      let _ = tokenize(bytes);
+    }
+  }
+}
+
+#[test]
+fn test_fuzz_random_bytes() {
+  let mut random = Random(0x9e37_79b9_7f4a_7c15);
+  for _ in 0..2000 {
+    let len = random.below(100);
+    let bytes: Vec<u8> = (0..len).map(|_| random.next() as u8).collect();
+    let _ = tokenize(bytes);
+  }
+}
+
+#[test]
+fn test_fuzz_random_tokens() {
+  let words = [
+    "fn", "let", "const", "if", "else", "for", "in", "while", "loop", "match", "return", "break",
+    "continue", "throw", "throws", "catch", "defer", "struct", "enum", "type", "import", "extern",
+    "pub", "true", "false", "step", "foo", "bar", "1", "2.5", "\"str\"", "'outer", "(", ")", "{",
+    "}", "[", "]", ",", ":", ".", "..", "..=", "...", "?", "#", "=", "==", "=>", "+", "-", "*",
+    "/", "%", "<", ">=", "&&", "||", "!", " ", "\n", "//", "/*", "*/", "///", "\"", "'", "\\",
+  ];
+  let mut random = Random(0xd1b5_4a32_d192_ed03);
+  for _ in 0..3000 {
+    let mut code = String::new();
+    for _ in 0..random.below(60) {
+      code.push_str(words[random.below(words.len())]);
+      if random.below(2) == 0 {
+        code.push(' ');
+      }
+    }
+    let _ = tokenize(code.into_bytes());
+  }
+}
+
+#[test]
+fn test_fuzz_deeply_nested_code() {
+  let cases = [
+    format!("fn a() {{ let b = {} }}", "(".repeat(100_000)),
+    format!("fn a() {}", "{ if a ".repeat(100_000)),
+    format!("fn a(b {}int) {{}}", "[]".repeat(100_000)),
+    format!("struct A {{ {} }}", "b struct { ".repeat(100_000)),
+    format!("fn a() int {{ return {}1 }}", "1 + ".repeat(100_000)),
+  ];
+  // Runs on a new thread so the test uses the default stack size instead of the one of the test runner
+  std::thread::spawn(move || {
+    for code in cases.iter() {
+      assert!(tokenize(code.as_bytes().to_vec()).is_err());
+    }
+  })
+  .join()
+  .unwrap();
+}
+
+#[test]
+fn test_nested_code_below_max_depth() {
+  let depth = MAX_DEPTH - 2;
+  let cases = [
+    format!(
+      "fn a() int {{ return {}1{} }}",
+      "(".repeat(depth),
+      ")".repeat(depth)
+    ),
+    format!(
+      "fn a(b bool) {{ {}{} }}",
+      "if b { ".repeat(depth),
+      "}".repeat(depth)
+    ),
+    format!("fn a() int {{ return {}1 }}", "1 + ".repeat(depth)),
+  ];
+  std::thread::spawn(move || {
+    for code in cases {
+      parse_str_to_lang(code.clone(), Lang::Go);
+      parse_str_to_lang(code, Lang::JS);
+    }
+  })
+  .join()
+  .unwrap();
+}
@@ -106,3 +106,112 @@ fn test_advanced_global_type_4() {
     "#,
   );
 }
+
+#[test]
+fn test_newtype_global_type() {
+  parse_str(
+    r#"
+      type Meters int
+      type Names []string
+    "#,
+  );
+}
+
+#[test]
+fn test_alias_is_the_same_type() {
+  parse_str(
+    r#"
+      type Count = int
+
+      fn double(value int) int {
+        return value
+      }
+
+      fn main() {
+        let a: Count = 2
+        let b: int = a
+        let c: Count = double(b)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_newtype_is_a_distinct_type() {
+  parse_str(
+    r#"
+      type Meters int
+
+      fn walk(distance Meters) Meters {
+        return distance
+      }
+
+      fn main() {
+        let a: Meters = 2
+        let b = walk(a)
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      type Meters int
+
+      fn main() {
+        let a: Meters = 2
+        let b: int = a
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      type Meters int
+      type Feet int
+
+      fn walk(distance Meters) {}
+
+      fn main() {
+        let a: Feet = 2
+        walk(a)
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      type Meters int
+
+      fn walk(distance int) Meters {
+        return distance
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_alias_of_newtype() {
+  parse_str(
+    r#"
+      type Meters int
+      type Distance = Meters
+
+      fn walk(distance Distance) {}
+
+      fn main() {
+        let a: Meters = 2
+        walk(a)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_global_type_output() {
+  let go = parse_str_to_lang(
+    r#"
+      type Count = int
+      type Meters int
+    "#,
+    Lang::Go,
+  );
+  assert!(go.contains("type Count = int"));
+  assert!(go.contains("type Meters int"));
+}
@@ -167,3 +167,137 @@ fn test_invalid_if_else_if_else() {
     "#,
   );
 }
+
+#[test]
+fn test_if_with_operators() {
+  parse_str(
+    r#"
+      fn test(a int, b int) {
+        if a == b || a > 10 && b <= 2 {
+        } else if a != b {
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_with_operators_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn test(a int, b int) int {
+        if a==b && (a + 1) * 2 > b {
+          return a - (b - 1)
+        }
+        return a + b * 2
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("if a == b && (a + 1) * 2 > b {"));
+  assert!(output.contains("return a - (b - 1)"));
+  assert!(output.contains("return a + b * 2"));
+}
+
+#[test]
+fn test_if_with_operators_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn test(a int, b int) {
+        if a == b || a != 2 {
+        }
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("if (a === b || a !== 2) {"));
+}
+
+#[test]
+fn test_unary_operators() {
+  parse_str(
+    r#"
+      fn test(a int, b bool, c f64) {
+        if !b && -a < 0 && -c > -1.5 && !(a > 2) {
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_unary_operators_fail() {
+  parse_str_fail(
+    r#"
+      fn test(a int) {
+        if !a {
+        }
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(a string) {
+        let b = -a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_unary_operators_output() {
+  let code = r#"
+    fn test(a int, b bool) int {
+      if !b && !(a > 2) {
+        return -1
+      }
+      return -(a + 1) * 2
+    }
+  "#;
+  let output = parse_str_to_lang(code, Lang::Go);
+  assert!(output.contains("if !b && !(a > 2) {"));
+  assert!(output.contains("return -1"));
+  assert!(output.contains("return -(a + 1) * 2"));
+
+  let output = parse_str_to_lang(code, Lang::JS);
+  assert!(output.contains("if (!b && !(a > 2)) {"));
+  assert!(output.contains("return -1;"));
+  assert!(output.contains("return -(a + 1) * 2;"));
+}
+
+#[test]
+fn test_integer_division_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn test(a int, b int, c f64) f64 {
+        let d = a / b
+        let e = 2 * (a / 2)
+        return c / 2.0
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("let d = Math.trunc(a / b);"));
+  assert!(output.contains("let e = 2 * Math.trunc(a / 2);"));
+  assert!(output.contains("return c / 2;"));
+}
+
+#[test]
+fn test_const_integer_division_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      const a = 7
+      const f = 7.0
+      fn test() f64 {
+        let b = a / 2
+        let c = b / 2
+        return f / 2
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("let b = Math.trunc(a / 2);"), "{}", output);
+  // Locals get the type of the constant they are made from
+  assert!(output.contains("let c = Math.trunc(b / 2);"), "{}", output);
+  assert!(output.contains("return f / 2;"), "{}", output);
+}
@@ -66,3 +66,256 @@ fn simple_import_with_something_after() {
     );
   }
 }
+
+/// The files of a project with a utils module imported by main.tp
+fn with_utils(main: &str) -> HashMap<String, String> {
+  let utils = r#"
+    const max = 10
+    struct Point {
+      x int
+      y int
+    }
+    enum Shape {
+      square
+      circle(radius f64)
+    }
+    type Name = string
+    fn add(a int, b int) int {
+      return a + b
+    }
+    fn origin() Point {
+      return origin()
+    }
+    fn name() Name {
+      return "utils"
+    }
+    fn parse(value string) int throws {
+      throw value
+    }
+  "#;
+  [
+    (String::from("main.tp"), String::from(main)),
+    (String::from("utils.tp"), String::from(utils)),
+  ]
+  .iter()
+  .cloned()
+  .collect()
+}
+
+#[test]
+fn test_imported_items() {
+  parse_files(with_utils(
+    r#"
+      import Utils "./utils.tp"
+      fn main() {
+        let a: int = Utils.add(1, Utils.max)
+        let point: Utils.Point = Utils.origin()
+        let shape: Utils.Shape = Utils.Shape.circle(1.5)
+        let square = Utils.Shape.square
+        let name: string = Utils.name()
+        let b = Utils.parse("1") catch err {
+          return
+        }
+      }
+    "#,
+  ));
+}
+
+#[test]
+fn test_imported_function_checks() {
+  parse_files_fail(
+    with_utils(
+      r#"
+        import Utils "./utils.tp"
+        fn main() {
+          Utils.add(1, "2")
+        }
+      "#,
+    ),
+    "Expected int, found string",
+  );
+  parse_files_fail(
+    with_utils(
+      r#"
+        import Utils "./utils.tp"
+        fn main() {
+          let a: int = Utils.origin()
+        }
+      "#,
+    ),
+    "Expected int, found Utils.Point",
+  );
+  parse_files_fail(
+    with_utils(
+      r#"
+        import Utils "./utils.tp"
+        fn main() {
+          Utils.parse("1")
+        }
+      "#,
+    ),
+    "handle the error",
+  );
+}
+
+#[test]
+fn test_unknown_member() {
+  let cases = vec!["Utils.foo()", "let a = Utils.min", "let a: Utils.Line = 1"];
+  for case in cases {
+    let main = format!("import Utils \"./utils.tp\"\nfn main() {{\n{}\n}}", case);
+    parse_files_fail(with_utils(&main), "The module Utils has no item named");
+  }
+}
+
+#[test]
+fn test_unknown_module() {
+  let cases = vec!["Foo.add(1, 2)", "let a = Foo.max", "let a: Foo.Point = 1"];
+  for case in cases {
+    let main = format!("import Utils \"./utils.tp\"\nfn main() {{\n{}\n}}", case);
+    parse_files_fail(with_utils(&main), "There is no imported module named Foo");
+  }
+}
+
+#[test]
+fn test_imported_module_is_checked_once() {
+  parse_files(
+    [
+      (
+        String::from("main.tp"),
+        String::from("import\n  A \"./a.tp\"\n  B \"./b.tp\"\nfn main() {\n A.a()\n B.b()\n}"),
+      ),
+      (
+        String::from("a.tp"),
+        String::from("import B \"./b.tp\"\nfn a() {\n B.b()\n}"),
+      ),
+      (String::from("b.tp"), String::from("fn b() {}")),
+    ]
+    .iter()
+    .cloned()
+    .collect(),
+  );
+}
+
+#[test]
+fn test_import_cycle() {
+  let files: HashMap<String, String> = [
+    (
+      String::from("main.tp"),
+      String::from("import A \"./a.tp\"\nfn main() {}"),
+    ),
+    (
+      String::from("a.tp"),
+      String::from("fn a() {}\nimport B \"./b.tp\""),
+    ),
+    (String::from("b.tp"), String::from("import A \"./a.tp\"")),
+  ]
+  .iter()
+  .cloned()
+  .collect();
+
+  let res = compile(files, None);
+  let errors: Vec<String> = res
+    .borrow()
+    .errors
+    .iter()
+    .map(|error| error.to_string())
+    .collect();
+  assert_eq!(errors.len(), 2);
+  for error in &errors {
+    assert!(error.contains("Files import each other main.tp -> a.tp -> b.tp -> a.tp"));
+  }
+  assert!(errors[0].contains("a.tp:2:8"));
+  assert!(errors[1].contains("b.tp:1:8"));
+}
+
+#[test]
+fn test_import_self() {
+  parse_files_fail(
+    [(
+      String::from("main.tp"),
+      String::from("import Main \"./main.tp\""),
+    )]
+    .iter()
+    .cloned()
+    .collect(),
+    "Files import each other main.tp -> main.tp",
+  );
+}
+
+#[test]
+fn test_imported_items_go_output() {
+  let output = parse_files_to_lang(
+    with_utils(
+      r#"
+        import Utils "./utils.tp"
+        const max = 1
+        fn area(shape Utils.Shape) f64 {
+          match shape {
+            Utils.Shape.circle(radius) => {
+              return radius * radius
+            }
+            _ => {}
+          }
+          return 0
+        }
+        fn main() {
+          let max = Utils.add(1, Utils.max)
+          let point: Utils.Point = Utils.origin()
+          let circle = area(Utils.Shape.circle(1.5))
+          let square = area(Utils.Shape.square)
+          let name: Utils.Name = Utils.name()
+        }
+      "#,
+    ),
+    Lang::Go,
+  );
+  // The imported module is added to the same file with the prefix of the module
+  assert_eq!(output.matches("package main").count(), 1);
+  assert!(output.contains("const max = 1"));
+  assert!(output.contains("func area(shape utils_Shape) float64 {"));
+  assert!(output.contains("if __value, __ok := shape.(utils_ShapeCircle); __ok {"));
+  assert!(output.contains("max := utils_add(1,utils_max)"));
+  assert!(output.contains("point := utils_origin()"));
+  assert!(output.contains("circle := area(utils_ShapeCircle{1.5})"));
+  assert!(output.contains("square := area(utils_ShapeSquare{})"));
+  assert!(output.contains("name := utils_name()"));
+
+  // The items of the module refer to each other with the prefix
+  assert!(output.contains("const utils_max = 10"));
+  assert!(output.contains("type utils_Point struct"));
+  assert!(output.contains("type utils_Shape interface {\n  isutils_Shape()\n}"));
+  assert!(output.contains("func (utils_ShapeCircle) isutils_Shape() {}"));
+  assert!(output.contains("type utils_Name = string"));
+  assert!(output.contains("func utils_add(a int, b int) int {"));
+  assert!(output.contains("func utils_origin() utils_Point {\n  return utils_origin()\n}"));
+  assert!(output.contains("func utils_name() utils_Name {"));
+  // The packages used by the module are imported
+  assert!(output.contains("\"errors\""));
+}
+
+#[test]
+fn test_nested_imports_go_output() {
+  let output = parse_files_to_lang(
+    [
+      (
+        String::from("main.tp"),
+        String::from("import\n  A \"./a.tp\"\n  B \"./lib/b.tp\"\nfn main() {\n A.a()\n B.b()\n}"),
+      ),
+      (
+        String::from("a.tp"),
+        String::from("import B \"./lib/b.tp\"\nconst size = 2\nfn a() int {\n let size = B.b()\n return size\n}"),
+      ),
+      (String::from("lib/b.tp"), String::from("fn b() int {\n return 1\n}")),
+    ]
+    .iter()
+    .cloned()
+    .collect(),
+    Lang::Go,
+  );
+  assert!(output.contains("a_a()\n  lib_b_b()"));
+  // A module imported twice is only added once
+  assert_eq!(output.matches("func lib_b_b() int {").count(), 1);
+  // Variables of the module don't get the prefix
+  assert!(output.contains("size := lib_b_b()\n  return size"));
+  assert!(output.contains("const a_size = 2"));
+}
@@ -0,0 +1,114 @@
+use super::*;
+
+#[test]
+fn test_names_starting_with_keywords() {
+  let tokens = parse_str(
+    r#"
+      fn iffy() {}
+      fn elsewhere() {}
+      fn test() {
+        let format = 1
+        let returned = format
+        if returned == 1 {
+          iffy()
+        }
+        elsewhere()
+      }
+    "#,
+  );
+  let actions = &tokens.functions["test"].body.actions;
+  assert_eq!(actions.len(), 4);
+  match &actions[3].type_ {
+    ActionType::FunctionCall(call) if call.name == "elsewhere" => {}
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_else_on_next_line() {
+  let tokens = parse_str(
+    r#"
+      fn test(a int) {
+        if a == 1 {
+        }
+        else if a == 2 {
+        }
+        else {
+        }
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::If(if_) if if_.else_ifs.len() == 1 && if_.else_body.is_some() => {}
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_comment_chars_in_string() {
+  let tokens = parse_str(
+    r#"
+      fn test() {
+        let url = "http://example.com/*"
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::Variable(var) => match &var.action.type_ {
+      ActionType::StaticString(string) if string.content == "http://example.com/*" => {}
+      _ => panic!("{:?}", tokens),
+    },
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_return_without_value_in_match_arm() {
+  let tokens = parse_str(
+    r#"
+      fn test(a int) {
+        match a {
+          1 => return
+          _ => test(1)
+        }
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::Match(match_) if match_.arms.len() == 2 => {}
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_keyword_as_name() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let else = 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_invalid_char() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = 1 @ 2
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_unterminated_string() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = "foo
+      }
+    "#,
+  );
+}
@@ -38,7 +38,7 @@ fn test_simple_for_names() {
         panic!("{:?}", tokens);
       }
       let list = &res.actions.actions;
-      if list.len() != 0 {
+      if !list.is_empty() {
         panic!("{:?}", tokens);
       }
       match &res.list.type_ {
@@ -264,3 +264,282 @@ fn test_loop_unreachable_code_warning_return() {
     "#,
   );
 }
+
+#[test]
+fn test_for_with_index() {
+  let tokens = parse_str(
+    r#"
+      fn test(items []string) {
+        for i, item in items {}
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::For(res) => {
+      if res.item_name != "item" || res.index_name != Some(String::from("i")) {
+        panic!("{:?}", tokens);
+      }
+    }
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_for_with_equal_index_and_item_names_fail() {
+  parse_str_fail(
+    r#"
+      fn test(items []string) {
+        for item, item in items {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_for_range() {
+  let tokens = parse_str(
+    r#"
+      fn test() {
+        for i in 0..10 {}
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::For(res) => match &res.list.type_ {
+      ActionType::Range(range) if !range.inclusive && range.step.is_none() => {}
+      _ => panic!("{:?}", tokens),
+    },
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_for_inclusive_range_with_step() {
+  let tokens = parse_str(
+    r#"
+      fn test(end int) {
+        for i in 0..=end step 2 {}
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::For(res) => match &res.list.type_ {
+      ActionType::Range(range) if range.inclusive && range.step.is_some() => {}
+      _ => panic!("{:?}", tokens),
+    },
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_for_range_with_index_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        for i, n in 0..10 {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_for_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn main() {
+        let items = "abc"
+        for item in items {}
+        for i, item in items {}
+        for i, _ in items {}
+        for i in 0..10 {}
+        for i in 0..=10 step 2 {}
+      }
+    "#,
+    Lang::Go,
+  );
+  // Go doesn't compile loop variables that are never used
+  assert!(output.contains("for _, item := range items {\n    _ = item\n  }"));
+  assert!(output.contains("for i, item := range items {\n    _ = i\n    _ = item\n  }"));
+  assert!(output.contains("for i := range items {\n    _ = i\n  }"));
+  assert!(output.contains("for i := 0; i < 10; i++ {}"));
+  assert!(output.contains("for i := 0; i <= 10; i += 2 {}"));
+}
+
+#[test]
+fn test_for_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn main() {
+        let items = "abc"
+        for item in items {}
+        for i, item in items {}
+        for i in 0..10 {}
+        for i in 0..=10 step 2 {}
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("for (const item of items) {}"));
+  assert!(output.contains("for (const [i, item] of items.entries()) {}"));
+  assert!(output.contains("for (let i = 0; i < 10; i++) {}"));
+  assert!(output.contains("for (let i = 0; i <= 10; i += 2) {}"));
+}
+
+#[test]
+fn test_labeled_loops() {
+  parse_str(
+    r#"
+      fn test(items []string) {
+        'outer: for item in items {
+          'inner: loop {
+            continue 'outer
+            break 'inner
+          }
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_labeled_loop_names() {
+  let tokens = parse_str(
+    r#"
+      fn test() {
+        'outer: while true {
+          break 'outer
+        }
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::While(res) if res.label == Some(String::from("outer")) => {
+      match &res.actions.actions[0].type_ {
+        ActionType::Break(Some(label)) if label == "outer" => {}
+        _ => panic!("{:?}", tokens),
+      }
+    }
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_break_unknown_label_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        'outer: loop {
+          break 'other
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_continue_label_outside_loop_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        'outer: loop {
+          break 'outer
+        }
+        loop {
+          continue 'outer
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_duplicated_label_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        'outer: loop {
+          break 'outer
+        }
+        'outer: loop {
+          break 'outer
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_label_on_non_loop_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        'outer: test()
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_unused_label_warning() {
+  parse_str_warning(
+    r#"
+      fn test() {
+        'outer: loop {
+          break
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_labeled_loop_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn main() {
+        'outer: loop {
+          'inner: loop {
+            continue 'outer
+          }
+        }
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("  outer:\n  for {\n    for {\n      continue outer\n"));
+}
+
+#[test]
+fn test_labeled_loop_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn main() {
+        'outer: loop {
+          loop {
+            break 'outer
+          }
+        }
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("  outer:\n  while (true) {\n    while (true) {\n      break outer;\n"));
+}
+
+#[test]
+fn test_range_step_not_positive_fail() {
+  // Ranges only count up, a step of 0 or less would never reach the end
+  let cases = vec![
+    "fn main() {\n for i in 0..10 step 0 {}\n }",
+    "fn main() {\n for i in 10..0 step -2 {}\n }",
+    "const STEP = 1 - 1\n fn main() {\n for i in 0..10 step STEP {}\n }",
+  ];
+  for code in cases {
+    let res = single_file_compile(String::from(code));
+    let errors = &res.borrow().errors;
+    assert_eq!(errors.len(), 1, "{}\n{:?}", code, errors);
+    assert!(errors[0]
+      .to_string()
+      .contains("The step of a range must be bigger than 0"));
+  }
+}
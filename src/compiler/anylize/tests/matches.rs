@@ -0,0 +1,156 @@
+use super::*;
+
+#[test]
+fn test_match() {
+  parse_str(
+    r#"
+      fn test(value int) {
+        match value {
+          1 => {}
+          2 => {}
+          _ => {}
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_match_destructure() {
+  let tokens = parse_str(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        rect(w f64, h f64)
+      }
+      fn test(shape Shape) {
+        match shape {
+          Shape.circle(r) => {}
+          Shape.rect(w, _) => {}
+        }
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::Match(res) => match &res.arms[1].pattern {
+      MatchPattern::Destructure(field, names) if field == "Shape.rect" && names.len() == 2 => {}
+      _ => panic!("{:?}", tokens),
+    },
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_match_destructure_variable_usage() {
+  parse_str(
+    r#"
+      enum Shape {
+        circle(radius f64)
+      }
+      fn test(shape Shape) {
+        match shape {
+          Shape.circle(r) => {
+            let radius = r
+          }
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_match_destructure_variable_outside_arm_fail() {
+  parse_str_fail(
+    r#"
+      enum Shape {
+        circle(radius f64)
+      }
+      fn test(shape Shape) {
+        match shape {
+          Shape.circle(r) => {}
+        }
+        let radius = r
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_match_destructure_arity_fail() {
+  parse_str_fail(
+    r#"
+      enum Shape {
+        rect(w f64, h f64)
+      }
+      fn test(shape Shape) {
+        match shape {
+          Shape.rect(w) => {}
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_match_unreachable_arm_warning() {
+  parse_str_warning(
+    r#"
+      fn test(value int) {
+        match value {
+          _ => {}
+          1 => {}
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_match_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none
+      }
+      fn test(shape Shape) {
+        match shape {
+          Shape.circle(r) => {
+            let a = r
+          }
+          _ => {}
+        }
+      }
+      fn main() {}
+    "#,
+    Lang::Go,
+  );
+  assert!(
+    output.contains("if __value, __ok := shape.(ShapeCircle); __ok {\n    r := __value.radius\n")
+  );
+  assert!(output.contains("} else {}"));
+}
+
+#[test]
+fn test_match_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none
+      }
+      fn test(shape Shape) {
+        match shape {
+          Shape.circle(r) => {
+            let a = r
+          }
+          Shape.none => {}
+        }
+      }
+      fn main() {}
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("if (shape.tag === \"circle\") {\n    const r = shape.radius;\n"));
+  assert!(output.contains("} else if (shape === Shape.none) {}"));
+}
@@ -1,12 +1,28 @@
+mod attributes;
 mod comments;
+mod conditions;
+mod consts;
+mod defers;
 mod enums;
+mod externs;
 mod functions;
+mod fuzz;
 mod general;
 mod global_types;
 mod ifs;
 mod imports;
+mod lexer;
 mod loops;
+mod matches;
+mod recursive_types;
+mod returns;
+mod scopes;
+mod spans;
 mod structs;
+mod throws;
+mod tuples;
+mod types;
+mod unused;
 mod variables;
 
 use super::*;
@@ -16,22 +32,56 @@ use std::rc::Rc;
 
 /// parse multiple files and check if the output doesn't contain any errors
 pub fn parse_files(contents: HashMap<String, String>) {
-  let res = compile(contents);
-  if res.borrow().errors.len() > 0 {
+  let res = compile(contents, None);
+  if !res.borrow().errors.is_empty() {
     panic!("{:?}", res.borrow().errors);
   }
 }
 
+/// parse multiple files and return the code generated for lang
+pub fn parse_files_to_lang(contents: HashMap<String, String>, lang: Lang) -> String {
+  let res = compile(contents, Some(lang));
+  if !res.borrow().errors.is_empty() {
+    panic!("{:?}", res.borrow().errors);
+  }
+  let borrowed_res = res.borrow();
+  borrowed_res.outputs.get("main.tp").unwrap().clone()
+}
+
+/// parse multiple files and expect an error that contains message
+pub fn parse_files_fail(contents: HashMap<String, String>, message: &str) {
+  let res = compile(contents, None);
+  let errors = &res.borrow().errors;
+  if !errors
+    .iter()
+    .any(|error| error.to_string().contains(message))
+  {
+    panic!("Expected an error containing {:?}\n{:?}", message, errors);
+  }
+}
+
 /// Parse a string of code and validate it
-pub fn parse_str<'a>(contents: impl Into<String>) -> AnilizedTokens {
+pub fn parse_str(contents: impl Into<String>) -> AnilizedTokens {
   let res = single_file_compile(contents.into());
-  if res.borrow().errors.len() > 0 {
+  if !res.borrow().errors.is_empty() {
     panic!("{:?}", res.borrow().errors);
   }
   let borrowed_res = res.borrow();
   borrowed_res.tokens.get("main.tp").unwrap().clone()
 }
 
+/// Parse a string of code and return the code generated for lang
+pub fn parse_str_to_lang(contents: impl Into<String>, lang: Lang) -> String {
+  let mut files = HashMap::new();
+  files.insert(String::from("main.tp"), contents.into());
+  let res = compile(files, Some(lang));
+  if !res.borrow().errors.is_empty() {
+    panic!("{:?}", res.borrow().errors);
+  }
+  let borrowed_res = res.borrow();
+  borrowed_res.outputs.get("main.tp").unwrap().clone()
+}
+
 /// Parse a string of code and expext it somewhere to fail
 pub fn parse_str_fail(contents: impl Into<String>) {
   parse_str_fail_meta(contents, None);
@@ -44,7 +94,7 @@ pub fn parse_str_fail_with_meta(contents: impl Into<String>, meta: impl Into<Str
 
 fn parse_str_fail_meta(contents: impl Into<String>, meta_option: Option<String>) {
   let res = single_file_compile(contents.into());
-  if res.borrow().errors.len() == 0 {
+  if res.borrow().errors.is_empty() {
     let debug_string = if let Some(tokens) = res.borrow().tokens.get("main.tp") {
       format!("{:?}", tokens)
     } else {
@@ -61,7 +111,7 @@ fn parse_str_fail_meta(contents: impl Into<String>, meta_option: Option<String>)
 /// Parse a string of code and expext a warning
 pub fn parse_str_warning(contents: impl Into<String>) {
   let res = single_file_compile(contents.into());
-  if res.borrow().warnings.len() == 0 {
+  if res.borrow().warnings.is_empty() {
     if let Some(tokens) = res.borrow().tokens.get("main.tp") {
       panic!("{:?}", tokens);
     }
@@ -69,20 +119,18 @@ pub fn parse_str_warning(contents: impl Into<String>) {
   }
 }
 
-fn single_file_compile<'a>(data: String) -> Rc<RefCell<CompilerMeta>> {
+fn single_file_compile(data: String) -> Rc<RefCell<CompilerMeta>> {
   let mut files = HashMap::new();
   files.insert(String::from("main.tp"), data);
-  compile(files)
+  compile(files, None)
 }
 
-fn compile<'a>(files: HashMap<String, String>) -> Rc<RefCell<CompilerMeta>> {
-  let meta = Rc::new(RefCell::new(CompilerMeta {
-    files,
-    errors: vec![],
-    warnings: vec![],
-    tokens: HashMap::new(),
-  }));
+fn compile(files: HashMap<String, String>, lang: Option<Lang>) -> Rc<RefCell<CompilerMeta>> {
+  run_compiler(CompilerMeta::new(files, lang))
+}
 
+fn run_compiler(meta: CompilerMeta) -> Rc<RefCell<CompilerMeta>> {
+  let meta = Rc::new(RefCell::new(meta));
   let meta_clone = Rc::clone(&meta);
   Compiler::start("main.tp", meta);
 
@@ -92,9 +140,26 @@ fn compile<'a>(files: HashMap<String, String>) -> Rc<RefCell<CompilerMeta>> {
 #[derive(Debug, Clone)]
 struct CompilerMeta {
   files: HashMap<String, String>,
+  lang: Option<Lang>,
+  deny_unused: bool,
   errors: Vec<LocationError>,
   warnings: Vec<LocationError>,
   tokens: HashMap<String, AnilizedTokens>,
+  outputs: HashMap<String, String>,
+}
+
+impl CompilerMeta {
+  fn new(files: HashMap<String, String>, lang: Option<Lang>) -> Self {
+    Self {
+      files,
+      lang,
+      deny_unused: false,
+      errors: vec![],
+      warnings: vec![],
+      tokens: HashMap::new(),
+      outputs: HashMap::new(),
+    }
+  }
 }
 
 impl CompilerProps for CompilerMeta {
@@ -107,8 +172,9 @@ impl CompilerProps for CompilerMeta {
   }
   fn get_options(&self) -> Options {
     Options {
-      lang: None,
+      lang: self.lang.clone(),
       debug: true,
+      deny_unused: self.deny_unused,
     }
   }
   fn warning(&mut self, warnings: LocationError) {
@@ -120,5 +186,7 @@ impl CompilerProps for CompilerMeta {
   fn debug_formatted_tokens(&mut self, file_name: String, tokens: AnilizedTokens) {
     self.tokens.insert(file_name, tokens);
   }
-  fn debug_parsed_output(&mut self, _: String, _: String) {}
+  fn debug_parsed_output(&mut self, file_name: String, src: String) {
+    self.outputs.insert(file_name, src);
+  }
 }
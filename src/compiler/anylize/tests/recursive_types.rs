@@ -0,0 +1,99 @@
+use super::*;
+
+#[test]
+fn test_recursion_through_arrays() {
+  parse_str(
+    r#"
+      struct Node {
+        children []Node
+      }
+      type Tree []Tree
+      type Forest = []Node
+      struct Park {
+        forest Forest
+        trees [][]Park
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_recursion_through_enums() {
+  // Enums with data are stored behind an interface
+  parse_str(
+    r#"
+      enum Expr {
+        number(value int)
+        add(left Expr, right Expr)
+      }
+      struct Foo {
+        bar enum {
+          a(foo Foo)
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_recursive_type_fail() {
+  let cases = vec![
+    "struct Foo {\n foo Foo\n }",
+    "struct Foo {\n bar Bar\n }\n struct Bar {\n foo Foo\n }",
+    "struct Foo {\n foo (int, Foo)\n }",
+    "struct Foo {\n bar struct {\n foo Foo\n }\n }",
+    "type A = B\n type B = A",
+    "type A B\n type B A",
+    "type A = []A",
+    "type A = struct {\n next []A\n }",
+    "struct Foo {\n bar Bar\n }\n type Bar = Foo",
+  ];
+  for code in cases {
+    parse_str_fail_with_meta(code, code);
+  }
+}
+
+#[test]
+fn test_recursive_type_message() {
+  let res = single_file_compile(String::from(
+    r#"
+      struct Foo {
+        bar Bar
+      }
+      struct Bar {
+        baz Baz
+      }
+      type Baz = Foo
+    "#,
+  ));
+  let errors = &res.borrow().errors;
+  assert_eq!(errors.len(), 1, "{:?}", errors);
+  assert!(errors[0]
+    .to_string()
+    .contains("The type contains itself Bar -> Baz -> Foo -> Bar"));
+}
+
+#[test]
+fn test_recursive_type_reported_once() {
+  let cases = vec![
+    "struct Foo {\n a Foo\n b Foo\n }",
+    "type A = B\n type B = A",
+    "type A = A",
+    "struct Foo {\n bar Bar\n }\n type Bar = Foo",
+  ];
+  for code in cases {
+    let res = single_file_compile(String::from(code));
+    let errors = &res.borrow().errors;
+    assert_eq!(errors.len(), 1, "{}\n{:?}", code, errors);
+  }
+}
+
+#[test]
+fn test_recursive_alias_message() {
+  let res = single_file_compile(String::from("type L = []L"));
+  let errors = &res.borrow().errors;
+  assert_eq!(errors.len(), 1, "{:?}", errors);
+  assert!(errors[0]
+    .to_string()
+    .contains("The alias refers to itself L -> L, use a new type like `type A []A` instead"));
+}
@@ -0,0 +1,210 @@
+use super::*;
+
+#[test]
+fn test_function_returns_value() {
+  parse_str(
+    r#"
+      fn test(a int) string {
+        if a > 1 {
+          return "foo"
+        }
+        return "bar"
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_function_without_return_fail() {
+  parse_str_fail(
+    r#"
+      fn test() string {}
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() string {
+        let a = "foo"
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_else_returns() {
+  parse_str(
+    r#"
+      fn test(a int) string {
+        if a > 1 {
+          return "foo"
+        } else if a > 0 {
+          return "bar"
+        } else {
+          return "baz"
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_if_without_else_fail() {
+  parse_str_fail(
+    r#"
+      fn test(a int) string {
+        if a > 1 {
+          return "foo"
+        } else if a > 0 {
+          return "bar"
+        }
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(a int) string {
+        if a > 1 {
+          return "foo"
+        } else {
+          let b = 1
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_throw_ends_function() {
+  parse_str(
+    r#"
+      fn test(a int) string throws {
+        if a > 1 {
+          return "foo"
+        }
+        throw "a is too small"
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_infinite_loop_ends_function() {
+  parse_str(
+    r#"
+      fn test() string {
+        loop {}
+      }
+    "#,
+  );
+  parse_str(
+    r#"
+      fn test(a int) int {
+        'outer: loop {
+          loop {
+            break
+          }
+          for i in 0..10 {
+            continue 'outer
+          }
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_loop_with_break_fail() {
+  parse_str_fail(
+    r#"
+      fn test(a int) int {
+        loop {
+          if a > 1 {
+            break
+          }
+        }
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() int {
+        'outer: loop {
+          loop {
+            break 'outer
+          }
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_match_returns() {
+  parse_str(
+    r#"
+      fn test(a int) string {
+        match a {
+          1 => return "one"
+          _ => return "many"
+        }
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(a int) string {
+        match a {
+          1 => return "one"
+          2 => return "two"
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_void_function_returns_value_fail() {
+  parse_str(
+    r#"
+      fn foo() {}
+      fn test() {
+        return foo()
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        return "foo"
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_bare_return_in_function_with_value_fail() {
+  parse_str_fail(
+    r#"
+      fn test(a int) int {
+        if a > 1 {
+          return
+        }
+        return a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_missing_return_message() {
+  let res = single_file_compile(String::from(
+    r#"
+      fn test() string {}
+    "#,
+  ));
+  let errors = &res.borrow().errors;
+  assert_eq!(errors.len(), 1);
+  assert!(errors[0]
+    .to_string()
+    .contains("can reach its end without returning a value of type string"));
+}
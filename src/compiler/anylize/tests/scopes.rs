@@ -0,0 +1,129 @@
+use super::*;
+
+#[test]
+fn test_loop_variables_exist_inside_loop() {
+  parse_str(
+    r#"
+      fn test(list []string) {
+        for item in list {
+          let a: string = item
+        }
+        for i, item in list {
+          let b: int = i
+        }
+        for i in 0..10 {
+          let c: int = i
+        }
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(list []string) {
+        for item in list {}
+        let a = item
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(list []string) {
+        for item in list {
+          let a: int = item
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_block_variables_exist_inside_block() {
+  parse_str(
+    r#"
+      fn test(a int) {
+        if a > 1 {
+          let b = 1
+        } else {
+          let b = 2
+        }
+        let b = 3
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(a int) {
+        if a > 1 {
+          let b = 1
+        }
+        let c = b
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_shadowing() {
+  // Locals can shadow globals
+  parse_str(
+    r#"
+      const foo = 1
+      fn test() {
+        let foo = "bar"
+        let a: string = foo
+      }
+    "#,
+  );
+
+  // But not other locals or arguments
+  let cases = vec![
+    "fn test() {\n let a = 1\n if a > 1 {\n let a = 2\n }\n }",
+    "fn test(a int) {\n let a = 1\n }",
+    "fn test(a []int) {\n for a in a {}\n }",
+    "fn test(a []int) {\n for i, i in a {}\n }",
+    "fn foo() throws {}\n fn test(err string) {\n foo() catch err {}\n }",
+    "fn test() {\n let (a, a) = (1, 2)\n }",
+  ];
+  for code in cases {
+    parse_str_fail_with_meta(code, code);
+  }
+}
+
+#[test]
+fn test_variable_can_not_refer_to_itself() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_symbol_table() {
+  let code = r#"
+      fn test(list []int) {
+        let total = 0
+        for item in list {
+          total = item
+        }
+      }
+    "#;
+  let res = parse_str(code);
+  let table = res.symbols.get("test").unwrap();
+  let names: Vec<&str> = table.symbols.iter().map(|s| s.name.as_str()).collect();
+  assert_eq!(names, vec!["list", "total", "item"]);
+
+  let item = &table.symbols[2];
+  assert_eq!(item.kind, SymbolKind::LoopVariable);
+  assert_eq!(item.references.len(), 1);
+
+  let total = &table.symbols[1];
+  assert_eq!(total.references.len(), 0);
+  assert_eq!(total.assignments.len(), 1);
+
+  // Looking up the symbol at the reference to item
+  let position = code.find("= item").unwrap() + 2;
+  assert_eq!(table.symbol_at(position).unwrap().name, "item");
+}
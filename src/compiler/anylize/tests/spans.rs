@@ -0,0 +1,62 @@
+use super::*;
+
+#[test]
+fn test_line_col() {
+  let file = File::new(0, b"ab\ncd\n\nef".to_vec(), "main.tp");
+  assert_eq!(file.line_col(0), (1, 1));
+  assert_eq!(file.line_col(4), (2, 2));
+  assert_eq!(file.line_col(6), (3, 1));
+  assert_eq!(file.line_col(8), (4, 2));
+}
+
+#[test]
+fn test_line_col_many_lines() {
+  let file = File::new(0, "\n".repeat(70_000).into_bytes(), "main.tp");
+  assert_eq!(file.line_col(70_000), (70_001, 1));
+}
+
+#[test]
+fn test_action_span() {
+  let code = r#"
+    fn test() throws {
+      let a = 1 + 2
+      test()?
+    }
+  "#;
+  let tokens = parse_str(code);
+  let actions = &tokens.functions["test"].body.actions;
+  let text = |location: &Span| &code[location.start..location.end];
+  assert_eq!(text(&actions[0].location), "let a = 1 + 2");
+  match &actions[0].type_ {
+    ActionType::Variable(var) => assert_eq!(text(&var.action.location), "1 + 2"),
+    _ => panic!("{:?}", tokens),
+  }
+  assert_eq!(text(&actions[1].location), "test()?");
+}
+
+#[test]
+fn test_error_underlines_span() {
+  let file = File::new(0, b"fn test() {\n  foo(bar)\n}".to_vec(), "main.tp");
+  let error = file.must_error(
+    AnylizeErrAndWarns::VariableRefDoesNotExist,
+    Span::new(file.id, 18, 21),
+  );
+  let output = error.to_string();
+  assert!(output.contains("main.tp:2:7"));
+  assert!(output.contains("2:   foo(bar)\n         ^^^ "));
+}
+
+#[test]
+fn test_error_shown_in_file_of_span() {
+  let main = File::new(0, b"fn main() {}".to_vec(), "main.tp");
+  let utils = File::new(1, b"\nfn foo(bar int) {}".to_vec(), "utils.tp");
+  let files = [(main.id, main), (utils.id, utils.clone())];
+  let mut results = AnylizeResults::new(files.iter().cloned().collect(), false);
+  results.add(
+    AnylizeErrAndWarns::VariableRefDoesNotExist,
+    &Span::new(utils.id, 8, 11),
+  );
+  let output = results.errors[0].to_string();
+  assert!(output.contains("utils.tp:2:8"));
+  assert!(output.contains("2: fn foo(bar int) {}\n          ^^^ "));
+}
@@ -0,0 +1,325 @@
+use super::*;
+
+#[test]
+fn test_throwing_function() {
+  let tokens = parse_str(
+    r#"
+      fn test() int throws {
+        throw "foo"
+      }
+    "#,
+  );
+  assert!(tokens.functions["test"].throws);
+}
+
+#[test]
+fn test_throwing_function_without_result() {
+  let tokens = parse_str(
+    r#"
+      fn test() throws {
+        throw "foo"
+      }
+    "#,
+  );
+  assert!(tokens.functions["test"].throws);
+  assert!(tokens.functions["test"].res.is_none());
+}
+
+#[test]
+fn test_throw_outside_throwing_function_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        throw "foo"
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_main_cannot_throw_fail() {
+  parse_str_fail(
+    r#"
+      fn main() throws {}
+    "#,
+  );
+}
+
+#[test]
+fn test_unhandled_error_fail() {
+  parse_str_fail(
+    r#"
+      fn foo() throws {}
+      fn test() {
+        foo()
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_propagate_error() {
+  parse_str(
+    r#"
+      fn foo() int throws {
+        return 1
+      }
+      fn test() throws {
+        foo()?
+        let a = foo()?
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_propagate_outside_throwing_function_fail() {
+  parse_str_fail(
+    r#"
+      fn foo() throws {}
+      fn test() {
+        foo()?
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_propagate_inside_argument_fail() {
+  parse_str_fail(
+    r#"
+      fn foo() int throws {
+        return 1
+      }
+      fn bar(a int) {}
+      fn test() throws {
+        bar(foo()?)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_catch_error() {
+  parse_str(
+    r#"
+      fn foo() int throws {
+        return 1
+      }
+      fn test() {
+        foo() catch {}
+        let a = foo() catch err {
+          let message = err
+          return
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_catch_value_must_leave() {
+  // Without a value for the variable the body can't continue after the catch
+  let res = single_file_compile(String::from(
+    "fn foo() int throws {\n return 1\n }\n fn test() {\n let a = foo() catch {}\n let (b, c) = (1, 2)\n }",
+  ));
+  let errors = &res.borrow().errors;
+  assert_eq!(errors.len(), 1, "{:?}", errors);
+  assert!(errors[0].to_string().contains(
+    "The catch has no value for the variable, end it with return, throw, break or continue"
+  ));
+
+  parse_str(
+    r#"
+      fn foo() int throws {
+        return 1
+      }
+      fn pair() (int, int) throws {
+        return 1, 2
+      }
+      fn test() int throws {
+        for i in 0..10 {
+          let a = foo() catch {
+            continue
+          }
+          let b = foo() catch err {
+            if a > 1 {
+              break
+            } else {
+              throw err
+            }
+          }
+          let (c, d) = pair() catch {
+            return b
+          }
+        }
+        return 0
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_catch_unused_error_go_output() {
+  // Go doesn't compile variables that are never used
+  let output = parse_str_to_lang(
+    r#"
+      fn parse() int throws {
+        throw "foo"
+      }
+      fn main() {
+        parse() catch err {}
+        parse() catch err {
+          let message = err
+        }
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("if _, __err := parse(); __err != nil {}"));
+  assert!(output.contains("if _, __err := parse(); __err != nil {\n    err := __err.Error()"));
+}
+
+#[test]
+fn test_catch_without_error_fail() {
+  parse_str_fail(
+    r#"
+      fn foo() {}
+      fn test() {
+        foo() catch {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_catch_error_variable_outside_catch_fail() {
+  parse_str_fail(
+    r#"
+      fn foo() throws {}
+      fn test() {
+        foo() catch err {}
+        let message = err
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_throws_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn parse() int throws {
+        throw "foo"
+      }
+      fn test() (int, string) throws {
+        let a = parse()?
+        return a, "b"
+      }
+      fn main() {
+        parse() catch {}
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("import (\n  \"errors\"\n)"));
+  assert!(output.contains("func parse() (int, error) {\n  return 0, errors.New(\"foo\")\n}"));
+  assert!(output.contains("func test() (int, string, error) {"));
+  assert!(
+    output.contains("a, __err := parse()\n  if __err != nil {\n    return 0, \"\", __err\n  }")
+  );
+  assert!(output.contains("return a, \"b\", nil"));
+  assert!(output.contains("if _, __err := parse(); __err != nil {}"));
+}
+
+#[test]
+fn test_throws_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn parse() int throws {
+        throw "foo"
+      }
+      fn main() {
+        let a = parse() catch err {
+          return
+        }
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("throw new Error(\"foo\");"));
+  assert!(output.contains(
+    "let a;\n  try {\n    a = parse();\n  } catch (__err) {\n    const err = __err.message;\n    return;\n  }"
+  ));
+}
+
+#[test]
+fn test_throws_without_result_returns_nil_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn check(a int) throws {
+        if a > 1 {
+          throw "too big"
+        }
+      }
+      fn fail() throws {
+        throw "foo"
+      }
+      fn main() {
+        check(1) catch {}
+        fail() catch {}
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("    return errors.New(\"too big\")\n  }\n  return nil\n}"));
+  assert!(output.contains("func fail() error {\n  return errors.New(\"foo\")\n}"));
+}
+
+#[test]
+fn test_catch_error_name_does_not_collide_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn parse() throws {
+        throw "foo"
+      }
+      fn main() {
+        parse() catch _err {}
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("} catch (__err) {\n    const _err = __err.message;\n  }"));
+}
+
+#[test]
+fn test_one_error_per_call() {
+  let cases = vec![
+    (
+      "fn thrower() throws {}\n fn test() throws {\n defer thrower()\n }",
+      "Functions that can fail can't be deferred",
+    ),
+    (
+      "fn thrower() throws {}\n fn test() throws {\n defer thrower()?\n }",
+      "Functions that can fail can't be deferred",
+    ),
+    (
+      "fn thrower() {}\n fn test() throws {\n defer thrower()?\n }",
+      "Errors can only be handled on statements",
+    ),
+    (
+      "fn parse(x string) int throws {\n return 1\n }\n fn use_int(a int) {}\n fn test(x string) throws {\n use_int(parse(x)?)\n }",
+      "Errors can only be handled on statements",
+    ),
+    (
+      "fn parse(x string) int throws {\n return 1\n }\n fn test(x string) throws {\n if parse(x) catch { return } > 1 {}\n }",
+      "Errors can only be handled on statements",
+    ),
+  ];
+  for (code, message) in cases {
+    let res = single_file_compile(String::from(code));
+    let errors = &res.borrow().errors;
+    assert_eq!(errors.len(), 1, "{}\n{:?}", code, errors);
+    assert!(errors[0].to_string().contains(message), "{:?}", errors);
+  }
+}
@@ -0,0 +1,248 @@
+use super::*;
+
+#[test]
+fn test_tuple_result() {
+  parse_str(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return (a, b)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_result_go_style_return() {
+  parse_str(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return a, b
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_outside_results_fail() {
+  // Go only supports multiple values as function results
+  let cases = vec![
+    "fn test(a (int, string)) {}",
+    "struct Foo {\n a (int, string)\n }",
+    "type Foo = (int, string)",
+    "type Foo (int, string)",
+    "fn test() ((int, int), int) {\n return (1, 1), 1\n }",
+    "fn test(a [](int, int)) {\n a\n }",
+    "fn test() {\n let a: (int, string) = (1, \"a\")\n }",
+    "fn test() {\n let a = (1, \"a\")\n }",
+    "fn pair() (int, string) {\n return 1, \"a\"\n }\n fn test() {\n let a = pair()\n }",
+  ];
+  for code in cases {
+    let res = single_file_compile(String::from(code));
+    let errors = &res.borrow().errors;
+    assert_eq!(errors.len(), 1, "{}\n{:?}", code, errors);
+    assert!(
+      errors[0]
+        .to_string()
+        .contains("Tuples can only be returned from functions and destructured into variables"),
+      "{}\n{:?}",
+      code,
+      errors
+    );
+  }
+}
+
+#[test]
+fn test_tuple_typed_value_arity() {
+  // The arity of a value comes from its type so only the tuple itself is reported
+  let cases = vec![
+    "fn test(a (int, string)) (int, string) {\n return a\n }",
+    "fn test(a (int, string)) {\n let (i, s) = a\n i\n s\n }",
+  ];
+  for code in cases {
+    let res = single_file_compile(String::from(code));
+    let errors = &res.borrow().errors;
+    assert_eq!(errors.len(), 1, "{}\n{:?}", code, errors);
+  }
+}
+
+#[test]
+fn test_invalid_tuple_type() {
+  parse_str_fail(
+    r#"
+      fn test(a (int)) {}
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_return_arity_fail() {
+  parse_str_fail(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return (a, b, a)
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_return_function_call() {
+  parse_str(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return a, b
+      }
+      fn test(a int, b int) (int, int) {
+        return divmod(a, b)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_destructuring() {
+  parse_str(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return (a, b)
+      }
+      fn test() {
+        let (q, r) = divmod(7, 2)
+        let (_, b) = (q, r)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_destructuring_arity_fail() {
+  parse_str_fail(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return (a, b)
+      }
+      fn test() {
+        let (q, r, x) = divmod(7, 2)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_destructuring_type() {
+  parse_str(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return (a, b)
+      }
+      fn test() {
+        let (q, r): (int, int) = divmod(7, 2)
+        let (a, b): (u8, string) = (1, "foo")
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_destructuring_type_fail() {
+  parse_str_fail(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return (a, b)
+      }
+      fn test() {
+        let (x, y): (int, string) = divmod(7, 2)
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        let (x, y): (int, int, int) = (1, 2)
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        let (x, y): (int, Foo) = (1, 2)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_destructuring_duplicated_name_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let (a, a) = (1, 2)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_tuple_destructuring_names() {
+  let tokens = parse_str(
+    r#"
+      fn test() {
+        let (foo, bar) = (1, 2)
+      }
+    "#,
+  );
+  match &tokens.functions["test"].body.actions[0].type_ {
+    ActionType::VariableTuple(var) => {
+      if var.names != vec![String::from("foo"), String::from("bar")] {
+        panic!("{:?}", tokens);
+      }
+      match &var.action.type_ {
+        ActionType::Tuple(items) if items.len() == 2 => {}
+        _ => panic!("{:?}", tokens),
+      }
+    }
+    _ => panic!("{:?}", tokens),
+  }
+}
+
+#[test]
+fn test_tuple_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return a, b
+      }
+      fn main() {
+        let (q, r) = divmod(7, 2)
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("func divmod(a int, b int) (int, int) {"));
+  assert!(output.contains("return a, b"));
+  assert!(output.contains("q, r := divmod(7,2)"));
+}
+
+#[test]
+fn test_tuple_js_output() {
+  let output = parse_str_to_lang(
+    r#"
+      fn divmod(a int, b int) (int, int) {
+        return a, b
+      }
+      fn main() {
+        let (q, _) = divmod(7, 2)
+      }
+    "#,
+    Lang::JS,
+  );
+  assert!(output.contains("return [a, b];"));
+  assert!(output.contains("let [q, ] = divmod(7,2);"));
+}
@@ -0,0 +1,468 @@
+use super::*;
+
+#[test]
+fn test_variable_matching_type() {
+  parse_str(
+    r#"
+      fn test() {
+        let a: string = "foo"
+        let b: u8 = 1 + 2
+        let c: f32 = 1
+        let d: bool = a == "bar"
+        let (e, _): (int, string) = (1, "foo")
+        let f: bool = d && b > 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_variable_wrong_type_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a: string = 1 + 2
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a: int = 1.5
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a: (int, string) = (1, 2)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_inferred_variable_type() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = "foo"
+        let b: int = a
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = 1 < 2
+        let b: int = a
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_assignment_wrong_type_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = 1
+        a = "foo"
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_operands_wrong_type_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = "foo" + 1
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        let a = 1 && true
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_condition_must_be_bool() {
+  parse_str(
+    r#"
+      fn test(a int) {
+        if a > 1 {}
+        while a == 2 {}
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(a int) {
+        if a {}
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(a int) {
+        if a > 1 {} else if "foo" {}
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        while 1 {}
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_argument_wrong_type_fail() {
+  parse_str(
+    r#"
+      fn add(a int, b u8) int {
+        return a
+      }
+      fn test() {
+        add(1, 2)
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn add(a int, b int) int {
+        return a + b
+      }
+      fn test() {
+        add(1, "2")
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_return_wrong_type_fail() {
+  parse_str_fail(
+    r#"
+      fn test() int {
+        return "hello"
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() {
+        return 1
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test() int {
+        return
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_catch_error_is_a_string() {
+  parse_str(
+    r#"
+      fn foo() throws {}
+      fn test() {
+        foo() catch err {
+          let message: string = err
+        }
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn foo() throws {}
+      fn test() {
+        foo() catch err {
+          let message: int = err
+        }
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_static_value_fits_newtype() {
+  parse_str(
+    r#"
+      type Id int
+      type Name = string
+      fn test() {
+        let id: Id = 1
+        let name: Name = "foo"
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      type Name = string
+      fn test() {
+        let name: Name = 1
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_type_mismatch_message() {
+  let res = single_file_compile(String::from(
+    r#"
+      fn test() {
+        let a: bool = "foo"
+      }
+    "#,
+  ));
+  let errors = &res.borrow().errors;
+  assert_eq!(errors.len(), 1);
+  assert!(errors[0]
+    .to_string()
+    .contains("Expected bool, found string"));
+}
+
+#[test]
+fn test_type_refs_exist() {
+  parse_str(
+    r#"
+      struct Foo {
+        bar Bar
+        baz []Baz
+      }
+      enum Bar {
+        a
+        b(foo Foo)
+      }
+      type Baz = []Foo
+      fn test(foo Foo) (Foo, Bar) {
+        let bar: Bar = Bar.a
+        return (foo, bar)
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_unknown_type_fail() {
+  let cases = vec![
+    "fn test(a Foo) {}",
+    "fn test() Foo {\n return 1\n }",
+    "fn test() {\n let a: []Foo = 1\n }",
+    "struct Bar {\n foo Foo\n }",
+    "enum Bar {\n foo(a (int, Foo))\n }",
+    "type Bar = struct {\n foo Foo\n }",
+    "const a: Foo = 1",
+  ];
+  for code in cases {
+    parse_str_fail_with_meta(code, code);
+  }
+}
+
+#[test]
+fn test_type_ref_symbol() {
+  let res = parse_str(
+    r#"
+      struct Foo {}
+      fn test(foo Foo) {}
+    "#,
+  );
+  match &res.functions.get("test").unwrap().args[0].type_.type_ {
+    TypeType::TypeRef(type_ref) => assert_eq!(type_ref.symbol, Some(TypeSymbol::Struct)),
+    _ => panic!("Expected a type reference"),
+  }
+}
+
+#[test]
+fn test_imported_type_ref() {
+  parse_files(
+    [
+      (
+        String::from("main.tp"),
+        String::from(
+          r#"
+            import Utils "./utils.tp"
+            fn test(foo Utils.Foo) {}
+          "#,
+        ),
+      ),
+      (String::from("utils.tp"), String::from("struct Foo {}")),
+    ]
+    .iter()
+    .cloned()
+    .collect(),
+  );
+  parse_str_fail(
+    r#"
+      fn test(foo Utils.Foo) {}
+    "#,
+  );
+}
+
+#[test]
+fn test_type_imported_twice_is_the_same_type() {
+  parse_files(
+    [
+      (
+        String::from("main.tp"),
+        String::from(
+          r#"
+            import A "./utils.tp"
+            import B "./utils.tp"
+            fn test(foo A.Foo) B.Foo {
+              return foo
+            }
+            fn main() {
+              test(B.make())
+            }
+          "#,
+        ),
+      ),
+      (
+        String::from("utils.tp"),
+        String::from(
+          r#"
+            struct Foo {}
+            fn make() Foo {
+              return make()
+            }
+          "#,
+        ),
+      ),
+    ]
+    .iter()
+    .cloned()
+    .collect(),
+  );
+  parse_files_fail(
+    [
+      (
+        String::from("main.tp"),
+        String::from(
+          r#"
+            import Utils "./utils.tp"
+            struct Foo {}
+            fn test(foo Foo) Utils.Foo {
+              return foo
+            }
+          "#,
+        ),
+      ),
+      (String::from("utils.tp"), String::from("struct Foo {}")),
+    ]
+    .iter()
+    .cloned()
+    .collect(),
+    "Expected Utils.Foo, found Foo",
+  );
+}
+
+#[test]
+fn test_inline_struct_types() {
+  parse_str(
+    r#"
+      fn foo(a struct { x int }) {}
+      fn bar(b struct { x int }) {
+        foo(b)
+      }
+    "#,
+  );
+  let cases = [
+    r#"
+      fn foo(a struct { x int }) {}
+      fn bar(b struct { y int }) {
+        foo(b)
+      }
+    "#,
+    r#"
+      fn foo(a struct { x int }) {}
+      fn bar(b struct { x string }) {
+        foo(b)
+      }
+    "#,
+    r#"
+      fn foo(a enum { x }) {}
+      fn bar(b enum { y }) {
+        foo(b)
+      }
+    "#,
+    r#"
+      struct Foo { x int }
+      fn foo(a Foo) {}
+      fn bar(b struct { x int }) {
+        foo(b)
+      }
+    "#,
+  ];
+  for code in cases.iter() {
+    parse_str_fail_with_meta(*code, *code);
+  }
+}
+
+#[test]
+fn test_unknown_type_message() {
+  let res = single_file_compile(String::from(
+    r#"
+      fn test(a []Foo) {}
+    "#,
+  ));
+  let errors = &res.borrow().errors;
+  assert_eq!(errors.len(), 1);
+  assert!(errors[0].to_string().contains("The type Foo doesn't exist"));
+}
+
+#[test]
+fn test_no_value_fail() {
+  parse_str_fail(
+    r#"
+      fn nothing() {}
+      fn test() {
+        let e: int = nothing()
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn nothing() {}
+      fn test() {
+        let f = nothing()
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn nothing() {}
+      fn foo(a int) {}
+      fn test() {
+        foo(nothing())
+      }
+    "#,
+  );
+}
+
+#[test]
+fn test_variadic_type_mismatch_reported_once() {
+  let res = single_file_compile(String::from(
+    r#"
+      fn log(parts ...string) {}
+      fn main() {
+        log("a", 1)
+      }
+    "#,
+  ));
+  let errors = &res.borrow().errors;
+  assert_eq!(errors.len(), 1, "{:?}", errors);
+}
@@ -0,0 +1,255 @@
+use super::*;
+
+/// Compiles main.tp and returns the messages of the warnings and errors
+fn unused_messages(contents: &str, deny_unused: bool) -> (Vec<String>, Vec<String>) {
+  let mut files = HashMap::new();
+  files.insert(String::from("main.tp"), String::from(contents));
+  files.insert(String::from("utils.tp"), String::from("fn foo() {}"));
+  let mut meta = CompilerMeta::new(files, None);
+  meta.deny_unused = deny_unused;
+  let res = run_compiler(meta);
+  let res = res.borrow();
+  (
+    res.warnings.iter().map(|w| w.to_string()).collect(),
+    res.errors.iter().map(|e| e.to_string()).collect(),
+  )
+}
+
+fn assert_contains(messages: &[String], message: &str) {
+  if !messages.iter().any(|m| m.contains(message)) {
+    panic!(
+      "Expected a message containing {:?}\n{:?}",
+      message, messages
+    );
+  }
+}
+
+#[test]
+fn test_unused_variable() {
+  let (warnings, errors) = unused_messages(
+    r#"
+      fn main() {
+        let a = 1
+        let (b, c) = (1, 2)
+        let d = c
+        d = 2
+      }
+    "#,
+    false,
+  );
+  assert_eq!(errors.len(), 0, "{:?}", errors);
+  assert_contains(&warnings, "The variable a is never used");
+  assert_contains(&warnings, "The variable b is never used");
+  // Assigning a new value is not using the variable
+  assert_contains(&warnings, "The variable d is never used");
+  assert_eq!(warnings.len(), 3, "{:?}", warnings);
+}
+
+#[test]
+fn test_used_variables() {
+  let (warnings, errors) = unused_messages(
+    r#"
+      fn foo() (int, int) throws {
+        return (1, 2)
+      }
+      fn main() {
+        let a = 1
+        if a > 0 {
+          let b = a
+          while b < 10 {}
+        }
+        foo() catch err {
+          let message = err
+          if message == "" {}
+        }
+      }
+    "#,
+    false,
+  );
+  assert_eq!(errors.len(), 0, "{:?}", errors);
+  assert_eq!(warnings.len(), 0, "{:?}", warnings);
+}
+
+#[test]
+fn test_unused_argument() {
+  let (warnings, _) = unused_messages(
+    r#"
+      fn foo(a int, b int) int {
+        return a
+      }
+      fn main() {
+        foo(1, 2)
+      }
+    "#,
+    false,
+  );
+  assert_contains(&warnings, "The argument b is never used");
+  assert_eq!(warnings.len(), 1, "{:?}", warnings);
+}
+
+#[test]
+fn test_unused_import() {
+  let (warnings, _) = unused_messages(
+    r#"
+      import Utils "./utils.tp"
+      fn main() {}
+    "#,
+    false,
+  );
+  assert_contains(&warnings, "The import Utils is never used");
+
+  let (warnings, _) = unused_messages(
+    r#"
+      import Utils "./utils.tp"
+      fn main() {
+        Utils.foo()
+      }
+    "#,
+    false,
+  );
+  assert_eq!(warnings.len(), 0, "{:?}", warnings);
+}
+
+#[test]
+fn test_unused_function() {
+  let (warnings, _) = unused_messages(
+    r#"
+      fn count(n int) int {
+        if n > 0 {
+          return count(n - 1)
+        }
+        return 0
+      }
+      fn bar() {}
+      fn main() {
+        bar()
+      }
+    "#,
+    false,
+  );
+  // Calling itself doesn't count as using a function
+  assert_contains(&warnings, "The function count is never used");
+  assert_eq!(warnings.len(), 1, "{:?}", warnings);
+}
+
+#[test]
+fn test_local_variable_named_like_function_is_not_a_use() {
+  let (warnings, _) = unused_messages(
+    r#"
+      fn count() int {
+        return 1
+      }
+      fn main() {
+        let count = 1
+        if count > 0 {}
+      }
+    "#,
+    false,
+  );
+  assert_contains(&warnings, "The function count is never used");
+  assert_eq!(warnings.len(), 1, "{:?}", warnings);
+}
+
+#[test]
+fn test_underscore_prefix_ignores_unused() {
+  let (warnings, errors) = unused_messages(
+    r#"
+      fn _foo(_a int) {
+        let _b = 1
+        let (_, _c) = (1, 2)
+      }
+      fn main() {}
+    "#,
+    true,
+  );
+  assert_eq!(errors.len(), 0, "{:?}", errors);
+  assert_eq!(warnings.len(), 0, "{:?}", warnings);
+}
+
+#[test]
+fn test_deny_unused() {
+  let (warnings, errors) = unused_messages(
+    r#"
+      import Utils "./utils.tp"
+      fn foo(a int) {
+        let b = 1
+      }
+      fn main() {}
+    "#,
+    true,
+  );
+  assert_eq!(warnings.len(), 0, "{:?}", warnings);
+  assert_contains(&errors, "The import Utils is never used");
+  assert_contains(&errors, "The function foo is never used");
+  assert_contains(&errors, "The argument a is never used");
+  assert_contains(&errors, "The variable b is never used");
+}
+
+#[test]
+fn test_underscore_prefix_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none
+      }
+      fn parse() int throws {
+        return 1
+      }
+      fn test(items []int) {
+        let _a = 1
+        let (_b, _) = (1, 2)
+        let _c = parse() catch _err {
+          return
+        }
+        for _i, item in items {
+          if item > 1 {}
+        }
+      }
+      fn area(shape Shape) {
+        match shape {
+          Shape.circle(_radius) => {}
+          _ => {}
+        }
+      }
+    "#,
+    Lang::Go,
+  );
+  assert!(output.contains("_a := 1\n  _ = _a"));
+  assert!(output.contains("_b, _ := 1, 2\n  _ = _b"));
+  // An error message that isn't used is left out
+  assert!(!output.contains("_err := __err.Error()"));
+  assert!(output.contains("  }\n  _ = _c"));
+  assert!(output.contains("for _i, item := range items {\n    _ = _i"));
+  assert!(output.contains("_radius := __value.radius\n    _ = _radius"));
+}
+
+#[test]
+fn test_unused_variables_go_output() {
+  let output = parse_str_to_lang(
+    r#"
+      enum Shape {
+        circle(radius f64)
+        none
+      }
+      fn test(shape Shape) {
+        let x = 1
+        let y = 2
+        let z = y
+        let w = 1
+        w = 2
+        match shape {
+          Shape.circle(r) => {}
+          _ => {}
+        }
+      }
+    "#,
+    Lang::Go,
+  );
+  // Go doesn't compile variables that are never read, these only give a warning
+  assert!(output.contains("x := 1\n  _ = x"));
+  assert!(output.contains("z := y\n  _ = z"));
+  assert!(output.contains("w := 1\n  _ = w"));
+  assert!(output.contains("r := __value.radius\n    _ = r"));
+  assert!(!output.contains("_ = y"));
+}
@@ -100,3 +100,44 @@ fn test_duplicated_vars_fail_2() {
     "#,
   );
 }
+
+#[test]
+fn test_reserved_names_fail() {
+  parse_str_fail(
+    r#"
+      fn test() {
+        let __err = 0
+      }
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn test(__value int) {}
+    "#,
+  );
+  parse_str_fail(
+    r#"
+      fn __defers() {}
+    "#,
+  );
+}
+
+#[test]
+fn test_assignment_output() {
+  let code = r#"
+    fn main() {
+      let a = 0
+      while a < 10 {
+        a = a + 1
+      }
+    }
+  "#;
+  let output = parse_str_to_lang(code, Lang::Go);
+  assert!(
+    output.contains("for a < 10 {\n    a = a + 1\n  }"),
+    "{}",
+    output
+  );
+  let output = parse_str_to_lang(code, Lang::JS);
+  assert!(output.contains("a = a + 1;\n"), "{}", output);
+}
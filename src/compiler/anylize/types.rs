@@ -0,0 +1,325 @@
+use super::*;
+use std::mem::discriminant;
+use tokenize::{TypeId, TypeRef, TypeSymbol};
+
+/// Aliases pointing to other aliases are followed at most this many times, this prevents endless loops on cyclic aliases
+const MAX_ALIAS_DEPTH: usize = 32;
+
+/// Replaces transparent aliases `type A = B` with the type they point to,
+/// newtypes `type A B` are distinct types so they are kept as is
+pub fn resolve_aliases(type_: &Type, tokens: &AnilizedTokens) -> Type {
+  follow_type_refs(type_, tokens, false)
+}
+
+/// Replaces aliases and newtypes with the type they are build on,
+/// static values like `1` can be used for a newtype `type Id int` so these are checked against this type
+pub fn underlying_type(type_: &Type, tokens: &AnilizedTokens) -> Type {
+  follow_type_refs(type_, tokens, true)
+}
+
+fn follow_type_refs(type_: &Type, tokens: &AnilizedTokens, newtypes: bool) -> Type {
+  let mut res = type_.clone();
+  for _ in 0..MAX_ALIAS_DEPTH {
+    let alias = match &res.type_ {
+      TypeType::TypeRef(type_ref) => match global_type(type_ref, tokens) {
+        Some((type_, alias)) if alias || newtypes => type_,
+        _ => return res,
+      },
+      _ => return res,
+    };
+    res = alias;
+  }
+  res
+}
+
+/// Returns the type a global type points to and true if the global type is an alias,
+/// the types of an imported module are made to refer to its types using the alias of the import
+fn global_type(type_ref: &TypeRef, tokens: &AnilizedTokens) -> Option<(Type, bool)> {
+  match tokens.module_ref(&type_ref.name) {
+    Some((alias, module, name)) => {
+      let global_type = module.types.get(name)?;
+      Some((qualify_type(&global_type.type_, alias), global_type.alias))
+    }
+    None => {
+      let global_type = tokens.types.get(&type_ref.name)?;
+      Some((global_type.type_.clone(), global_type.alias))
+    }
+  }
+}
+
+/// Returns true if a value of type found can be used where expected is expected,
+/// unknown type references are already reported so they are seen as equal to everything
+pub fn same_type(expected: &Type, found: &Type, tokens: &AnilizedTokens) -> bool {
+  let expected = resolve_aliases(expected, tokens);
+  let found = resolve_aliases(found, tokens);
+
+  match (&expected.type_, &found.type_) {
+    (TypeType::TypeRef(type_ref), _) | (_, TypeType::TypeRef(type_ref))
+      if type_ref.id.is_none() =>
+    {
+      true
+    }
+    (TypeType::TypeRef(expected), TypeType::TypeRef(found)) => expected.id == found.id,
+    // Inline structs and enums don't have a name so they are the same if they have the same fields
+    (TypeType::Struct(expected), TypeType::Struct(found)) => {
+      same_fields(&expected.fields, &found.fields, tokens)
+    }
+    (TypeType::Enum(expected), TypeType::Enum(found)) => {
+      expected.fields.len() == found.fields.len()
+        && expected
+          .fields
+          .iter()
+          .zip(found.fields.iter())
+          .all(|(expected, found)| {
+            expected.name == found.name && same_fields(&expected.payload, &found.payload, tokens)
+          })
+    }
+    (TypeType::Array(expected), TypeType::Array(found)) => same_type(expected, found, tokens),
+    (TypeType::Tuple(expected), TypeType::Tuple(found)) => {
+      expected.len() == found.len()
+        && expected
+          .iter()
+          .zip(found.iter())
+          .all(|(expected, found)| same_type(expected, found, tokens))
+    }
+    (expected, found) => discriminant(expected) == discriminant(found),
+  }
+}
+
+/// Returns true if both lists have fields with the same names and types in the same order
+fn same_fields(expected: &[StructField], found: &[StructField], tokens: &AnilizedTokens) -> bool {
+  expected.len() == found.len()
+    && expected.iter().zip(found.iter()).all(|(expected, found)| {
+      expected.name == found.name && same_type(&expected.type_, &found.type_, tokens)
+    })
+}
+
+/// Returns what the types inside this file can refer to by their name and the id of the type they refer to,
+/// the types of imported modules are referred to using the alias of the import `utils.Foo`
+pub fn type_symbols(tokens: &AnilizedTokens) -> HashMap<String, (TypeSymbol, TypeId)> {
+  let mut res: HashMap<String, (TypeSymbol, TypeId)> = HashMap::new();
+  for name in tokens.structs.keys() {
+    res.insert(name.clone(), (TypeSymbol::Struct, tokens.type_id(name)));
+  }
+  for name in tokens.enums.keys() {
+    res.insert(name.clone(), (TypeSymbol::Enum, tokens.type_id(name)));
+  }
+  for name in tokens.types.keys() {
+    res.insert(name.clone(), (TypeSymbol::Type, tokens.type_id(name)));
+  }
+  for (alias, module) in &tokens.modules {
+    let names = module
+      .structs
+      .keys()
+      .chain(module.enums.keys())
+      .chain(module.types.keys());
+    for name in names {
+      res.insert(
+        format!("{}.{}", alias, name),
+        (TypeSymbol::Import(alias.clone()), module.type_id(name)),
+      );
+    }
+  }
+  res
+}
+
+/// Makes a type of an imported module refer to its types using the alias of the import, `Foo` becomes `utils.Foo`
+pub fn qualify_type(type_: &Type, alias: &str) -> Type {
+  let qualified = match &type_.type_ {
+    TypeType::TypeRef(TypeRef {
+      name,
+      symbol: Some(TypeSymbol::Struct),
+      id,
+    })
+    | TypeType::TypeRef(TypeRef {
+      name,
+      symbol: Some(TypeSymbol::Enum),
+      id,
+    })
+    | TypeType::TypeRef(TypeRef {
+      name,
+      symbol: Some(TypeSymbol::Type),
+      id,
+    }) => TypeType::TypeRef(TypeRef {
+      name: format!("{}.{}", alias, name),
+      symbol: Some(TypeSymbol::Import(alias.to_string())),
+      id: id.clone(),
+    }),
+    TypeType::Array(type_) => TypeType::Array(Box::new(qualify_type(type_, alias))),
+    TypeType::Tuple(types) => TypeType::Tuple(
+      types
+        .iter()
+        .map(|type_| qualify_type(type_, alias))
+        .collect(),
+    ),
+    type_ => type_.clone(),
+  };
+  Type {
+    location: type_.location.clone(),
+    type_: qualified,
+  }
+}
+
+/// Fills in what the type references inside of type_ point to,
+/// the references that don't point to anything are added to unknown
+pub fn resolve_type_refs(
+  type_: &mut Type,
+  symbols: &HashMap<String, (TypeSymbol, TypeId)>,
+  unknown: &mut Vec<Type>,
+) {
+  match &mut type_.type_ {
+    TypeType::TypeRef(type_ref) => match symbols.get(&type_ref.name) {
+      Some((symbol, id)) => {
+        type_ref.symbol = Some(symbol.clone());
+        type_ref.id = Some(id.clone());
+      }
+      None => unknown.push(type_.clone()),
+    },
+    TypeType::Array(type_) => resolve_type_refs(type_, symbols, unknown),
+    TypeType::Tuple(types) => {
+      for type_ in types {
+        resolve_type_refs(type_, symbols, unknown);
+      }
+    }
+    TypeType::Struct(struct_) => {
+      for field in &mut struct_.fields {
+        resolve_type_refs(&mut field.type_, symbols, unknown);
+      }
+    }
+    TypeType::Enum(enum_) => {
+      for field in &mut enum_.fields {
+        for data in &mut field.payload {
+          resolve_type_refs(&mut data.type_, symbols, unknown);
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Returns the chains of types that contain themselves like `A -> B -> A`, every cycle is returned once.
+/// Structs and newtypes can only contain themselves through an array as go needs to know their size,
+/// aliases can't refer to themselves at all as go doesn't allow recursive aliases
+pub fn recursive_types(tokens: &AnilizedTokens) -> Vec<Vec<String>> {
+  let mut edges: HashMap<&str, Vec<String>> = HashMap::new();
+  for (name, struct_) in &tokens.structs {
+    let mut refs = vec![];
+    for field in &struct_.fields {
+      value_type_refs(&field.type_, &mut refs);
+    }
+    // Every type is only followed once so a cycle is only found once
+    refs.sort();
+    refs.dedup();
+    edges.insert(name, refs);
+  }
+  for (name, global_type) in &tokens.types {
+    let mut refs = vec![];
+    value_type_refs(&global_type.type_, &mut refs);
+    if global_type.alias {
+      alias_type_refs(&global_type.type_, tokens, &mut refs);
+    }
+    // An alias can refer to the same type as value and as alias
+    refs.sort();
+    refs.dedup();
+    edges.insert(name, refs);
+  }
+
+  // The names are sorted so the cycles are always reported in the same order
+  let mut names: Vec<&str> = edges.keys().copied().collect();
+  names.sort();
+  let mut done: HashSet<&str> = HashSet::new();
+  let mut cycles: Vec<Vec<String>> = vec![];
+  for name in names {
+    find_cycles(name, &edges, &mut vec![], &mut done, &mut cycles);
+  }
+  cycles
+}
+
+fn find_cycles<'a>(
+  name: &'a str,
+  edges: &'a HashMap<&str, Vec<String>>,
+  path: &mut Vec<&'a str>,
+  done: &mut HashSet<&'a str>,
+  cycles: &mut Vec<Vec<String>>,
+) {
+  if let Some(start) = path.iter().position(|item| *item == name) {
+    let mut cycle: Vec<String> = path[start..].iter().map(|item| item.to_string()).collect();
+    cycle.push(name.to_string());
+    cycles.push(cycle);
+    return;
+  }
+  if done.contains(name) {
+    return;
+  }
+
+  path.push(name);
+  if let Some(refs) = edges.get(name) {
+    for next in refs {
+      if let Some((next, _)) = edges.get_key_value(next.as_str()) {
+        find_cycles(next, edges, path, done, cycles);
+      }
+    }
+  }
+  path.pop();
+  done.insert(name);
+}
+
+/// Adds the names of the structs and types that are stored inside a value of this type,
+/// arrays and enums with data are stored behind a pointer so the types inside of them are left out
+fn value_type_refs(type_: &Type, refs: &mut Vec<String>) {
+  match &type_.type_ {
+    TypeType::TypeRef(TypeRef {
+      name,
+      symbol: Some(TypeSymbol::Struct),
+      ..
+    })
+    | TypeType::TypeRef(TypeRef {
+      name,
+      symbol: Some(TypeSymbol::Type),
+      ..
+    }) => refs.push(name.clone()),
+    TypeType::Tuple(types) => {
+      for type_ in types {
+        value_type_refs(type_, refs);
+      }
+    }
+    TypeType::Struct(struct_) => {
+      for field in &struct_.fields {
+        value_type_refs(&field.type_, refs);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// Adds the names of the aliases an alias refers to anywhere in its type, including inside arrays
+fn alias_type_refs(type_: &Type, tokens: &AnilizedTokens, refs: &mut Vec<String>) {
+  match &type_.type_ {
+    TypeType::TypeRef(TypeRef {
+      name,
+      symbol: Some(TypeSymbol::Type),
+      ..
+    }) if tokens.types.get(name).is_some_and(|type_| type_.alias) => {
+      refs.push(name.clone());
+    }
+    TypeType::Array(type_) => alias_type_refs(type_, tokens, refs),
+    TypeType::Tuple(types) => {
+      for type_ in types {
+        alias_type_refs(type_, tokens, refs);
+      }
+    }
+    TypeType::Struct(struct_) => {
+      for field in &struct_.fields {
+        alias_type_refs(&field.type_, tokens, refs);
+      }
+    }
+    TypeType::Enum(enum_) => {
+      for field in &enum_.fields {
+        for data in &field.payload {
+          alias_type_refs(&data.type_, tokens, refs);
+        }
+      }
+    }
+    _ => {}
+  }
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+/// Returns the names of all global functions, variables, enum fields and types referenced in the file,
+/// calls of a function to itself are left out so a recursive function doesn't count as used.
+/// This needs the symbol tables of the functions as a name that refers to a local variable isn't a global reference
+pub fn referenced_names(data: &AnilizedTokens) -> HashSet<String> {
+  let mut names: HashSet<String> = HashSet::new();
+  let no_locals: HashSet<(usize, usize)> = HashSet::new();
+
+  for (function_name, function) in &data.functions {
+    for arg in &function.args {
+      type_names(&arg.type_, &mut names);
+      if let Some(default) = &arg.default {
+        default.walk(&mut |action| action_names(action, None, &no_locals, &mut names));
+      }
+    }
+    if let Some(res) = &function.res {
+      type_names(res, &mut names);
+    }
+    let locals = local_references(data.symbols.get(function_name));
+    function
+      .body
+      .walk(&mut |action| action_names(action, Some(function_name), &locals, &mut names));
+  }
+  for var in data.vars.values() {
+    if let Some(data_type) = &var.data_type {
+      type_names(data_type, &mut names);
+    }
+    var
+      .action
+      .walk(&mut |action| action_names(action, None, &no_locals, &mut names));
+  }
+  for struct_ in data.structs.values() {
+    for field in &struct_.fields {
+      type_names(&field.type_, &mut names);
+    }
+  }
+  for enum_ in data.enums.values() {
+    for field in &enum_.fields {
+      for data in &field.payload {
+        type_names(&data.type_, &mut names);
+      }
+    }
+  }
+  for global_type in data.types.values() {
+    type_names(&global_type.type_, &mut names);
+  }
+
+  names
+}
+
+/// Returns the spans of the places where the variables and arguments of a function are read
+fn local_references(table: Option<&SymbolTable>) -> HashSet<(usize, usize)> {
+  table
+    .map(|table| {
+      table
+        .symbols
+        .iter()
+        .flat_map(|symbol| &symbol.references)
+        .map(|span| (span.start, span.end))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn action_names(
+  action: &Action,
+  inside_function: Option<&String>,
+  locals: &HashSet<(usize, usize)>,
+  names: &mut HashSet<String>,
+) {
+  match &action.type_ {
+    ActionType::FunctionCall(call) if Some(&call.name) != inside_function => {
+      names.insert(call.name.clone());
+    }
+    ActionType::VarRef(name) if !locals.contains(&(action.location.start, action.location.end)) => {
+      names.insert(name.clone());
+    }
+    ActionType::Variable(Variable {
+      data_type: Some(data_type),
+      ..
+    }) => type_names(data_type, names),
+    ActionType::VariableTuple(VariableTuple {
+      data_type: Some(data_type),
+      ..
+    }) => type_names(data_type, names),
+    ActionType::Match(match_) => {
+      for arm in &match_.arms {
+        if let MatchPattern::Destructure(field, _) = &arm.pattern {
+          names.insert(field.clone());
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+fn type_names(type_: &Type, names: &mut HashSet<String>) {
+  match &type_.type_ {
+    TypeType::TypeRef(type_ref) => {
+      names.insert(type_ref.name.clone());
+    }
+    TypeType::Array(type_) => type_names(type_, names),
+    TypeType::Tuple(types) => {
+      for type_ in types {
+        type_names(type_, names);
+      }
+    }
+    TypeType::Struct(struct_) => {
+      for field in &struct_.fields {
+        type_names(&field.type_, names);
+      }
+    }
+    TypeType::Enum(enum_) => {
+      for field in &enum_.fields {
+        for data in &field.payload {
+          type_names(&data.type_, names);
+        }
+      }
+    }
+    _ => {}
+  }
+}
@@ -1,6 +1,7 @@
-use super::CodeLocation;
+use super::tokenize::Attribute;
+use super::Span;
 
-static UPPER_CASE: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+static UPPER_CASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
 /// Checks if `name` is snake case
 ///
@@ -20,7 +21,12 @@ pub fn is_snake_case(name: &str) -> bool {
       return false;
     }
   }
-  return true;
+  true
+}
+
+/// Checks if `name` is reserved for the names the compiler generates, these start with `__`
+pub fn is_reserved_name(name: &str) -> bool {
+  name.starts_with("__")
 }
 
 /// Checks if `name` is pascal case
@@ -53,6 +59,10 @@ pub trait GetName {
   fn name(&self) -> Option<String>;
 }
 
+pub trait GetAttributes {
+  fn attributes(&self) -> &[Attribute];
+}
+
 pub trait GetLocation {
-  fn location(&self) -> CodeLocation;
+  fn location(&self) -> Span;
 }
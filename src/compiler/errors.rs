@@ -6,57 +6,76 @@ use std::error::Error;
 #[derive(Clone)]
 pub struct LocationError {
   pub error_type: StateError,
+  /// The code around the error, boxed so results with this error stay small
+  pub lines: Option<Box<ErrorLines>>,
+  pub file_name: String,
+}
+
+#[derive(Clone)]
+pub struct ErrorLines {
   pub prev_line: Option<String>,
-  pub line: Option<(String, usize, u16)>,
+  /// The line with the error: (contents, line number, column, amound of chars to underline)
+  pub line: (String, usize, usize, usize),
   pub next_line: Option<String>,
-  pub file_name: String,
 }
 
 impl LocationError {
   pub fn only_file_name(error: impl Into<StateError>, file_name: String) -> Self {
     Self {
       error_type: error.into(),
-      prev_line: None,
-      line: None,
-      next_line: None,
+      lines: None,
       file_name,
     }
   }
   fn err(&self) -> String {
-    let mut output: Vec<String> = vec![];
-
-    let err = self.clone();
-
-    if let Some((line, x, y)) = err.line {
-      output.push(format!("Error in file: {}", self.file_name));
-
-      if let Some(line) = err.prev_line.clone() {
-        output.push(format!("{}: {}", x - 1, line.replace("\t", "  ")));
-      }
-
-      let mut spacing = String::from("");
-      for _ in 0..x + x.to_string().len() + y as usize + 1 {
-        spacing += " ";
-      }
+    let lines = match &self.lines {
+      Some(lines) => lines,
+      None => return format!("Error in file: {}\n{}", self.file_name, self.error_type),
+    };
+    let (line, line_number, column, len) = &lines.line;
+
+    // All line numbers are padded to the same width so the code lines up
+    let width = (line_number + 1).to_string().len();
+    let mut output: Vec<String> = vec![format!(
+      "Error in file: {}:{}:{}",
+      self.file_name, line_number, column
+    )];
+
+    if let Some(prev_line) = &lines.prev_line {
       output.push(format!(
-        "{}: {}\n{}^-- {}",
-        x,
-        line.replace("\t", "  "),
-        spacing,
-        err.error_type,
+        "{:>width$}: {}",
+        line_number - 1,
+        prev_line.replace("\t", "  "),
+        width = width
       ));
-
-      if let Some(line) = err.next_line.clone() {
-        output.push(format!("{}: {}", x + 1, line.replace("\t", "  ")));
-      }
-    } else {
+    }
+    output.push(format!(
+      "{:>width$}: {}",
+      line_number,
+      line.replace("\t", "  "),
+      width = width
+    ));
+
+    // Tabs are shown as 2 spaces so they count as 2 chars before the underline
+    let before = line.get(..column - 1).unwrap_or(line.as_str());
+    let spacing = " ".repeat(width + 2 + before.chars().count() + before.matches('\t').count());
+    output.push(format!(
+      "{}{} {}",
+      spacing,
+      "^".repeat(*len),
+      self.error_type
+    ));
+
+    if let Some(next_line) = &lines.next_line {
       output.push(format!(
-        "Error in file: {}\n{}",
-        self.file_name, err.error_type
+        "{:>width$}: {}",
+        line_number + 1,
+        next_line.replace("\t", "  "),
+        width = width
       ));
     }
 
-    format!("{}", output.join("\n"))
+    output.join("\n")
   }
 }
 
@@ -81,9 +100,9 @@ pub enum StateError {
   // Target(TargetError),
 }
 
-impl Into<StateError> for AnylizeErrAndWarns {
-  fn into(self) -> StateError {
-    StateError::AnylizeErrorOrWarning(self)
+impl From<AnylizeErrAndWarns> for StateError {
+  fn from(val: AnylizeErrAndWarns) -> Self {
+    StateError::AnylizeErrorOrWarning(val)
   }
 }
 
@@ -100,17 +119,16 @@ impl Display for StateError {
 #[derive(Clone)]
 pub enum TokenizeError {
   UnableToOpenFile(String),
-  IncompletedArgument,
   UnexpectedEOF,
   UnexpectedChar(char),
+  UnexpectedToken(String),
   UnexpectedResult,
-  InvalidNameChar,
   Custom(&'static str),
 }
 
-impl Into<StateError> for TokenizeError {
-  fn into(self) -> StateError {
-    StateError::Tokenize(self)
+impl From<TokenizeError> for StateError {
+  fn from(val: TokenizeError) -> Self {
+    StateError::Tokenize(val)
   }
 }
 
@@ -118,7 +136,6 @@ impl Display for TokenizeError {
   fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
     match self {
       Self::UnableToOpenFile(file_name) => write!(f, "Unable to open file {}", file_name),
-      Self::IncompletedArgument => write!(f, "Incompletted argument"),
       Self::UnexpectedEOF => write!(f, "Unexpected EOF"),
       Self::UnexpectedChar(c) => match c {
         '\n' | '\t' => write!(
@@ -128,8 +145,8 @@ impl Display for TokenizeError {
         ),
         _ => write!(f, "Unexpected char: {}", c),
       },
+      Self::UnexpectedToken(token) => write!(f, "Unexpected token: {}", token),
       Self::UnexpectedResult => write!(f, "Unexpected result"),
-      Self::InvalidNameChar => write!(f, "Invalid name char"),
       Self::Custom(error) => write!(f, "{}", error),
     }
   }
@@ -1,149 +1,107 @@
-use super::errors::{LocationError, StateError};
+use super::errors::{ErrorLines, LocationError, StateError};
+use std::fmt;
 use std::rc::Rc;
 
-/// This is used in meany places to safe the location of code
+/// The part of a file a token or parsed item was made from, this is used in meany places to report errors
 ///
-/// Why the index and y?
-/// There are a few things to consider when storing code:
-/// 1. How memory efficent is it because of all tokens you will need a locaton to later report errors?
-/// 2. How much memory / cpu power does it cost to know these locations?
-/// 3. How easially can we get from this to debug information with code?
-///
-/// 1. This solution is only 64 + 16 bits per location so pretty memory efficient
-///
-/// 2. Pretty low the compiler only has to track y and index this way so every char iteration only
-/// cost 1 variable to update unless it's a newline in that case 2. This is pretty memory and cpu efficient
-///
-/// 3. Because we already know the line where location was created (y) we don't have to count every \n till the index.
-/// Becuase of that we only have to seek 1 line backwards and forwards to get a view of the 3 lines an location was made on
-#[derive(Debug, Clone)]
-pub struct CodeLocation {
-  pub index: usize,
-  pub y: u16,
+/// Only the byte offsets are stored, the line and column are looked up in the line table of the file when needed.
+/// This keeps locations small and cheap to create as every token and item has one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+  /// The id of the file this span is in, see File.id
+  pub file_id: usize,
+  /// The byte offset of the first char
+  pub start: usize,
+  /// The byte offset after the last char
+  pub end: usize,
 }
 
-impl CodeLocation {
-  pub fn new(index: usize, y: u16) -> Self {
-    Self { index, y }
+impl Span {
+  pub fn new(file_id: usize, start: usize, end: usize) -> Self {
+    Self {
+      file_id,
+      start,
+      end,
+    }
+  }
+  /// Returns a span covering self and other `foo + bar`
+  pub fn to(&self, other: &Span) -> Self {
+    Self {
+      file_id: self.file_id,
+      start: self.start.min(other.start),
+      end: self.end.max(other.end),
+    }
   }
 }
 
 #[derive(Clone, Debug)]
 pub struct File {
+  /// A number that identifies this file within a compilation
+  pub id: usize,
   pub bytes: Rc<Vec<u8>>,
   pub name: String,
+  /// The byte offset of the start of every line
+  line_starts: Rc<Vec<usize>>,
 }
 
 impl File {
-  pub fn new(bytes: Vec<u8>, file_name: impl Into<String>) -> Self {
-    let mut mut_bytes = bytes;
-
-    let mut chars_to_remove: Vec<usize> = vec![];
-
+  pub fn new(id: usize, bytes: Vec<u8>, file_name: impl Into<String>) -> Self {
     // Remove all the '\r' from the code because we currently do not support it
-    for (i, c) in mut_bytes.iter().enumerate().rev() {
-      if *c as char == '\r' {
-        chars_to_remove.push(i);
+    let mut bytes = bytes;
+    bytes.retain(|c| *c != b'\r');
+
+    let mut line_starts = vec![0];
+    for (i, c) in bytes.iter().enumerate() {
+      if *c == b'\n' {
+        line_starts.push(i + 1);
       }
     }
-    for i in chars_to_remove {
-      mut_bytes.remove(i);
-    }
 
     Self {
-      bytes: Rc::new(mut_bytes),
+      id,
+      bytes: Rc::new(bytes),
       name: file_name.into(),
+      line_starts: Rc::new(line_starts),
     }
   }
 
-  pub fn must_error(&self, error: impl Into<StateError>, location: CodeLocation) -> LocationError {
-    let mut index = location.index;
-    let mut scan_prev_line = false;
-    let mut prev_line_bytes: Vec<u8> = vec![];
-    let mut line_bytes: Vec<u8> = vec![];
-    let mut next_line_bytes: Vec<u8> = vec![];
-    let mut x = 0;
-
-    if index > 0 {
-      loop {
-        index -= 1;
-        if let Some(c) = self.bytes.get(index) {
-          match *c as char {
-            '\n' => {
-              scan_prev_line = true;
-              break;
-            }
-            _ => line_bytes.insert(0, *c),
-          };
-          x += 1;
-        }
-
-        if index == 0 {
-          break;
-        }
-      }
-
-      if scan_prev_line {
-        while let Some(c) = self.bytes.get(index) {
-          if index == 0 {
-            break;
-          }
-          index -= 1;
-          match *c as char {
-            '\n' => {
-              break;
-            }
-            _ => prev_line_bytes.insert(0, *c),
-          }
-        }
-      }
-    }
-
-    index = location.index;
-    while let Some(c) = self.bytes.get(index) {
-      index += 1;
-      match *c as char {
-        '\n' => break,
-        _ => line_bytes.push(*c),
-      }
-    }
-
-    while let Some(c) = self.bytes.get(index) {
-      index += 1;
-      match *c as char {
-        '\n' => break,
-        _ => next_line_bytes.push(*c),
-      }
-    }
+  /// Returns the line and column of a byte offset, both start counting at 1
+  pub fn line_col(&self, index: usize) -> (usize, usize) {
+    let line = self.line_starts.partition_point(|start| *start <= index);
+    (line, index - self.line_starts[line - 1] + 1)
+  }
 
-    let prev_line = if scan_prev_line {
-      Some(String::from_utf8(prev_line_bytes).unwrap())
-    } else {
-      None
+  /// Returns the contents of a line without the newline, line starts counting at 1
+  fn line(&self, line: usize) -> Option<String> {
+    let start = *self.line_starts.get(line.checked_sub(1)?)?;
+    let end = match self.line_starts.get(line) {
+      Some(next_start) => next_start - 1,
+      None => self.bytes.len(),
     };
+    Some(String::from_utf8_lossy(&self.bytes[start..end]).into_owned())
+  }
 
-    let line = Some((String::from_utf8(line_bytes).unwrap(), x, location.y));
+  pub fn must_error(&self, error: impl Into<StateError>, location: Span) -> LocationError {
+    let start = location.start.min(self.bytes.len());
+    let (line_number, column) = self.line_col(start);
+    let text = self.line(line_number).unwrap_or_default();
 
-    let next_line = if next_line_bytes.len() > 0 {
-      Some(String::from_utf8(next_line_bytes).unwrap())
-    } else {
-      None
-    };
+    // Underline the span, a span that continues on the next lines is underlined till the end of the first line
+    let line_end = start - (column - 1) + text.len();
+    let len = location.end.min(line_end).saturating_sub(start).max(1);
 
     LocationError {
       error_type: error.into(),
-      prev_line,
-      line,
-      next_line,
+      lines: Some(Box::new(ErrorLines {
+        prev_line: self.line(line_number - 1),
+        line: (text, line_number, column, len),
+        next_line: self.line(line_number + 1),
+      })),
       file_name: self.name.to_string(),
     }
   }
 
-  pub fn error<T>(
-    &self,
-    error: impl Into<StateError>,
-    location: CodeLocation,
-  ) -> Result<T, LocationError> {
+  pub fn error<T>(&self, error: impl Into<StateError>, location: Span) -> Result<T, LocationError> {
     Err(self.must_error(error, location))
   }
 }
@@ -154,9 +112,15 @@ pub struct Path {
   parts: Vec<String>,
 }
 
-impl Into<String> for Path {
-  fn into(self) -> String {
-    self.to_string()
+impl From<Path> for String {
+  fn from(val: Path) -> Self {
+    val.to_string()
+  }
+}
+
+impl fmt::Display for Path {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.parts.join("/"))
   }
 }
 
@@ -172,20 +136,17 @@ impl Path {
     res.push(path.into());
     res
   }
-  pub fn to_string(&self) -> String {
-    self.parts.join("/")
-  }
   pub fn pop(&mut self) -> Option<String> {
     self.parts.pop()
   }
   pub fn push_path(&mut self, path: Self) {
-    if self.parts.len() == 0 || path.absolute {
+    if self.parts.is_empty() || path.absolute {
       self.absolute = path.absolute;
       self.parts = path.parts;
       return;
     }
 
-    if path.parts.len() == 0 {
+    if path.parts.is_empty() {
       return;
     }
 
@@ -217,12 +178,12 @@ impl Path {
   }
   pub fn push(&mut self, path: String) {
     let mut new_path = Self::new();
-    for (index, item) in path.split('/').into_iter().enumerate() {
+    for (index, item) in path.split('/').enumerate() {
       if item == "." {
         continue;
       }
 
-      if item == "" {
+      if item.is_empty() {
         if index == 0 {
           new_path.absolute = true;
         }
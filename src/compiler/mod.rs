@@ -4,13 +4,13 @@ pub mod files;
 pub mod target;
 pub mod tokenize;
 
-use anylize::anilize_tokens;
 pub use anylize::AnilizedTokens;
+use anylize::{anilize_tokens, AnylizeErrAndWarns};
 pub use errors::LocationError;
 use errors::TokenizeError;
-pub use files::{CodeLocation, File, Path};
+pub use files::{File, Path, Span};
 use std::cell::RefCell;
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 use std::rc::Rc;
 use target::generate;
 pub use target::Lang;
@@ -21,6 +21,8 @@ use tokenize::Tokenizer;
 pub struct Options {
   pub lang: Option<Lang>,
   pub debug: bool,
+  /// Report unused variables, arguments, imports and functions as errors instead of warnings
+  pub deny_unused: bool,
 }
 
 pub trait CompilerProps {
@@ -32,6 +34,7 @@ pub trait CompilerProps {
     Options {
       lang: None,
       debug: false,
+      deny_unused: false,
     }
   }
   /// When a warning showsup this function will be called
@@ -49,28 +52,29 @@ pub trait CompilerProps {
   fn debug_parsed_output(&mut self, _: String, _: String) {}
 }
 
-pub enum Work {
-  ParseFile(Path),
-}
-
 pub struct Compiler {
-  opened_files: HashMap<String, Rc<Vec<u8>>>,
+  /// The files opened so far by name, the id of a file is the order it was opened in
+  opened_files: HashMap<String, File>,
+  /// The anylized modules by file name, a module is anylized after the modules it imports
+  modules: HashMap<String, Rc<AnilizedTokens>>,
+  /// The modules that are being compiled right now in the order they are imported,
+  /// a module imported by one of these is part of an import cycle
+  compiling: Vec<CompilingModule>,
   options: Options,
   props: Rc<RefCell<dyn CompilerProps>>,
-  work_todo: VecDeque<Work>,
 }
 
-impl Compiler {
-  pub fn add_work(&mut self, work: Work) {
-    self.work_todo.push_back(work);
-  }
+/// A module of which the imports are being compiled
+struct CompilingModule {
+  file: File,
+  /// The location of the import that is being compiled right now
+  import: Option<Span>,
+}
 
+impl Compiler {
   fn open_file(&mut self, file_name: &str) -> Result<File, LocationError> {
-    if let Some(bytes) = self.opened_files.get(file_name) {
-      Ok(File {
-        bytes: Rc::clone(bytes),
-        name: file_name.to_string(),
-      })
+    if let Some(file) = self.opened_files.get(file_name) {
+      Ok(file.clone())
     } else {
       let bytes = match self.props.borrow_mut().open_file(file_name) {
         Err(_) => {
@@ -82,15 +86,15 @@ impl Compiler {
         Ok(v) => v,
       };
 
-      let file = File::new(bytes, file_name);
+      let file = File::new(self.opened_files.len(), bytes, file_name);
       self
         .opened_files
-        .insert(file_name.to_string(), Rc::clone(&file.bytes));
+        .insert(file_name.to_string(), file.clone());
       Ok(file)
     }
   }
 
-  pub fn start<'a>(entry_file_name: &str, props: Rc<RefCell<dyn CompilerProps>>) {
+  pub fn start(entry_file_name: &str, props: Rc<RefCell<dyn CompilerProps>>) {
     let options = {
       let props = props.borrow_mut();
       props.get_options()
@@ -98,78 +102,127 @@ impl Compiler {
 
     let mut c = Self {
       opened_files: HashMap::new(),
-      work_todo: VecDeque::from(vec![Work::ParseFile(Path::from(entry_file_name))]),
+      modules: HashMap::new(),
+      compiling: vec![],
       options,
       props,
     };
 
-    // TODO: remake this, we should probebly have one AnilizedTokens as output
-    let mut first_res: Option<AnilizedTokens> = None;
+    let res = match c.compile_module(Path::from(entry_file_name)) {
+      Some(res) => res,
+      None => return,
+    };
 
-    loop {
-      let todo = match c.work_todo.pop_front() {
-        None => break,
-        Some(data) => data,
+    if let Some(lang) = c.options.lang.clone() {
+      let src = match generate((*res).clone(), lang) {
+        Err(err) => {
+          c.props.borrow_mut().error(err);
+          return;
+        }
+        Ok(v) => v,
       };
 
-      match todo {
-        Work::ParseFile(to_parse_file_name) => {
-          let entry_file = match c.open_file(&to_parse_file_name.to_string()) {
-            Ok(val) => val,
-            Err(err) => {
-              c.props.borrow_mut().error(err);
-              return;
-            }
-          };
-
-          let res = match Tokenizer::tokenize(entry_file) {
-            Err(err) => {
-              c.props.borrow_mut().error(err);
-              return;
-            }
-            Ok(v) => v,
-          };
-
-          let file_name = res.file.name.clone();
-          let (formatted_res, anilize_res) = anilize_tokens(&mut c, res);
-
-          for warning in anilize_res.warnings {
-            c.props.borrow_mut().warning(warning);
-          }
-
-          if anilize_res.errors.len() > 0 {
-            for error in anilize_res.errors {
-              c.props.borrow_mut().error(error);
-            }
-            return;
-          }
-
-          if c.options.debug {
-            c.props
-              .borrow_mut()
-              .debug_formatted_tokens(file_name.clone(), formatted_res.clone());
-          }
-
-          first_res = Some(formatted_res);
-        }
+      if c.options.debug {
+        c.props
+          .borrow_mut()
+          .debug_parsed_output(entry_file_name.to_string(), src)
       }
     }
+  }
 
-    if let Some(res) = first_res {
-      if let Some(lang) = c.options.lang {
-        let src = match generate(res, lang) {
-          Err(err) => {
-            c.props.borrow_mut().error(err);
-            return;
-          }
-          Ok(v) => v,
-        };
-
-        if c.options.debug {
-          c.props
-            .borrow_mut()
-            .debug_parsed_output(String::from("main.tp"), src) // TODO: fix the main.tp here
-        }
+  /// Parses and anylizes a file after the files it imports,
+  /// returns None if the file or one of its imports contains errors
+  fn compile_module(&mut self, path: Path) -> Option<Rc<AnilizedTokens>> {
+    let file_name = path.to_string();
+    if let Some(module) = self.modules.get(&file_name) {
+      return Some(Rc::clone(module));
+    }
+    if let Some(start) = self
+      .compiling
+      .iter()
+      .position(|module| module.file.name == file_name)
+    {
+      self.import_cycle(start, &file_name);
+      return None;
+    }
+
+    let file = match self.open_file(&file_name) {
+      Ok(val) => val,
+      Err(err) => {
+        self.props.borrow_mut().error(err);
+        return None;
+      }
+    };
+
+    let res = match Tokenizer::tokenize(file) {
+      Err(err) => {
+        self.props.borrow_mut().error(err);
+        return None;
+      }
+      Ok(v) => v,
+    };
+
+    // The imported modules are needed to check the references to their items
+    let index = self.compiling.len();
+    self.compiling.push(CompilingModule {
+      file: res.file.clone(),
+      import: None,
+    });
+    let mut modules: HashMap<String, Rc<AnilizedTokens>> = HashMap::new();
+    for import in &res.imports {
+      let mut import_path = path.clone();
+      import_path.pop(); // Remove the filename from the path
+      import_path.push(import.path.content.clone());
+
+      self.compiling[index].import = Some(import.location.clone());
+      let module = self.compile_module(import_path)?;
+      modules.insert(import.name.clone(), module);
+    }
+    self.compiling.pop();
+
+    let (formatted_res, anilize_res) = anilize_tokens(self, res, modules);
+
+    for warning in anilize_res.warnings {
+      self.props.borrow_mut().warning(warning);
+    }
+
+    if !anilize_res.errors.is_empty() {
+      for error in anilize_res.errors {
+        self.props.borrow_mut().error(error);
+      }
+      return None;
+    }
+
+    if self.options.debug {
+      self
+        .props
+        .borrow_mut()
+        .debug_formatted_tokens(file_name.clone(), formatted_res.clone());
+    }
+
+    let module = Rc::new(formatted_res);
+    self.modules.insert(file_name, Rc::clone(&module));
+    Some(module)
+  }
+
+  /// Reports an import cycle on every import that is part of it,
+  /// the cycle starts at the compiling module with index start and ends with file_name
+  fn import_cycle(&mut self, start: usize, file_name: &str) {
+    let mut chain: Vec<&str> = self
+      .compiling
+      .iter()
+      .map(|module| module.file.name.as_str())
+      .collect();
+    chain.push(file_name);
+    let chain = chain.join(" -> ");
+
+    for module in &self.compiling[start..] {
+      if let Some(import) = &module.import {
+        let error = module.file.must_error(
+          AnylizeErrAndWarns::ImportCycle(chain.clone()),
+          import.clone(),
+        );
+        self.props.borrow_mut().error(error);
       }
     }
   }
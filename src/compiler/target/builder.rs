@@ -0,0 +1,299 @@
+use core::fmt;
+
+pub trait BuildItems {
+  fn get_items(&mut self) -> &mut Vec<Item>;
+
+  fn add_enter_after(&self) -> bool {
+    false
+  }
+
+  fn function(&mut self, before_contents: Inline, contents: Block) {
+    let block = Item::Block(before_contents.items, contents.items);
+    self.get_items().push(block);
+    self.if_enter();
+  }
+
+  /// Same as function but with custom characters around the block like `const (` and `)`
+  fn block_with(
+    &mut self,
+    before_contents: Inline,
+    contents: Block,
+    open: impl Into<String>,
+    close: impl Into<String>,
+  ) {
+    let block = Item::DelimitedBlock(
+      before_contents.items,
+      contents.items,
+      open.into(),
+      close.into(),
+    );
+    self.get_items().push(block);
+    self.if_enter();
+  }
+
+  /// Adds multiple blocks where every next block starts on the closing line of the previous block
+  /// This is used for things like `if foo {} else {}`
+  fn chain(&mut self, blocks: Vec<(Inline, Block)>) {
+    let mut items: Vec<Item> = vec![];
+    for (before_contents, contents) in blocks {
+      items.push(Item::Block(before_contents.items, contents.items));
+    }
+    self.get_items().push(Item::Chain(items));
+    self.if_enter();
+  }
+
+  fn inline(&mut self, contents: Inline) {
+    let inline = Item::Inline(contents.items);
+    self.get_items().push(inline);
+    self.if_enter();
+  }
+
+  fn code(&mut self, code: impl Into<String>) {
+    self.get_items().push(Item::Code(code.into()));
+    self.if_enter();
+  }
+
+  fn if_enter(&mut self) {
+    if self.add_enter_after() {
+      self.enter();
+    }
+  }
+  fn enter(&mut self) {
+    self.get_items().push(Item::Enter);
+  }
+
+  /// Adds a comment above the next item, every line of the message becomes a comment line
+  fn comment(&mut self, message: &str) {
+    for line in message.lines() {
+      self.get_items().push(Item::Comment(line.into()));
+    }
+  }
+
+  /// Adds a line of code above the next item without an enter after it
+  fn line(&mut self, code: impl Into<String>) {
+    self.get_items().push(Item::Code(code.into()));
+  }
+}
+
+pub struct Block {
+  items: Vec<Item>,
+}
+
+pub struct Inline {
+  items: Vec<Item>,
+}
+
+impl Block {
+  pub fn new() -> Self {
+    Self { items: vec![] }
+  }
+}
+
+impl Inline {
+  pub fn new() -> Self {
+    Self { items: vec![] }
+  }
+  pub fn from_str(input: impl Into<String>) -> Self {
+    Self {
+      items: vec![Item::Code(input.into())],
+    }
+  }
+}
+
+impl BuildItems for Block {
+  fn get_items(&mut self) -> &mut Vec<Item> {
+    &mut self.items
+  }
+}
+
+impl BuildItems for Inline {
+  fn get_items(&mut self) -> &mut Vec<Item> {
+    &mut self.items
+  }
+}
+
+#[derive(Clone)]
+pub enum Item {
+  Code(String),
+  Enter,
+
+  /// This can be used to have multiple Items one 1 line
+  Inline(Vec<Item>),
+
+  /// The first argument is the prefix of the block,
+  /// after that the Vec with items will be wrapped in the data inside LangBuilder::block
+  Block(Vec<Item>, Vec<Item>),
+
+  /// Equal to Block but the last 2 arguments are used instaid of LangBuilder::block
+  DelimitedBlock(Vec<Item>, Vec<Item>, String, String),
+
+  /// Blocks of which the next block starts on the last line of the previous block
+  Chain(Vec<Item>),
+
+  /// A line of comment, the LangBuilder::comments are placed in front of it
+  Comment(String),
+}
+
+impl Item {
+  fn get_lines(self, builder: &LangBuilder) -> Vec<Option<String>> {
+    match self {
+      Self::Code(data) => vec![Some(data)],
+      Self::Enter => vec![None],
+      Self::Comment(data) => vec![Some(comment_line(&data, builder))],
+      Self::Inline(items) => {
+        let mut out: Vec<String> = vec![];
+        for item in items {
+          out.push(item.get_line(builder));
+        }
+        vec![Some(out.join(""))]
+      }
+      Self::Block(prefix, items) => {
+        block_lines(prefix, items, &builder.block.0, &builder.block.1, builder)
+      }
+      Self::DelimitedBlock(prefix, items, open, close) => {
+        block_lines(prefix, items, &open, &close, builder)
+      }
+      Self::Chain(items) => {
+        let mut res: Vec<Option<String>> = vec![];
+        for item in items {
+          let mut lines = item.get_lines(builder);
+          let joined = match (res.last(), lines.first()) {
+            (Some(Some(last)), Some(Some(first))) => Some(format!("{} {}", last, first)),
+            _ => None,
+          };
+          if let Some(joined) = joined {
+            res.pop();
+            lines[0] = Some(joined);
+          }
+          res.append(&mut lines);
+        }
+        res
+      }
+    }
+  }
+  fn get_line(self, builder: &LangBuilder) -> String {
+    match self {
+      Self::Code(data) => data,
+      Self::Enter => String::new(),
+      Self::Comment(data) => comment_line(&data, builder),
+      Self::Inline(items) => {
+        let mut out: Vec<String> = vec![];
+        for item in items {
+          out.push(item.get_line(builder));
+        }
+        out.join("")
+      }
+      Self::Block(_, _) | Self::DelimitedBlock(_, _, _, _) | Self::Chain(_) => String::new(), // TODO this is not yet used inside a inline but i would like to also support this
+    }
+  }
+}
+
+fn comment_line(data: &str, builder: &LangBuilder) -> String {
+  // Empty lines should not end with spacing
+  format!("{}{}", builder.comments, data)
+    .trim_end()
+    .to_string()
+}
+
+fn block_lines(
+  prefix: Vec<Item>,
+  items: Vec<Item>,
+  open: &str,
+  close: &str,
+  builder: &LangBuilder,
+) -> Vec<Option<String>> {
+  let mut prefix_items: Vec<String> = vec![];
+  for item in prefix {
+    prefix_items.push(item.get_line(builder));
+  }
+  let prefix_str = prefix_items.join("");
+  let prefix_str = if prefix_str.is_empty() {
+    // A block without prefix should not start with spacing
+    open.trim_start().to_string()
+  } else {
+    prefix_str + open
+  };
+
+  if items.is_empty() {
+    return vec![Some(prefix_str + close)];
+  }
+
+  let mut res = vec![Some(prefix_str)];
+
+  for item in items {
+    for line in item.get_lines(builder) {
+      if let Some(line_data) = line {
+        res.push(Some(format!("{}{}", builder.tabs_or_spaces, line_data)));
+      } else {
+        res.push(None);
+      }
+    }
+  }
+
+  res.push(Some(close.to_string()));
+  res
+}
+
+pub enum TabsOrSpaces {
+  // Unused:
+  // Tabs,
+  Spaces(u8),
+}
+
+impl fmt::Display for TabsOrSpaces {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Spaces(amount) => write!(f, "{:1$}", " ", *amount as usize),
+    }
+  }
+}
+
+pub struct LangBuilder {
+  pub tabs_or_spaces: TabsOrSpaces,
+  pub comments: String,
+  items: Vec<Item>,
+  /// This will be shown before and after a block of code with ofcourse enters between and
+  /// the contents will have tabs or spaces depending on the configuration
+  pub block: (String, String),
+}
+
+impl LangBuilder {
+  pub fn new() -> Self {
+    Self {
+      tabs_or_spaces: TabsOrSpaces::Spaces(2),
+      comments: String::from("// "),
+      block: (" {".into(), "}".into()),
+      items: vec![],
+    }
+  }
+}
+
+impl LangBuilder {
+  /// Adds all items of another builder to the end of this one
+  pub fn append(&mut self, mut other: LangBuilder) {
+    self.items.append(&mut other.items);
+  }
+}
+
+impl fmt::Display for LangBuilder {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut res: Vec<String> = vec![];
+
+    for item in self.items.clone() {
+      for line in item.get_lines(self) {
+        res.push(line.unwrap_or_default());
+      }
+    }
+
+    write!(f, "{}", res.join("\n"))
+  }
+}
+
+impl BuildItems for LangBuilder {
+  fn get_items(&mut self) -> &mut Vec<Item> {
+    &mut self.items
+  }
+  fn add_enter_after(&self) -> bool {
+    true
+  }
+}
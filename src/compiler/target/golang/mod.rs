@@ -0,0 +1,1086 @@
+use super::*;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Returns the go type of the values of an enum without data
+fn enum_value_type(enum_: &Enum, tokens: &AnilizedTokens) -> &'static str {
+  let enum_name = enum_.name.clone().unwrap_or_default();
+  for field in &enum_.fields {
+    if let Some(Action {
+      type_: ActionType::StaticString(_),
+      ..
+    }) = field.value
+    {
+      return "string";
+    }
+    // Inline enums don't have a name so their values are not folded
+    let value = tokens.consts.get(&format!("{}.{}", enum_name, field.name));
+    if let Some(ConstValue::String(_)) = value {
+      return "string";
+    }
+  }
+  "int"
+}
+
+/// Adds a doc comment in the GoDoc format `// Foo does bar`
+fn doc_comment(doc: &Option<String>, lb: &mut impl BuildItems) {
+  if let Some(doc) = doc {
+    lb.comment(doc);
+  }
+}
+
+pub struct Go {
+  tokens: AnilizedTokens,
+  /// The packages that need to be imported
+  imports: Vec<String>,
+  /// The response type of the function we are generating
+  return_type: Option<Type>,
+  /// True if the function we are generating can throw errors
+  throws: bool,
+  /// The variables of the function we are generating that are never read
+  unused: HashSet<String>,
+  /// The places where the function we are generating reads its variables and arguments
+  locals: HashSet<(usize, usize)>,
+  /// The prefixes of the names of the items by the id of the file they are declared in,
+  /// the items of the file we compile have no prefix
+  prefixes: HashMap<usize, String>,
+}
+
+/// Returns true if one of the actions references the variable,
+/// go doesn't compile if a variable is declared but never used
+fn uses_variable(actions: &Actions, name: &str) -> bool {
+  let mut used = false;
+  actions.walk(&mut |action| {
+    if let ActionType::VarRef(var_name) = &action.type_ {
+      if var_name == name {
+        used = true;
+      }
+    }
+  });
+  used
+}
+
+/// Returns the prefix of the names of the items of an imported module, `./utils.tp` becomes `utils_`,
+/// imported modules are generated into the same go file so their names can't clash with the names of other modules
+fn module_prefix(module: &AnilizedTokens) -> String {
+  let name = module.file().name.trim_end_matches(".tp");
+  let mut res: String = name
+    .trim_start_matches("./")
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+    .collect();
+  res.push('_');
+  res
+}
+
+/// Adds the imported modules and the modules they import to modules once
+fn imported_modules(tokens: &AnilizedTokens, modules: &mut Vec<Rc<AnilizedTokens>>) {
+  let mut imports: Vec<&Rc<AnilizedTokens>> = tokens.modules.values().collect();
+  imports.sort_by(|a, b| a.file().name.cmp(&b.file().name));
+  for module in imports {
+    if modules
+      .iter()
+      .any(|added| added.file().id == module.file().id)
+    {
+      continue;
+    }
+    modules.push(Rc::clone(module));
+    imported_modules(module, modules);
+  }
+}
+
+/// Returns the go name of an enum field, `Shape.circle` becomes `ShapeCircle`
+fn enum_field_name(enum_name: &str, field_name: &str) -> String {
+  let mut res = String::from(enum_name);
+  for part in field_name.split('_') {
+    let mut chars = part.chars();
+    if let Some(first) = chars.next() {
+      res.push(first.to_ascii_uppercase());
+      res.push_str(chars.as_str());
+    }
+  }
+  res
+}
+
+impl Go {
+  /// Generate golang code using tokens from parser
+  pub fn generate(main_lb: &mut LangBuilder, t: AnilizedTokens) -> Result<(), LocationError> {
+    // TODO: Replace when file importing is implemented
+    main_lb.code("package main");
+
+    let mut modules: Vec<Rc<AnilizedTokens>> = vec![];
+    imported_modules(&t, &mut modules);
+    let mut prefixes: HashMap<usize, String> = modules
+      .iter()
+      .map(|module| (module.file().id, module_prefix(module)))
+      .collect();
+    prefixes.insert(t.file().id, String::new());
+
+    let mut code = Self {
+      tokens: t.clone(),
+      imports: vec![],
+      return_type: None,
+      throws: false,
+      unused: HashSet::new(),
+      locals: HashSet::new(),
+      prefixes,
+    };
+
+    // The imports are only known after generating the code so we write the code into a separate builder
+    let mut body = LangBuilder::new();
+    code.items(t, &mut body);
+
+    // We only output a single file so the imported modules are added to it with the prefix of their module
+    for module in modules {
+      let mut module = (*module).clone();
+      module.retain_target(&Lang::Go);
+      code.tokens = module.clone();
+      code.items(module, &mut body);
+    }
+
+    if !code.imports.is_empty() {
+      code.imports.sort();
+      let mut imports = Block::new();
+      for import in &code.imports {
+        imports.code(format!("\"{}\"", import));
+      }
+      main_lb.block_with(Inline::from_str("import"), imports, " (", ")");
+    }
+    main_lb.append(body);
+
+    Ok(())
+  }
+  /// Adds the functions, types, structs, enums and constants of a module
+  fn items(&mut self, t: AnilizedTokens, lb: &mut impl BuildItems) {
+    // define functions, extern functions already exist in go
+    for (_, func) in t.functions {
+      if !func.external {
+        self.function(func, lb);
+      }
+    }
+
+    // define types
+    for (_, type_) in t.types {
+      self.custom_type(type_, lb);
+    }
+
+    // define structs
+    for (_, structure) in t.structs {
+      self.structure(structure, lb);
+    }
+
+    // define enums
+    for (_, enum_) in t.enums {
+      self.enumeration(enum_, lb);
+    }
+
+    // define globals
+    for (_, glob) in t.vars {
+      self.global_var(glob, lb);
+    }
+  }
+  /// Marks the variables that are never read as used `_ = foo`,
+  /// go doesn't compile if a variable is never used where we only give a warning
+  fn discard_unused(&self, names: &[String], lb: &mut impl BuildItems) {
+    for name in names {
+      if name != "_" && self.unused.contains(name) {
+        lb.code(format!("_ = {}", name));
+      }
+    }
+  }
+  /// Returns the go name of a global item, the items of imported modules `utils.foo` get the prefix of their module
+  fn global_name(&self, name: &str) -> String {
+    let (tokens, name) = match self.tokens.module_ref(name) {
+      Some((_, module, name)) => (module, name),
+      None => (&self.tokens, name),
+    };
+    format!("{}{}", self.prefixes[&tokens.file().id], name)
+  }
+  /// Returns the go name of an enum field `Shape.circle` or `utils.Shape.circle` and the enum it belongs to
+  fn enum_field(&self, name: &str) -> Option<(String, Enum)> {
+    let (enum_, field_name) = self.tokens.enum_field_ref(name)?;
+    let enum_name = self.global_name(&name[..name.len() - field_name.len() - 1]);
+    Some((enum_field_name(&enum_name, field_name), enum_.clone()))
+  }
+  /// Adds a package to the imports of the file
+  fn import(&mut self, package: impl Into<String>) {
+    let package = package.into();
+    if !self.imports.contains(&package) {
+      self.imports.push(package);
+    }
+  }
+  /// Returns the name to call a function with,
+  /// extern functions call the native function they are bound to and import its package
+  fn function_name(&mut self, name: String) -> String {
+    let bound_to = self.tokens.extern_binding(&name, &Lang::Go);
+    match bound_to {
+      Some((symbol, import)) => {
+        if let Some(import) = import {
+          self.import(import);
+        }
+        symbol
+      }
+      None => self.global_name(&name),
+    }
+  }
+  /// Parse a type
+  pub fn parse_type(&mut self, type_: Type, lb: &mut impl BuildItems) {
+    match type_.type_ {
+      TypeType::Array(res) => self.type_array(*res, lb),
+      TypeType::Char => lb.code("char"),
+      TypeType::Bool => lb.code("bool"),
+      TypeType::Int => lb.code("int"),
+      TypeType::I8 => lb.code("int8"),
+      TypeType::I16 => lb.code("int16"),
+      TypeType::I32 => lb.code("int32"),
+      TypeType::I64 => lb.code("int64"),
+      TypeType::UInt => lb.code("uint"),
+      TypeType::U8 => lb.code("uint8"),
+      TypeType::U16 => lb.code("uint16"),
+      TypeType::U32 => lb.code("uint32"),
+      TypeType::U64 => lb.code("uint64"),
+      TypeType::F32 => lb.code("float32"),
+      TypeType::F64 => lb.code("float64"),
+      TypeType::String => lb.code("string"),
+      TypeType::Struct(res) => self.structure(res, lb),
+      TypeType::Tuple(res) => self.type_tuple(res, lb),
+      TypeType::TypeRef(res) => lb.code(match res.id {
+        Some(id) => format!("{}{}", self.prefixes[&id.file_id], id.name),
+        None => res.name,
+      }),
+      TypeType::Enum(res) => lb.code(if res.has_payload() {
+        // Inline enums with data can't be named so we can't define an interface for them
+        "interface{}"
+      } else {
+        enum_value_type(&res, &self.tokens)
+      }),
+    }
+  }
+  /// Parse a custom type definition
+  pub fn custom_type(&mut self, type_: GlobalType, lb: &mut impl BuildItems) {
+    doc_comment(&type_.doc, lb);
+    let name = self.global_name(&type_.name);
+    let mut code = Inline::from_str(if type_.alias {
+      format!("type {} = ", name)
+    } else {
+      format!("type {} ", name)
+    });
+    self.parse_type(type_.type_, &mut code);
+    lb.inline(code);
+  }
+  /// Parse a function
+  pub fn function(&mut self, func: Function, lb: &mut impl BuildItems) {
+    doc_comment(&func.doc, lb);
+    let name = func.name.unwrap();
+    let symbols = match self.tokens.symbols.get(&name) {
+      Some(table) => table.symbols.clone(),
+      None => vec![],
+    };
+    self.unused = symbols
+      .iter()
+      .filter(|symbol| symbol.references.is_empty())
+      .map(|symbol| symbol.name.clone())
+      .collect();
+    self.locals = symbols
+      .iter()
+      .flat_map(|symbol| &symbol.references)
+      .map(|span| (span.start, span.end))
+      .collect();
+    let mut prefix = Inline::from_str(format!("func {}(", self.global_name(&name)));
+    let mut is_first = true;
+    for arg in func.args {
+      if is_first {
+        is_first = false;
+      } else {
+        prefix.code(", ");
+      }
+      prefix.code(format!("{} ", arg.name));
+      if arg.variadic {
+        prefix.code("...");
+      }
+      self.parse_type(arg.type_, &mut prefix);
+    }
+    prefix.code(")");
+
+    self.function_results(func.res.clone(), func.throws, &mut prefix);
+
+    self.return_type = func.res;
+    self.throws = func.throws;
+
+    // Functions that only return an error need an explicit `return nil` at the end
+    let needs_return = self.return_type.is_none()
+      && func.throws
+      && !matches!(
+        func.body.actions.last(),
+        Some(Action {
+          type_: ActionType::Return(_) | ActionType::Throw(_),
+          ..
+        })
+      );
+
+    let mut actions = Block::new();
+    for action in func.body.actions {
+      self.action(action, &mut actions, false);
+    }
+    if needs_return {
+      actions.code("return nil");
+    }
+
+    lb.function(prefix, actions);
+  }
+  /// Adds the results of a function after its arguments
+  fn function_results(&mut self, res: Option<Type>, throws: bool, src: &mut Inline) {
+    match (res, throws) {
+      (Some(type_), false) => {
+        src.code(" ");
+        self.parse_type(type_, src);
+      }
+      (None, true) => src.code(" error"),
+      (Some(type_), true) => {
+        // Functions that can fail return an error as last value `(int, error)`
+        let types = match type_.type_ {
+          TypeType::Tuple(types) => types,
+          _ => vec![type_],
+        };
+        src.code(" (");
+        for type_ in types {
+          self.parse_type(type_, src);
+          src.code(", ");
+        }
+        src.code("error)");
+      }
+      (None, false) => {}
+    }
+  }
+  /// Returns the start of a return statement for an error containing the zero values of the
+  /// response of the function we are generating, the error itself should be added after this
+  fn return_error(&mut self) -> Inline {
+    let types = match self.return_type.clone() {
+      Some(Type {
+        type_: TypeType::Tuple(types),
+        ..
+      }) => types,
+      Some(type_) => vec![type_],
+      None => vec![],
+    };
+
+    let mut src = Inline::from_str("return ");
+    for type_ in types {
+      match type_.type_ {
+        TypeType::Int
+        | TypeType::I8
+        | TypeType::I16
+        | TypeType::I32
+        | TypeType::I64
+        | TypeType::UInt
+        | TypeType::U8
+        | TypeType::U16
+        | TypeType::U32
+        | TypeType::U64
+        | TypeType::F32
+        | TypeType::F64
+        | TypeType::Char => src.code("0"),
+        TypeType::String => src.code("\"\""),
+        TypeType::Bool => src.code("false"),
+        TypeType::Array(_) => src.code("nil"),
+        _ => {
+          // *new(T) results in the zero value of any type
+          src.code("*new(");
+          self.parse_type(type_, &mut src);
+          src.code(")");
+        }
+      }
+      src.code(", ");
+    }
+    src
+  }
+  /// Returns the amound of values a function call results in, the error not included
+  fn call_results(&self, call: &Action) -> usize {
+    let function = match &call.type_ {
+      ActionType::FunctionCall(call) => self.tokens.function(&call.name),
+      _ => None,
+    };
+    match function.and_then(|function| function.res) {
+      Some(Type {
+        type_: TypeType::Tuple(types),
+        ..
+      }) => types.len(),
+      Some(_) => 1,
+      None => 0,
+    }
+  }
+  /// Generates the code for a function call that can fail and it's error handling,
+  /// names contains the variables to assign the results to
+  fn handle_error(&mut self, action: Action, names: Vec<String>, lb: &mut impl BuildItems) {
+    let (call, error_name, body) = match action.type_ {
+      ActionType::Propagate(call) => (*call, None, None),
+      ActionType::Catch(catch) => (*catch.action, catch.error_name, Some(catch.body)),
+      _ => return,
+    };
+
+    let mut handler = Block::new();
+    match body {
+      None => {
+        let mut src = self.return_error();
+        src.code("__err");
+        handler.inline(src);
+      }
+      Some(body) => {
+        // The error message is only needed if the body uses it
+        if let Some(name) = error_name.filter(|name| uses_variable(&body, name)) {
+          handler.code(format!("{} := __err.Error()", name));
+        }
+        for action in body.actions {
+          self.action(action, &mut handler, false);
+        }
+      }
+    }
+
+    let assigned = names.clone();
+    let mut names = names;
+    names.push(String::from("__err"));
+    if names.len() == 1 {
+      // Nothing is assigned so we can scope the error to the if statement
+      for _ in 0..self.call_results(&call) {
+        names.insert(0, String::from("_"));
+      }
+      let mut prefix = Inline::from_str(format!("if {} := ", names.join(", ")));
+      self.action(call, &mut prefix, true);
+      prefix.code("; __err != nil");
+      lb.function(prefix, handler);
+    } else {
+      let mut src = Inline::from_str(format!("{} := ", names.join(", ")));
+      self.action(call, &mut src, true);
+      lb.inline(src);
+      lb.function(Inline::from_str("if __err != nil"), handler);
+      self.discard_unused(&assigned, lb);
+    }
+  }
+  /// Parse a const variable
+  pub fn global_var(&mut self, var: Variable, lb: &mut impl BuildItems) {
+    doc_comment(&var.doc, lb);
+    let mut inline = Inline::new();
+
+    inline.code(format!("const {} = ", self.global_name(&var.name)));
+    self.const_action(&var.name, *var.action, &mut inline);
+
+    lb.inline(inline);
+  }
+  /// Writes the folded value of a constant, if the value is unknown the action itself is written
+  fn const_action(&mut self, name: &str, action: Action, lb: &mut impl BuildItems) {
+    match self.tokens.consts.get(name) {
+      Some(value) => {
+        let value = value.clone().into_action(action.location);
+        self.action(value, lb, true);
+      }
+      None => self.action(action, lb, true),
+    }
+  }
+  /// Adds the value of an enum field, fields without a value get the value the anylize stage gave them
+  fn enum_value(&mut self, enum_name: &str, field: EnumField, lb: &mut impl BuildItems) {
+    let name = format!("{}.{}", enum_name, field.name);
+    match field.value {
+      Some(value) => self.const_action(&name, value, lb),
+      None => {
+        if let Some(value) = self.tokens.consts.get(&name) {
+          let value = value.clone().into_action(field.location);
+          self.action(value, lb, true);
+        }
+      }
+    }
+  }
+  /// Parse a structure
+  pub fn structure(&mut self, structure: Struct, lb: &mut impl BuildItems) {
+    doc_comment(&structure.doc, lb);
+    let prefix_str = if let Some(name) = structure.name {
+      format!("type {} struct ", self.global_name(&name))
+    } else {
+      String::from("struct ")
+    };
+
+    let mut fields = Block::new();
+    for field in structure.fields {
+      doc_comment(&field.doc, &mut fields);
+      let mut contents = Inline::from_str(format!("{} ", field.name));
+      self.parse_type(field.type_, &mut contents);
+      fields.inline(contents);
+    }
+    lb.function(Inline::from_str(prefix_str), fields);
+  }
+  /// Parse an enum
+  ///
+  /// Enums without data become a custom type with constants for every field,
+  /// enums with data become an interface with a struct for every field
+  pub fn enumeration(&mut self, enum_: Enum, lb: &mut impl BuildItems) {
+    let name = enum_.name.clone().unwrap_or_default();
+    let go_name = self.global_name(&name);
+    doc_comment(&enum_.doc, lb);
+
+    if !enum_.has_payload() {
+      lb.code(format!(
+        "type {} {}",
+        go_name,
+        enum_value_type(&enum_, &self.tokens)
+      ));
+
+      let mut consts = Block::new();
+      for field in enum_.fields {
+        doc_comment(&field.doc, &mut consts);
+        let mut inline = Inline::from_str(format!(
+          "{} {} = ",
+          enum_field_name(&go_name, &field.name),
+          go_name
+        ));
+        self.enum_value(&name, field, &mut inline);
+        consts.inline(inline);
+      }
+      lb.block_with(Inline::from_str("const"), consts, " (", ")");
+      return;
+    }
+
+    let mut methods = Block::new();
+    methods.code(format!("is{}()", go_name));
+    lb.function(
+      Inline::from_str(format!("type {} interface", go_name)),
+      methods,
+    );
+
+    for field in enum_.fields {
+      let struct_name = enum_field_name(&go_name, &field.name);
+
+      doc_comment(&field.doc, lb);
+      let mut fields = Block::new();
+      for data in field.payload {
+        let mut contents = Inline::from_str(format!("{} ", data.name));
+        self.parse_type(data.type_, &mut contents);
+        fields.inline(contents);
+      }
+      lb.function(
+        Inline::from_str(format!("type {} struct", struct_name)),
+        fields,
+      );
+      lb.code(format!("func ({}) is{}() {{}}", struct_name, go_name));
+    }
+  }
+  /// Parse array type
+  pub fn type_array(&mut self, item: Type, lb: &mut impl BuildItems) {
+    let mut array = Inline::from_str("[]");
+    self.parse_type(item, &mut array);
+    lb.inline(array);
+  }
+  /// Parse tuple type, go only supports these as function results
+  pub fn type_tuple(&mut self, types: Vec<Type>, lb: &mut impl BuildItems) {
+    let mut tuple = Inline::from_str("(");
+    for (i, type_) in types.into_iter().enumerate() {
+      if i != 0 {
+        tuple.code(", ");
+      }
+      self.parse_type(type_, &mut tuple);
+    }
+    tuple.code(")");
+    lb.inline(tuple);
+  }
+  /// Parse an action
+  pub fn action(&mut self, action: Action, lb: &mut impl BuildItems, inline: bool) {
+    // match an action and return code
+    match action.type_ {
+      ActionType::Assigment(res) if inline => {
+        lb.code(res.name + " = ");
+        self.action(*res.action, lb, true);
+      }
+      ActionType::Assigment(res) => {
+        let mut inline = Inline::from_str(res.name + " = ");
+        self.action(*res.action, &mut inline, true);
+        lb.inline(inline);
+      }
+      ActionType::Break(label) => lb.code(match label {
+        Some(label) => format!("break {}", label),
+        None => String::from("break"),
+      }),
+      ActionType::Continue(label) => lb.code(match label {
+        Some(label) => format!("continue {}", label),
+        None => String::from("continue"),
+      }),
+      ActionType::For(res) => self.action_for(res, lb),
+      ActionType::FunctionCall(res) => self.action_func_call(res, lb),
+      ActionType::Loop(res) => self.action_loop(res, lb),
+      ActionType::Return(res) => self.action_return(res, lb),
+      ActionType::StaticNumber(res) => self.action_num(res, lb),
+      ActionType::StaticString(res) => self.action_str(res, lb),
+      ActionType::StaticBoolean(res) => self.action_bool(res, lb),
+      ActionType::Variable(res) => self.action_var(res, lb),
+      ActionType::VariableTuple(res) => self.action_var_tuple(res, lb),
+      ActionType::Tuple(res) => self.action_tuple(res, lb),
+      ActionType::VarRef(res) => self.action_var_ref(res, &action.location, lb),
+      ActionType::While(res) => self.action_while(res, lb),
+      ActionType::If(if_) => self.action_if(if_, lb),
+      ActionType::Match(res) => self.action_match(res, lb),
+      ActionType::Operation(res) => self.action_operation(res, lb),
+      ActionType::Unary(res) => self.action_unary(res, lb),
+      ActionType::Defer(res) => {
+        let mut src = Inline::from_str("defer ");
+        self.action(*res, &mut src, true);
+        lb.inline(src);
+      }
+      ActionType::Throw(res) => {
+        self.import("errors");
+        let mut src = self.return_error();
+        src.code("errors.New(");
+        self.action(*res, &mut src, true);
+        src.code(")");
+        lb.inline(src);
+      }
+      ActionType::Propagate(_) | ActionType::Catch(_) => self.handle_error(action, vec![], lb),
+      // Ranges are only used inside for loops
+      ActionType::Range(_) => {}
+    };
+  }
+  fn if_block(
+    &mut self,
+    body: Actions,
+    prefix: &'static str,
+    add_to_prefix: impl FnOnce(&mut Self, &mut Inline),
+  ) -> (Inline, Block) {
+    let mut prefix = Inline::from_str(prefix);
+    add_to_prefix(self, &mut prefix);
+    let mut actions = Block::new();
+    for action in body.actions {
+      self.action(action, &mut actions, false);
+    }
+    (prefix, actions)
+  }
+  pub fn action_if(&mut self, if_: ActionIf, lb: &mut impl BuildItems) {
+    let mut blocks: Vec<(Inline, Block)> = vec![];
+
+    // if
+    let segment = *if_.if_.check;
+    let body = if_.if_.body.clone();
+    blocks.push(self.if_block(body, "if ", |s, p| {
+      s.action(segment, p, true);
+    }));
+
+    // else if
+    for else_if in if_.else_ifs {
+      blocks.push(self.if_block(else_if.body.clone(), "else if ", |s, p| {
+        s.action(*else_if.check, p, true);
+      }));
+    }
+
+    // else
+    if let Some(res) = if_.else_body {
+      blocks.push(self.if_block(res, "else", |_, _| {}))
+    }
+
+    lb.chain(blocks);
+  }
+  pub fn action_match(&mut self, action: ActionMatch, lb: &mut impl BuildItems) {
+    // A switch can't be used here as a break inside of a switch only breaks the switch,
+    // so we compile this into if else statements
+    let (value_name, mut wrapper) = match &action.value.type_ {
+      ActionType::VarRef(name) if !name.contains('.') => (name.clone(), None),
+      _ => {
+        // Make sure the value is only evaluated once,
+        // type assertions can only be used on interfaces so the value needs to be stored as one
+        let needs_interface = action
+          .arms
+          .iter()
+          .any(|arm| matches!(arm.pattern, MatchPattern::Destructure(_, _)));
+        let mut wrapper = Block::new();
+        let mut value = Inline::from_str(if needs_interface {
+          "var __match interface{} = "
+        } else {
+          "__match := "
+        });
+        self.action(*action.value, &mut value, true);
+        wrapper.inline(value);
+        (String::from("__match"), Some(wrapper))
+      }
+    };
+
+    let mut blocks: Vec<(Inline, Block)> = vec![];
+    for arm in action.arms {
+      let is_default = matches!(arm.pattern, MatchPattern::Default);
+      let mut prefix = Inline::from_str(if blocks.is_empty() { "if " } else { "else if " });
+      let mut body = Block::new();
+
+      match arm.pattern {
+        MatchPattern::Default => {
+          prefix = Inline::from_str(if blocks.is_empty() { "" } else { "else" });
+        }
+        MatchPattern::Destructure(field_ref, names) => {
+          let field_name = field_ref.rsplit('.').next().unwrap_or_default();
+          let (struct_name, payload) = match self.enum_field(&field_ref) {
+            Some((struct_name, enum_)) => {
+              let field = enum_.field(field_name);
+              (
+                struct_name,
+                field.map(|field| field.payload.clone()).unwrap_or_default(),
+              )
+            }
+            None => (String::new(), vec![]),
+          };
+
+          let mut used_value = false;
+          for (name, data) in names.iter().zip(payload.iter()) {
+            if name == "_" {
+              continue;
+            }
+            used_value = true;
+            body.code(format!("{} := __value.{}", name, data.name));
+          }
+          self.discard_unused(&names, &mut body);
+
+          prefix.code(format!(
+            "{}, __ok := {}.({}); __ok",
+            if used_value { "__value" } else { "_" },
+            value_name,
+            struct_name
+          ));
+        }
+        MatchPattern::Value(value) => {
+          prefix.code(format!("{} == ", value_name));
+          if let ActionType::VarRef(_) = value.type_ {
+            // Enum fields might be struct literals which need to be wrapped inside an if statement
+            prefix.code("(");
+            self.action(*value, &mut prefix, true);
+            prefix.code(")");
+          } else {
+            self.action(*value, &mut prefix, true);
+          }
+        }
+      }
+
+      for act in arm.body.actions {
+        self.action(act, &mut body, false);
+      }
+      blocks.push((prefix, body));
+
+      if is_default {
+        // All arms after this one are unreachable
+        break;
+      }
+    }
+
+    if let Some(wrapper) = &mut wrapper {
+      wrapper.chain(blocks);
+    } else {
+      lb.chain(blocks);
+      return;
+    }
+    lb.function(Inline::new(), wrapper.unwrap());
+  }
+  pub fn action_for(&mut self, action: ActionFor, lb: &mut impl BuildItems) {
+    self.loop_label(action.label.clone(), &action.actions, lb);
+    let mut actions = Block::new();
+    let prefix = match action.list.type_ {
+      ActionType::Range(range) => self.range_loop(range, &action.item_name),
+      _ => {
+        // Go doesn't allow _ as only name in a range loop so we leave out the names that are not used
+        let mut names: Vec<String> = vec![];
+        if action.item_name != "_" {
+          names.push(action.index_name.unwrap_or(String::from("_")));
+          names.push(action.item_name);
+        } else if let Some(index_name) = action.index_name.filter(|name| name != "_") {
+          names.push(index_name);
+        }
+
+        let mut prefix = Inline::from_str(if names.is_empty() {
+          String::from("for range ")
+        } else {
+          format!("for {} := range ", names.join(", "))
+        });
+        self.action(*action.list, &mut prefix, true);
+        self.discard_unused(&names, &mut actions);
+        prefix
+      }
+    };
+
+    for action in action.actions.actions {
+      self.action(action, &mut actions, false);
+    }
+
+    lb.function(prefix, actions);
+  }
+  /// Returns the header of a counting loop `for i := 0; i < 10; i++`
+  fn range_loop(&mut self, range: ActionRange, name: &str) -> Inline {
+    let name = if name == "_" { "__i" } else { name };
+
+    let mut prefix = Inline::from_str(format!("for {} := ", name));
+    self.action(*range.start, &mut prefix, true);
+    prefix.code(format!(
+      "; {} {} ",
+      name,
+      if range.inclusive { "<=" } else { "<" }
+    ));
+    self.action(*range.end, &mut prefix, true);
+    match range.step {
+      Some(step) => {
+        prefix.code(format!("; {} += ", name));
+        self.action(*step, &mut prefix, true);
+      }
+      None => prefix.code(format!("; {}++", name)),
+    }
+    prefix
+  }
+  pub fn action_var_ref(&mut self, name: String, location: &Span, lb: &mut impl BuildItems) {
+    if let Some((field_name, enum_)) = self.enum_field(&name) {
+      lb.code(if enum_.has_payload() {
+        // An enum field without data
+        field_name + "{}"
+      } else {
+        field_name
+      });
+      return;
+    }
+    if self.locals.contains(&(location.start, location.end)) {
+      lb.code(name);
+    } else {
+      lb.code(self.global_name(&name));
+    }
+  }
+  pub fn action_func_call(&mut self, mut action: ActionFunctionCall, lb: &mut impl BuildItems) {
+    let temporaries = self.tokens.bind_arguments(&mut action);
+    if !temporaries.is_empty() {
+      self.bound_call(action, temporaries, lb);
+      return;
+    }
+
+    // Go has no named arguments or default values so we always pass every argument in order
+    let arguments = self
+      .tokens
+      .ordered_arguments(&action)
+      .unwrap_or(action.arguments);
+
+    if let Some((field_name, _)) = self.enum_field(&action.name) {
+      // This creates an enum field with data
+      let mut src = Inline::from_str(field_name + "{");
+      for (i, arg) in arguments.into_iter().enumerate() {
+        if i != 0 {
+          src.code(", ");
+        }
+        self.action(arg, &mut src, true);
+      }
+      src.code("}");
+      lb.inline(src);
+      return;
+    }
+
+    let mut src = Inline::from_str(self.function_name(action.name) + "(");
+
+    let has_arguments = !arguments.is_empty();
+    for (i, arg) in arguments.into_iter().enumerate() {
+      if i != 0 {
+        src.code(",");
+      }
+      self.action(arg, &mut src, true);
+    }
+    if let Some(spread) = action.spread {
+      if has_arguments {
+        src.code(",");
+      }
+      self.action(*spread, &mut src, true);
+      src.code("...");
+    }
+
+    src.code(")");
+
+    lb.inline(src);
+  }
+  /// Wraps a call with arguments bound to temporaries (see bind_arguments) in a function that takes the temporaries,
+  /// go evaluates the values passed to it from left to right so the functions are called in the order they are written
+  fn bound_call(
+    &mut self,
+    action: ActionFunctionCall,
+    temporaries: Vec<(String, Type, Action)>,
+    lb: &mut impl BuildItems,
+  ) {
+    let mut src = Inline::from_str("func(");
+    for (i, (name, type_, _)) in temporaries.iter().enumerate() {
+      if i != 0 {
+        src.code(", ");
+      }
+      src.code(format!("{} ", name));
+      self.parse_type(type_.clone(), &mut src);
+    }
+    src.code(")");
+
+    // Enum fields with data can also be called, these result in their enum
+    let (res, throws) = match self.tokens.function(&action.name) {
+      Some(function) => (function.res, function.throws),
+      None => {
+        let location = temporaries[0].2.location.clone();
+        let res = self.tokens.enum_type(&action.name);
+        (res.map(|type_| Type { type_, location }), false)
+      }
+    };
+    let has_results = res.is_some() || throws;
+    self.function_results(res, throws, &mut src);
+
+    src.code(if has_results { " { return " } else { " { " });
+    self.action_func_call(action, &mut src);
+    src.code(" }(");
+    for (i, (_, _, value)) in temporaries.into_iter().enumerate() {
+      if i != 0 {
+        src.code(", ");
+      }
+      self.action(value, &mut src, true);
+    }
+    src.code(")");
+    lb.inline(src);
+  }
+  /// Adds the label of a loop if it's used,
+  /// go doesn't allow labels that are never used
+  fn loop_label(&mut self, label: Option<String>, body: &Actions, lb: &mut impl BuildItems) {
+    let label = if let Some(label) = label {
+      label
+    } else {
+      return;
+    };
+
+    let mut used = false;
+    body.walk(&mut |action| match &action.type_ {
+      ActionType::Break(Some(name)) | ActionType::Continue(Some(name)) if name == &label => {
+        used = true
+      }
+      _ => {}
+    });
+    if used {
+      lb.code(format!("{}:", label));
+    }
+  }
+  pub fn action_loop(&mut self, action: ActionLoop, lb: &mut impl BuildItems) {
+    self.loop_label(action.label, &action.actions, lb);
+    let prefix = Inline::from_str("for");
+
+    let mut contents = Block::new();
+    for act in action.actions.actions {
+      self.action(act, &mut contents, false);
+    }
+
+    lb.function(prefix, contents);
+  }
+  pub fn action_return(&mut self, action: Option<Box<Action>>, lb: &mut impl BuildItems) {
+    let to_add = match action {
+      Some(return_action) => {
+        let mut src = Inline::from_str("return ");
+        if let ActionType::Propagate(call) = return_action.type_ {
+          // The called function returns the same values as this function
+          self.action(*call, &mut src, true);
+        } else {
+          self.action(*return_action, &mut src, true);
+          if self.throws {
+            src.code(", nil");
+          }
+        }
+        src
+      }
+      None if self.throws => Inline::from_str("return nil"),
+      None => Inline::from_str("return"),
+    };
+
+    lb.inline(to_add);
+  }
+  pub fn action_num(&mut self, action: Number, lb: &mut impl BuildItems) {
+    lb.code(match action.type_ {
+      NumberType::Float(res) => res.to_string(),
+      NumberType::Int(res) => res.to_string(),
+    });
+  }
+  pub fn action_str(&mut self, action: String_, lb: &mut impl BuildItems) {
+    lb.code(format!("\"{}\"", action.content));
+  }
+  pub fn action_bool(&mut self, boolean: Boolean, lb: &mut impl BuildItems) {
+    lb.code(boolean.to_string());
+  }
+  pub fn action_var(&mut self, action: Variable, lb: &mut impl BuildItems) {
+    if let ActionType::Propagate(_) | ActionType::Catch(_) = action.action.type_ {
+      self.handle_error(*action.action, vec![action.name], lb);
+      return;
+    }
+
+    let prefix = format!("{} := ", action.name);
+    let mut src = Inline::from_str(prefix);
+
+    self.action(*action.action, &mut src, true);
+
+    lb.inline(src);
+    self.discard_unused(&[action.name], lb);
+  }
+  pub fn action_var_tuple(&mut self, action: VariableTuple, lb: &mut impl BuildItems) {
+    if let ActionType::Propagate(_) | ActionType::Catch(_) = action.action.type_ {
+      self.handle_error(*action.action, action.names, lb);
+      return;
+    }
+
+    let prefix = format!("{} := ", action.names.join(", "));
+    let mut src = Inline::from_str(prefix);
+
+    self.action(*action.action, &mut src, true);
+
+    lb.inline(src);
+    self.discard_unused(&action.names, lb);
+  }
+  pub fn action_tuple(&mut self, items: Vec<Action>, lb: &mut impl BuildItems) {
+    // Go doesn't have tuples but does support multiple values in returns and assignments
+    let mut src = Inline::new();
+    for (i, item) in items.into_iter().enumerate() {
+      if i != 0 {
+        src.code(", ");
+      }
+      self.action(item, &mut src, true);
+    }
+    lb.inline(src);
+  }
+  /// Writes an operation `a + b`,
+  /// operands that bind weaker than the operator are wrapped in brackets
+  pub fn action_operation(&mut self, operation: ActionOperation, lb: &mut impl BuildItems) {
+    let precedence = operation.operator.precedence();
+    let mut src = Inline::new();
+    self.operand(*operation.left, precedence, false, &mut src);
+    src.code(format!(" {} ", operation.operator.to_str()));
+    self.operand(*operation.right, precedence, true, &mut src);
+    lb.inline(src);
+  }
+  /// Writes an unary operation `-a`, operations are wrapped in brackets `-(a + b)`
+  pub fn action_unary(&mut self, unary: ActionUnary, lb: &mut impl BuildItems) {
+    let mut src = Inline::from_str(unary.operator.to_str());
+    let wrap = matches!(
+      unary.action.type_,
+      ActionType::Operation(_) | ActionType::Unary(_)
+    );
+    if wrap {
+      src.code("(");
+      self.action(*unary.action, &mut src, true);
+      src.code(")");
+    } else {
+      self.action(*unary.action, &mut src, true);
+    }
+    lb.inline(src);
+  }
+  fn operand(&mut self, operand: Action, precedence: u8, is_right: bool, src: &mut Inline) {
+    let wrap = match &operand.type_ {
+      ActionType::Operation(operation) if is_right => operation.operator.precedence() <= precedence,
+      ActionType::Operation(operation) => operation.operator.precedence() < precedence,
+      _ => false,
+    };
+    if wrap {
+      src.code("(");
+      self.action(operand, src, true);
+      src.code(")");
+    } else {
+      self.action(operand, src, true);
+    }
+  }
+  pub fn action_while(&mut self, action: ActionWhile, lb: &mut impl BuildItems) {
+    self.loop_label(action.label, &action.actions, lb);
+    let mut prefix = Inline::from_str("for ");
+    self.action(*action.true_value, &mut prefix, true);
+
+    let mut contents = Block::new();
+    for action in action.actions.actions {
+      self.action(action, &mut contents, false);
+    }
+
+    lb.function(prefix, contents);
+  }
+}
@@ -0,0 +1,754 @@
+use super::*;
+
+/// Returns true if the name is an identifier or a path to one `console.log`
+fn is_path(name: &str) -> bool {
+  name.split('.').all(|part| {
+    let mut chars = part.chars();
+    match chars.next() {
+      Some(first) if first.is_ascii_alphabetic() || first == '_' || first == '$' => {
+        chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+      }
+      _ => false,
+    }
+  })
+}
+
+/// Adds a doc comment in the JSDoc format `/** Foo does bar */`,
+/// a `*/` inside the comment is escaped so it doesn't end the comment
+fn doc_comment(doc: &Option<String>, lb: &mut impl BuildItems) {
+  if let Some(doc) = doc {
+    lb.line("/**");
+    lb.comment(&doc.replace("*/", "*\\/"));
+    lb.line(" */");
+  }
+}
+
+pub struct JavaScript {
+  tokens: AnilizedTokens,
+  /// The import lines of the modules used by extern functions
+  imports: Vec<String>,
+}
+
+impl JavaScript {
+  // Generate javascript code using tokens from parser
+  pub fn generate(main_lb: &mut LangBuilder, t: AnilizedTokens) -> Result<(), LocationError> {
+    let mut code = Self {
+      tokens: t.clone(),
+      imports: vec![],
+    };
+    // Comments are only used for JSDoc blocks
+    main_lb.comments = String::from(" * ");
+
+    // The imports are only known after generating the code so we write the code into a separate builder
+    let mut body = LangBuilder::new();
+    let lb = &mut body;
+
+    // define enums
+    for (_, enum_) in t.enums {
+      code.enumeration(enum_, lb);
+    }
+
+    // define functions, extern functions already exist in javascript
+    for (_, func) in t.functions {
+      if !func.external {
+        code.function(func, lb);
+      }
+    }
+    for (_, glob) in t.vars {
+      code.global_var(glob, lb);
+    }
+
+    // Because JS does not call main, we must do that here
+    lb.code("main();");
+
+    code.imports.sort();
+    for import in &code.imports {
+      main_lb.code(import);
+    }
+    main_lb.append(body);
+    Ok(())
+  }
+  /// Returns the name to call a function with,
+  /// extern functions call the native function they are bound to and import its module
+  fn function_name(&mut self, name: String) -> String {
+    let bound_to = self.tokens.extern_binding(&name, &Lang::JS);
+    let (symbol, module) = match bound_to {
+      Some((symbol, Some(module))) => (symbol, module),
+      // A binding can also be an expression like `(s) => s.toUpperCase()`
+      Some((symbol, None)) if !is_path(&symbol) => return format!("({})", symbol),
+      Some((symbol, None)) => return symbol,
+      None => return name,
+    };
+
+    // `path.join` imports the whole module as path, `readFile` only imports readFile
+    let import = match symbol.split_once('.') {
+      Some((alias, _)) => format!("import * as {} from \"{}\";", alias, module),
+      None => format!("import {{ {} }} from \"{}\";", symbol, module),
+    };
+    if !self.imports.contains(&import) {
+      self.imports.push(import);
+    }
+    symbol
+  }
+  pub fn function(&mut self, func: Function, lb: &mut impl BuildItems) {
+    doc_comment(&func.doc, lb);
+    let mut prefix_str = format!("function {}(", func.name.unwrap());
+    let mut args = vec![];
+    for arg in func.args {
+      args.push(if arg.variadic {
+        format!("...{}", arg.name)
+      } else {
+        arg.name
+      });
+    }
+    prefix_str += &args.join(", ");
+    prefix_str += ")";
+    // prefix looks somwthing like this here
+    // function foo(a, b, c)
+
+    let has_defers = func
+      .body
+      .actions
+      .iter()
+      .any(|action| matches!(action.type_, ActionType::Defer(_)));
+
+    let mut actions = Block::new();
+    for action in func.body.actions {
+      self.action(action, &mut actions, false);
+    }
+
+    if has_defers {
+      // The deferred calls run in reverse order once the function returns or throws
+      let mut run_defer = Block::new();
+      run_defer.code("__defers.pop()();");
+      let mut finally_body = Block::new();
+      finally_body.function(Inline::from_str("while (__defers.length > 0)"), run_defer);
+      let mut wrapped = Block::new();
+      wrapped.code("const __defers = [];");
+      wrapped.chain(vec![
+        (Inline::from_str("try"), actions),
+        (Inline::from_str("finally"), finally_body),
+      ]);
+      actions = wrapped;
+    }
+
+    lb.function(Inline::from_str(prefix_str), actions);
+  }
+  pub fn enumeration(&mut self, enum_: Enum, lb: &mut impl BuildItems) {
+    // Enums are objects with a key for every field,
+    // fields with data are functions that return a tagged object `{ tag: "circle", radius: radius }`
+    let enum_name = enum_.name.clone().unwrap_or_default();
+    doc_comment(&enum_.doc, lb);
+    let has_payload = enum_.has_payload();
+    let mut fields = Block::new();
+    for field in enum_.fields {
+      doc_comment(&field.doc, &mut fields);
+      let mut inline = Inline::from_str(format!("{}: ", field.name));
+      if has_payload {
+        let mut values = vec![format!("tag: \"{}\"", field.name)];
+        let mut args = vec![];
+        for data in field.payload {
+          values.push(format!("{name}: {name}", name = data.name));
+          args.push(data.name);
+        }
+        if args.is_empty() {
+          inline.code(format!("{{ {} }}", values.join(", ")));
+        } else {
+          inline.code(format!(
+            "({}) => ({{ {} }})",
+            args.join(", "),
+            values.join(", ")
+          ));
+        }
+      } else {
+        self.enum_value(&enum_name, field, &mut inline);
+      }
+      inline.code(",");
+      fields.inline(inline);
+    }
+
+    lb.block_with(
+      Inline::from_str(format!("const {} =", enum_name)),
+      fields,
+      " {",
+      "};",
+    );
+  }
+  /// Adds the value of an enum field, fields without a value get the value the anylize stage gave them
+  fn enum_value(&mut self, enum_name: &str, field: EnumField, lb: &mut impl BuildItems) {
+    let name = format!("{}.{}", enum_name, field.name);
+    match field.value {
+      Some(value) => self.const_action(&name, value, lb),
+      None => {
+        if let Some(value) = self.tokens.consts.get(&name) {
+          let value = value.clone().into_action(field.location);
+          self.action(value, lb, true);
+        }
+      }
+    }
+  }
+  pub fn global_var(&mut self, var: Variable, lb: &mut impl BuildItems) {
+    doc_comment(&var.doc, lb);
+    let mut inline = Inline::new();
+
+    inline.code(format!("const {} = ", var.name));
+    self.const_action(&var.name, *var.action, &mut inline);
+    inline.code(";");
+
+    lb.inline(inline);
+  }
+  /// Writes the folded value of a constant, if the value is unknown the action itself is written
+  fn const_action(&mut self, name: &str, action: Action, lb: &mut impl BuildItems) {
+    match self.tokens.consts.get(name) {
+      Some(value) => {
+        let value = value.clone().into_action(action.location);
+        self.action(value, lb, true);
+      }
+      None => self.action(action, lb, true),
+    }
+  }
+  pub fn action(&mut self, action: Action, lb: &mut impl BuildItems, inline: bool) {
+    // match an action and return code
+    match action.type_ {
+      ActionType::Assigment(res) if inline => {
+        lb.code(res.name + " = ");
+        self.action(*res.action, lb, true);
+      }
+      ActionType::Assigment(res) => {
+        let mut inline = Inline::from_str(res.name + " = ");
+        self.action(*res.action, &mut inline, true);
+        inline.code(";");
+        lb.inline(inline);
+      }
+      ActionType::Break(label) => self.action_jump("break", label, lb, inline),
+      ActionType::Continue(label) => self.action_jump("continue", label, lb, inline),
+      ActionType::For(res) => self.action_for(res, lb),
+      ActionType::FunctionCall(res) => self.action_func_call(res, lb, inline),
+      ActionType::Loop(res) => self.action_loop(res, lb),
+      ActionType::Return(res) => self.action_return(res, lb),
+      ActionType::StaticNumber(res) => self.action_num(res, lb),
+      ActionType::StaticString(res) => self.action_str(res, lb),
+      ActionType::StaticBoolean(res) => self.action_bool(res, lb),
+      ActionType::Variable(res) => self.action_var(res, lb),
+      ActionType::VariableTuple(res) => self.action_var_tuple(res, lb),
+      ActionType::Tuple(res) => self.action_tuple(res, lb),
+      ActionType::VarRef(res) => lb.code(res + if inline { "" } else { ";" }),
+      ActionType::While(res) => self.action_while(res, lb),
+      ActionType::If(if_) => self.action_if(if_, lb),
+      ActionType::Match(res) => self.action_match(res, lb),
+      ActionType::Operation(res) => self.action_operation(res, &action.location, lb),
+      ActionType::Unary(res) => self.action_unary(res, lb),
+      ActionType::Defer(res) => self.action_defer(*res, lb),
+      ActionType::Throw(res) => {
+        let mut src = Inline::from_str("throw new Error(");
+        self.action(*res, &mut src, true);
+        src.code(");");
+        lb.inline(src);
+      }
+      // Errors are exceptions in javascript so they propagate by themselves
+      ActionType::Propagate(res) => self.action(*res, lb, inline),
+      ActionType::Catch(res) => self.action_catch(res, None, lb),
+      // Ranges are only used inside for loops
+      ActionType::Range(_) => {}
+    };
+  }
+  /// Wraps a function call that can fail inside a try catch,
+  /// assign_to is the code to assign the result of the function to
+  pub fn action_catch(
+    &mut self,
+    action: ActionCatch,
+    assign_to: Option<String>,
+    lb: &mut impl BuildItems,
+  ) {
+    let mut try_body = Block::new();
+    let mut src = Inline::from_str(match assign_to {
+      Some(assign_to) => assign_to + " = ",
+      None => String::new(),
+    });
+    self.action(*action.action, &mut src, true);
+    src.code(";");
+    try_body.inline(src);
+
+    let mut catch_body = Block::new();
+    if let Some(name) = action.error_name {
+      // Errors are represented by their message
+      catch_body.code(format!("const {} = __err.message;", name));
+    }
+    for act in action.body.actions {
+      self.action(act, &mut catch_body, false);
+    }
+
+    lb.chain(vec![
+      (Inline::from_str("try"), try_body),
+      (Inline::from_str("catch (__err)"), catch_body),
+    ]);
+  }
+  fn if_block(
+    &mut self,
+    body: Actions,
+    prefix: &'static str,
+    add_to_prefix: impl FnOnce(&mut Self, &mut Inline),
+  ) -> (Inline, Block) {
+    let mut prefix = Inline::from_str(prefix);
+    add_to_prefix(self, &mut prefix);
+    let mut actions = Block::new();
+    for action in body.actions {
+      self.action(action, &mut actions, false);
+    }
+    (prefix, actions)
+  }
+  pub fn action_if(&mut self, if_: ActionIf, lb: &mut impl BuildItems) {
+    let mut blocks: Vec<(Inline, Block)> = vec![];
+
+    // if
+    let check = *if_.if_.check;
+    let body = if_.if_.body.clone();
+    blocks.push(self.if_block(body, "if (", |s, p| {
+      s.action(check, p, true);
+      p.code(")");
+    }));
+
+    // else if
+    for else_if in if_.else_ifs {
+      blocks.push(self.if_block(else_if.body.clone(), "else if (", |s, p| {
+        s.action(*else_if.check, p, true);
+        p.code(")");
+      }));
+    }
+
+    // else
+    if let Some(res) = if_.else_body {
+      blocks.push(self.if_block(res, "else", |_, _| {}))
+    }
+
+    lb.chain(blocks);
+  }
+  pub fn action_match(&mut self, action: ActionMatch, lb: &mut impl BuildItems) {
+    // A switch can't be used here as a break inside of a switch only breaks the switch,
+    // so we compile this into if else statements
+    let (value_name, mut wrapper) = match &action.value.type_ {
+      ActionType::VarRef(name) if !name.contains('.') => (name.clone(), None),
+      _ => {
+        // Make sure the value is only evaluated once
+        let mut wrapper = Block::new();
+        let mut value = Inline::from_str("const __match = ");
+        self.action(*action.value, &mut value, true);
+        value.code(";");
+        wrapper.inline(value);
+        (String::from("__match"), Some(wrapper))
+      }
+    };
+
+    let mut blocks: Vec<(Inline, Block)> = vec![];
+    for arm in action.arms {
+      let is_default = matches!(arm.pattern, MatchPattern::Default);
+      let mut prefix = Inline::from_str(if blocks.is_empty() {
+        "if ("
+      } else {
+        "else if ("
+      });
+      let mut body = Block::new();
+
+      match arm.pattern {
+        MatchPattern::Default => {
+          prefix = Inline::from_str(if blocks.is_empty() { "" } else { "else" });
+        }
+        MatchPattern::Destructure(field_ref, names) => {
+          let (enum_name, field_name) = field_ref.split_once('.').unwrap_or_default();
+          let payload = self
+            .tokens
+            .enums
+            .get(enum_name)
+            .and_then(|enum_| enum_.field(field_name))
+            .map(|field| field.payload.clone())
+            .unwrap_or_default();
+
+          for (name, data) in names.iter().zip(payload.iter()) {
+            if name == "_" {
+              continue;
+            }
+            body.code(format!("const {} = {}.{};", name, value_name, data.name));
+          }
+
+          prefix.code(format!("{}.tag === \"{}\")", value_name, field_name));
+        }
+        MatchPattern::Value(value) => {
+          prefix.code(format!("{} === ", value_name));
+          self.action(*value, &mut prefix, true);
+          prefix.code(")");
+        }
+      }
+
+      for act in arm.body.actions {
+        self.action(act, &mut body, false);
+      }
+      blocks.push((prefix, body));
+
+      if is_default {
+        // All arms after this one are unreachable
+        break;
+      }
+    }
+
+    if let Some(wrapper) = &mut wrapper {
+      wrapper.chain(blocks);
+    } else {
+      lb.chain(blocks);
+      return;
+    }
+    lb.function(Inline::new(), wrapper.unwrap());
+  }
+  pub fn action_for(&mut self, action: ActionFor, lb: &mut impl BuildItems) {
+    self.loop_label(action.label.clone(), lb);
+    let prefix = match action.list.type_ {
+      ActionType::Range(range) => {
+        let name = action.item_name;
+        let mut prefix = Inline::from_str(format!("for (let {} = ", name));
+        self.action(*range.start, &mut prefix, true);
+        prefix.code(format!(
+          "; {} {} ",
+          name,
+          if range.inclusive { "<=" } else { "<" }
+        ));
+        self.action(*range.end, &mut prefix, true);
+        match range.step {
+          Some(step) => {
+            prefix.code(format!("; {} += ", name));
+            self.action(*step, &mut prefix, true);
+          }
+          None => prefix.code(format!("; {}++", name)),
+        }
+        prefix.code(")");
+        prefix
+      }
+      list => {
+        // for..of loops over the values, entries() gives us the index together with the value
+        let mut prefix = Inline::from_str(match &action.index_name {
+          Some(index_name) => format!("for (const [{}, {}] of ", index_name, action.item_name),
+          None => format!("for (const {} of ", action.item_name),
+        });
+        self.action(
+          Action {
+            location: action.list.location,
+            type_: list,
+            attributes: vec![],
+          },
+          &mut prefix,
+          true,
+        );
+        if action.index_name.is_some() {
+          prefix.code(".entries()");
+        }
+        prefix.code(")");
+        prefix
+      }
+    };
+
+    let mut actions = Block::new();
+    for action in action.actions.actions {
+      self.action(action, &mut actions, false);
+    }
+
+    lb.function(prefix, actions);
+  }
+  pub fn action_func_call(
+    &mut self,
+    action: ActionFunctionCall,
+    lb: &mut impl BuildItems,
+    inline: bool,
+  ) {
+    let is_enum_field = match action.name.split_once('.') {
+      Some((enum_name, _)) => self.tokens.enums.contains_key(enum_name),
+      None => false,
+    };
+    if is_enum_field && !inline {
+      // Creating an enum field without using it does nothing
+      return;
+    }
+
+    let mut src = Inline::new();
+    self.bound_call(action, &mut src, |s, action, src| {
+      src.code(s.function_name(action.name.clone()) + "(");
+      s.call_arguments(action, false, src);
+      src.code(")");
+    });
+    if !inline {
+      src.code(";");
+    }
+
+    lb.inline(src);
+  }
+  /// Writes the arguments of a function call separated by commas,
+  /// after_value tells if something is already written in front of the arguments that needs a comma
+  fn call_arguments(&mut self, action: ActionFunctionCall, after_value: bool, src: &mut Inline) {
+    // Named arguments don't exist in javascript so we always pass every argument in order
+    let arguments = self
+      .tokens
+      .ordered_arguments(&action)
+      .unwrap_or(action.arguments);
+
+    let mut has_arguments = after_value;
+    for arg in arguments {
+      if has_arguments {
+        src.code(",");
+      }
+      self.action(arg, src, true);
+      has_arguments = true;
+    }
+    if let Some(spread) = action.spread {
+      src.code(if has_arguments { ",..." } else { "..." });
+      self.action(*spread, src, true);
+    }
+  }
+  /// Writes a function call using add, when arguments are bound to temporaries (see bind_arguments)
+  /// the call is wrapped in a function that takes the temporaries so their values are evaluated in the order they are written
+  fn bound_call(
+    &mut self,
+    mut action: ActionFunctionCall,
+    src: &mut Inline,
+    add: impl FnOnce(&mut Self, ActionFunctionCall, &mut Inline),
+  ) {
+    let temporaries = self.tokens.bind_arguments(&mut action);
+    if temporaries.is_empty() {
+      add(self, action, src);
+      return;
+    }
+
+    let names: Vec<String> = temporaries
+      .iter()
+      .map(|(name, _, _)| name.clone())
+      .collect();
+    src.code(format!("(({}) => ", names.join(", ")));
+    add(self, action, src);
+    src.code(")(");
+    for (i, (_, _, value)) in temporaries.into_iter().enumerate() {
+      if i != 0 {
+        src.code(", ");
+      }
+      self.action(value, src, true);
+    }
+    src.code(")");
+  }
+  /// Defers a function call, the arguments are bound right away so they are evaluated where the call is deferred like in go
+  fn action_defer(&mut self, action: Action, lb: &mut impl BuildItems) {
+    let mut src = Inline::from_str("__defers.push(");
+    match action.type_ {
+      ActionType::FunctionCall(call) => self.bound_call(call, &mut src, |s, call, src| {
+        src.code(s.function_name(call.name.clone()) + ".bind(null");
+        s.call_arguments(call, true, src);
+        src.code(")");
+      }),
+      // The anylizer only allows function calls to be deferred
+      _ => {
+        src.code("() => ");
+        self.action(action, &mut src, true);
+      }
+    }
+    src.code(");");
+    lb.inline(src);
+  }
+  /// Generates a break or continue
+  fn action_jump(
+    &mut self,
+    keyword: &'static str,
+    label: Option<String>,
+    lb: &mut impl BuildItems,
+    inline: bool,
+  ) {
+    let mut src = String::from(keyword);
+    if let Some(label) = label {
+      src += &format!(" {}", label);
+    }
+    if !inline {
+      src += ";";
+    }
+    lb.code(src);
+  }
+  /// Adds the label of a loop
+  fn loop_label(&mut self, label: Option<String>, lb: &mut impl BuildItems) {
+    if let Some(label) = label {
+      lb.code(format!("{}:", label));
+    }
+  }
+  pub fn action_loop(&mut self, action: ActionLoop, lb: &mut impl BuildItems) {
+    self.loop_label(action.label, lb);
+    let prefix = Inline::from_str("while (true)");
+
+    let mut contents = Block::new();
+    for act in action.actions.actions {
+      self.action(act, &mut contents, false);
+    }
+
+    lb.function(prefix, contents);
+  }
+  pub fn action_return(&mut self, action: Option<Box<Action>>, lb: &mut impl BuildItems) {
+    let action = if let Some(action) = action {
+      action
+    } else {
+      lb.code("return;");
+      return;
+    };
+
+    let mut src = Inline::from_str("return ");
+
+    self.action(*action, &mut src, true);
+    src.code(";");
+
+    lb.inline(src);
+  }
+  pub fn action_num(&mut self, number: Number, lb: &mut impl BuildItems) {
+    lb.code(match number.type_ {
+      NumberType::Float(res) => res.to_string(),
+      NumberType::Int(res) => res.to_string(),
+    });
+  }
+  pub fn action_str(&mut self, action: String_, lb: &mut impl BuildItems) {
+    lb.code(format!("\"{}\"", action.content));
+  }
+  pub fn action_bool(&mut self, boolean: Boolean, lb: &mut impl BuildItems) {
+    lb.code(boolean.to_string());
+  }
+  pub fn action_var(&mut self, action: Variable, lb: &mut impl BuildItems) {
+    if let ActionType::Catch(catch) = action.action.type_ {
+      // The variable needs to be declared outside of the try block
+      lb.code(format!("let {};", action.name));
+      self.action_catch(catch, Some(action.name), lb);
+      return;
+    }
+
+    let prefix = format!(
+      "{var_type} {var_name} = ",
+      var_type = if let VarType::Const = action.var_type {
+        "const"
+      } else {
+        "let"
+      },
+      var_name = action.name
+    );
+    let mut src = Inline::from_str(prefix);
+
+    self.action(*action.action, &mut src, true);
+    src.code(";");
+
+    lb.inline(src);
+  }
+  pub fn action_var_tuple(&mut self, action: VariableTuple, lb: &mut impl BuildItems) {
+    // Ignored values are left empty in the array destructuring `let [, b] = foo();`
+    let mut names: Vec<String> = vec![];
+    for name in action.names {
+      names.push(if name == "_" { String::new() } else { name });
+    }
+
+    if let ActionType::Catch(catch) = action.action.type_ {
+      // The variables needs to be declared outside of the try block
+      let mut declared: Vec<String> = names.clone();
+      declared.retain(|name| !name.is_empty());
+      lb.code(format!("let {};", declared.join(", ")));
+      self.action_catch(catch, Some(format!("[{}]", names.join(", "))), lb);
+      return;
+    }
+
+    let prefix = format!(
+      "{var_type} [{var_names}] = ",
+      var_type = if let VarType::Const = action.var_type {
+        "const"
+      } else {
+        "let"
+      },
+      var_names = names.join(", ")
+    );
+    let mut src = Inline::from_str(prefix);
+
+    self.action(*action.action, &mut src, true);
+    src.code(";");
+
+    lb.inline(src);
+  }
+  pub fn action_tuple(&mut self, items: Vec<Action>, lb: &mut impl BuildItems) {
+    // Tuples are represented as arrays in javascript
+    let mut src = Inline::from_str("[");
+    for (i, item) in items.into_iter().enumerate() {
+      if i != 0 {
+        src.code(", ");
+      }
+      self.action(item, &mut src, true);
+    }
+    src.code("]");
+    lb.inline(src);
+  }
+  /// Writes an operation `a + b`,
+  /// operands that bind weaker than the operator are wrapped in brackets
+  pub fn action_operation(
+    &mut self,
+    operation: ActionOperation,
+    location: &Span,
+    lb: &mut impl BuildItems,
+  ) {
+    let precedence = operation.operator.precedence();
+    // Javascript only has floats so the division of two integers is rounded towards zero like in go
+    let integer_division = self.tokens.integer_divisions.contains(location);
+    let mut src = Inline::from_str(if integer_division { "Math.trunc(" } else { "" });
+    self.operand(*operation.left, precedence, false, &mut src);
+    src.code(format!(
+      " {} ",
+      match operation.operator {
+        // Javascript its == and != convert the types before comparing
+        Operator::Equal => "===",
+        Operator::NotEqual => "!==",
+        operator => operator.to_str(),
+      }
+    ));
+    self.operand(*operation.right, precedence, true, &mut src);
+    if integer_division {
+      src.code(")");
+    }
+    lb.inline(src);
+  }
+  /// Writes an unary operation `-a`, operations are wrapped in brackets `-(a + b)`
+  pub fn action_unary(&mut self, unary: ActionUnary, lb: &mut impl BuildItems) {
+    let mut src = Inline::from_str(unary.operator.to_str());
+    let wrap = matches!(
+      unary.action.type_,
+      ActionType::Operation(_) | ActionType::Unary(_)
+    );
+    if wrap {
+      src.code("(");
+      self.action(*unary.action, &mut src, true);
+      src.code(")");
+    } else {
+      self.action(*unary.action, &mut src, true);
+    }
+    lb.inline(src);
+  }
+  fn operand(&mut self, operand: Action, precedence: u8, is_right: bool, src: &mut Inline) {
+    let wrap = match &operand.type_ {
+      // The truncated division is a function call that doesn't need brackets
+      ActionType::Operation(_) if self.tokens.integer_divisions.contains(&operand.location) => {
+        false
+      }
+      ActionType::Operation(operation) if is_right => operation.operator.precedence() <= precedence,
+      ActionType::Operation(operation) => operation.operator.precedence() < precedence,
+      _ => false,
+    };
+    if wrap {
+      src.code("(");
+      self.action(operand, src, true);
+      src.code(")");
+    } else {
+      self.action(operand, src, true);
+    }
+  }
+  pub fn action_while(&mut self, action: tokenize::ActionWhile, lb: &mut impl BuildItems) {
+    self.loop_label(action.label, lb);
+    let mut prefix = Inline::from_str("while (");
+    self.action(*action.true_value, &mut prefix, true);
+    prefix.code(")");
+
+    let mut contents = Block::new();
+    for action in action.actions.actions {
+      self.action(action, &mut contents, false);
+    }
+
+    lb.function(prefix, contents);
+  }
+}
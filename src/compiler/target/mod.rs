@@ -0,0 +1,60 @@
+mod builder;
+mod golang;
+mod javascript;
+
+use super::*;
+pub use anylize::constants::ConstValue;
+pub use anylize::AnilizedTokens;
+pub use builder::{Block, BuildItems, Inline, LangBuilder};
+use golang::Go;
+use javascript::JavaScript;
+use tokenize::attributes::attribute_targets;
+pub use tokenize::{
+  Action, ActionCatch, ActionFor, ActionFunctionCall, ActionIf, ActionLoop, ActionMatch,
+  ActionOperation, ActionRange, ActionType, ActionUnary, ActionWhile, Actions, Attribute, Boolean,
+  Enum, EnumField, Function, GlobalType, MatchPattern, Number, NumberType, Operator, String_,
+  Struct, Type, TypeType, VarType, Variable, VariableTuple,
+};
+
+#[derive(Clone, Debug)]
+pub enum Lang {
+  JS,
+  Go,
+}
+
+impl Lang {
+  /// Returns the language that matches the name used in `#[target(go)]`
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "js" => Some(Self::JS),
+      "go" => Some(Self::Go),
+      _ => None,
+    }
+  }
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::JS => "js",
+      Self::Go => "go",
+    }
+  }
+  /// Returns true if something with these attributes should be build for this language
+  pub fn matches(&self, attributes: &[Attribute]) -> bool {
+    match attribute_targets(attributes) {
+      Some(targets) => targets.contains(&self.name()),
+      None => true,
+    }
+  }
+}
+
+pub fn generate(mut t: AnilizedTokens, lang: Lang) -> Result<String, LocationError> {
+  t.retain_target(&lang);
+  let mut lb = LangBuilder::new();
+  let code = match lang {
+    Lang::JS => JavaScript::generate(&mut lb, t),
+    Lang::Go => Go::generate(&mut lb, t),
+  };
+  match code {
+    Ok(_) => Ok(format!("{}", lb)),
+    Err(error) => Err(error),
+  }
+}
@@ -1,23 +1,187 @@
 use super::*;
 use actions::parse_actions;
 use errors::{LocationError, TokenizeError};
-use files::CodeLocation;
-use numbers::NumberTypes;
-use statics::{valid_name_char, NameBuilder};
-use strings::parse_static_str;
-use variable::parse_var;
+use files::Span;
+use lexer::{Token, TokenKind};
+use numbers::{NumberParser, NumberTypes};
+use operators::{parse_operator, Operator, UnaryOperator};
+use variable::{parse_var, parse_var_tuple};
 
 #[derive(Debug, Clone)]
 pub struct Action {
-  pub location: CodeLocation,
+  pub location: Span,
   pub type_: ActionType,
+  /// The attributes above a statement `#[target(go)]`
+  pub attributes: Vec<Attribute>,
 }
 
 impl Action {
-  fn here(t: &mut Tokenizer, type_: ActionType) -> Self {
+  fn new(location: Span, type_: ActionType) -> Self {
     Self {
-      location: t.last_index_location(),
+      location,
       type_,
+      attributes: vec![],
+    }
+  }
+  /// Calls f for this action and all actions inside of it
+  pub fn walk<F: FnMut(&Action)>(&self, f: &mut F) {
+    f(self);
+    match &self.type_ {
+      ActionType::Variable(var) => var.action.walk(f),
+      ActionType::VariableTuple(var) => var.action.walk(f),
+      ActionType::Return(Some(action))
+      | ActionType::Throw(action)
+      | ActionType::Defer(action)
+      | ActionType::Propagate(action) => action.walk(f),
+      ActionType::Assigment(assignment) => assignment.action.walk(f),
+      ActionType::FunctionCall(call) => {
+        for argument in &call.arguments {
+          argument.walk(f);
+        }
+        for (_, argument) in &call.named_arguments {
+          argument.walk(f);
+        }
+        if let Some(spread) = &call.spread {
+          spread.walk(f);
+        }
+      }
+      ActionType::Tuple(items) => {
+        for item in items {
+          item.walk(f);
+        }
+      }
+      ActionType::Range(range) => {
+        range.start.walk(f);
+        range.end.walk(f);
+        if let Some(step) = &range.step {
+          step.walk(f);
+        }
+      }
+      ActionType::For(for_) => {
+        for_.list.walk(f);
+        for_.actions.walk(f);
+      }
+      ActionType::While(while_) => {
+        while_.true_value.walk(f);
+        while_.actions.walk(f);
+      }
+      ActionType::Loop(loop_) => loop_.actions.walk(f),
+      ActionType::If(if_) => {
+        if_.if_.check.walk(f);
+        if_.if_.body.walk(f);
+        for else_if in &if_.else_ifs {
+          else_if.check.walk(f);
+          else_if.body.walk(f);
+        }
+        if let Some(else_body) = &if_.else_body {
+          else_body.walk(f);
+        }
+      }
+      ActionType::Match(match_) => {
+        match_.value.walk(f);
+        for arm in &match_.arms {
+          if let MatchPattern::Value(value) = &arm.pattern {
+            value.walk(f);
+          }
+          arm.body.walk(f);
+        }
+      }
+      ActionType::Catch(catch) => {
+        catch.action.walk(f);
+        catch.body.walk(f);
+      }
+      ActionType::Operation(operation) => {
+        operation.left.walk(f);
+        operation.right.walk(f);
+      }
+      ActionType::Unary(unary) => unary.action.walk(f),
+      ActionType::Return(None)
+      | ActionType::VarRef(_)
+      | ActionType::StaticString(_)
+      | ActionType::StaticNumber(_)
+      | ActionType::StaticBoolean(_)
+      | ActionType::Break(_)
+      | ActionType::Continue(_) => {}
+    }
+  }
+  /// Calls f for all blocks inside of this action, f can change the blocks
+  pub fn walk_blocks_mut<F: FnMut(&mut Actions)>(&mut self, f: &mut F) {
+    match &mut self.type_ {
+      ActionType::Variable(var) => var.action.walk_blocks_mut(f),
+      ActionType::VariableTuple(var) => var.action.walk_blocks_mut(f),
+      ActionType::Return(Some(action))
+      | ActionType::Throw(action)
+      | ActionType::Defer(action)
+      | ActionType::Propagate(action) => action.walk_blocks_mut(f),
+      ActionType::Assigment(assignment) => assignment.action.walk_blocks_mut(f),
+      ActionType::FunctionCall(call) => {
+        for argument in &mut call.arguments {
+          argument.walk_blocks_mut(f);
+        }
+        for (_, argument) in &mut call.named_arguments {
+          argument.walk_blocks_mut(f);
+        }
+        if let Some(spread) = &mut call.spread {
+          spread.walk_blocks_mut(f);
+        }
+      }
+      ActionType::Tuple(items) => {
+        for item in items {
+          item.walk_blocks_mut(f);
+        }
+      }
+      ActionType::Range(range) => {
+        range.start.walk_blocks_mut(f);
+        range.end.walk_blocks_mut(f);
+        if let Some(step) = &mut range.step {
+          step.walk_blocks_mut(f);
+        }
+      }
+      ActionType::For(for_) => {
+        for_.list.walk_blocks_mut(f);
+        for_.actions.walk_blocks_mut(f);
+      }
+      ActionType::While(while_) => {
+        while_.true_value.walk_blocks_mut(f);
+        while_.actions.walk_blocks_mut(f);
+      }
+      ActionType::Loop(loop_) => loop_.actions.walk_blocks_mut(f),
+      ActionType::If(if_) => {
+        if_.if_.check.walk_blocks_mut(f);
+        if_.if_.body.walk_blocks_mut(f);
+        for else_if in &mut if_.else_ifs {
+          else_if.check.walk_blocks_mut(f);
+          else_if.body.walk_blocks_mut(f);
+        }
+        if let Some(else_body) = &mut if_.else_body {
+          else_body.walk_blocks_mut(f);
+        }
+      }
+      ActionType::Match(match_) => {
+        match_.value.walk_blocks_mut(f);
+        for arm in &mut match_.arms {
+          if let MatchPattern::Value(value) = &mut arm.pattern {
+            value.walk_blocks_mut(f);
+          }
+          arm.body.walk_blocks_mut(f);
+        }
+      }
+      ActionType::Catch(catch) => {
+        catch.action.walk_blocks_mut(f);
+        catch.body.walk_blocks_mut(f);
+      }
+      ActionType::Operation(operation) => {
+        operation.left.walk_blocks_mut(f);
+        operation.right.walk_blocks_mut(f);
+      }
+      ActionType::Unary(unary) => unary.action.walk_blocks_mut(f),
+      ActionType::Return(None)
+      | ActionType::VarRef(_)
+      | ActionType::StaticString(_)
+      | ActionType::StaticNumber(_)
+      | ActionType::StaticBoolean(_)
+      | ActionType::Break(_)
+      | ActionType::Continue(_) => {}
     }
   }
 }
@@ -25,6 +189,7 @@ impl Action {
 #[derive(Debug, Clone)]
 pub enum ActionType {
   Variable(Variable),
+  VariableTuple(VariableTuple),
   Return(Option<Box<Action>>),
   Assigment(ActionAssigment),
   FunctionCall(ActionFunctionCall),
@@ -32,12 +197,70 @@ pub enum ActionType {
   StaticString(String_),
   StaticNumber(Number),
   StaticBoolean(Boolean),
-  Break,
-  Continue,
+  Tuple(Vec<Action>),
+  /// A range of numbers `0..10`, only used as the list of a for loop
+  Range(ActionRange),
+  /// Break out of a loop, with an optional label of the loop to break `break 'outer`
+  Break(Option<String>),
+  /// Continue a loop, with an optional label of the loop to continue `continue 'outer`
+  Continue(Option<String>),
   For(ActionFor),
   While(ActionWhile),
-  Loop(Actions),
+  Loop(ActionLoop),
   If(ActionIf),
+  Match(ActionMatch),
+  /// Fail the current function with an error message `throw "foo"`
+  Throw(Box<Action>),
+  /// Call a function once the current function returns `defer close()`
+  Defer(Box<Action>),
+  /// Pass the error of a function call on to the caller `foo()?`
+  Propagate(Box<Action>),
+  /// Handle the error of a function call `foo() catch err {}`
+  Catch(ActionCatch),
+  /// An operator applied to two actions `foo + bar`
+  Operation(ActionOperation),
+  /// An operator applied to a single action `-foo` or `!foo`
+  Unary(ActionUnary),
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionOperation {
+  pub left: Box<Action>,
+  pub operator: Operator,
+  pub right: Box<Action>,
+}
+
+impl From<ActionOperation> for ActionType {
+  fn from(val: ActionOperation) -> Self {
+    ActionType::Operation(val)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionUnary {
+  pub operator: UnaryOperator,
+  pub action: Box<Action>,
+}
+
+impl From<ActionUnary> for ActionType {
+  fn from(val: ActionUnary) -> Self {
+    ActionType::Unary(val)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionCatch {
+  /// The function call that might fail
+  pub action: Box<Action>,
+  /// The name of the variable that holds the error inside the body
+  pub error_name: Option<String>,
+  pub body: Actions,
+}
+
+impl From<ActionCatch> for ActionType {
+  fn from(val: ActionCatch) -> Self {
+    ActionType::Catch(val)
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -53,15 +276,46 @@ pub struct IfCheckAndBody {
   pub body: Actions,
 }
 
+#[derive(Debug, Clone)]
+pub struct ActionMatch {
+  /// The value to match against
+  pub value: Box<Action>,
+  pub arms: Vec<MatchArm>,
+}
+
+impl From<ActionMatch> for ActionType {
+  fn from(val: ActionMatch) -> Self {
+    ActionType::Match(val)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+  pub pattern: MatchPattern,
+  pub body: Actions,
+  pub location: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum MatchPattern {
+  /// Matches everything `_ => {}`
+  Default,
+  /// Matches an enum field and destructures its data into variables `Shape.circle(radius) => {}`
+  /// The first argument is the enum field and the second the names of the variables
+  Destructure(String, Vec<String>),
+  /// Matches if the value equals this action `1 => {}` or `Foo.bar => {}`
+  Value(Box<Action>),
+}
+
 #[derive(Debug, Clone)]
 pub struct ActionAssigment {
   pub name: String,
   pub action: Box<Action>,
 }
 
-impl Into<ActionType> for ActionAssigment {
-  fn into(self) -> ActionType {
-    ActionType::Assigment(self)
+impl From<ActionAssigment> for ActionType {
+  fn from(val: ActionAssigment) -> Self {
+    ActionType::Assigment(val)
   }
 }
 
@@ -69,11 +323,15 @@ impl Into<ActionType> for ActionAssigment {
 pub struct ActionFunctionCall {
   pub name: String,
   pub arguments: Vec<Action>,
+  /// The arguments set by name `foo(bar: baz)`, these are always after the other arguments
+  pub named_arguments: Vec<(String, Action)>,
+  /// An array passed as the values of a variadic argument `foo(...bar)`, this is always the last argument
+  pub spread: Option<Box<Action>>,
 }
 
-impl Into<ActionType> for ActionFunctionCall {
-  fn into(self) -> ActionType {
-    ActionType::FunctionCall(self)
+impl From<ActionFunctionCall> for ActionType {
+  fn from(val: ActionFunctionCall) -> Self {
+    ActionType::FunctionCall(val)
   }
 }
 
@@ -81,6 +339,8 @@ pub struct ParseAction<'a> {
   t: &'a mut Tokenizer,
   res: Option<Action>,
   action_to_expect: ActionToExpect,
+  /// The location of the first token of the action
+  start: Span,
 }
 
 pub enum ParseActionState {
@@ -88,22 +348,27 @@ pub enum ParseActionState {
   Assigment(ParseActionStateAssigment),       // foo = bar
   FunctionCall(ParseActionStateFunctionCall), // foo(bar)
   VarRef(String),                             // foo
-  Break,
-  Continue,
+  Break(Option<String>),
+  Continue(Option<String>),
   For(ActionFor),
   While(ActionWhile),
-  Loop(Actions),
+  Loop(ActionLoop),
   If(ActionIf),
+  Match(ActionMatch),
+  Throw(Action),
+  Defer(Action),
 }
 
 pub struct ParseActionStateFunctionCall {
   name: String,
   arguments: Vec<Action>,
+  named_arguments: Vec<(String, Action)>,
+  spread: Option<Box<Action>>,
 }
 
-impl Into<ParseActionState> for ParseActionStateFunctionCall {
-  fn into(self) -> ParseActionState {
-    ParseActionState::FunctionCall(self)
+impl From<ParseActionStateFunctionCall> for ParseActionState {
+  fn from(val: ParseActionStateFunctionCall) -> Self {
+    ParseActionState::FunctionCall(val)
   }
 }
 
@@ -112,9 +377,9 @@ pub struct ParseActionStateAssigment {
   action: Option<Action>,
 }
 
-impl Into<ParseActionState> for ParseActionStateAssigment {
-  fn into(self) -> ParseActionState {
-    ParseActionState::Assigment(self)
+impl From<ParseActionStateAssigment> for ParseActionState {
+  fn from(val: ParseActionStateAssigment) -> Self {
+    ParseActionState::Assigment(val)
   }
 }
 
@@ -122,34 +387,19 @@ pub struct ParseActionStateReturn {
   action: Option<Action>, // The value to return
 }
 
-impl Into<ParseActionState> for ParseActionStateReturn {
-  fn into(self) -> ParseActionState {
-    ParseActionState::Return(self)
+impl From<ParseActionStateReturn> for ParseActionState {
+  fn from(val: ParseActionStateReturn) -> Self {
+    ParseActionState::Return(val)
   }
 }
 
+#[derive(Clone, Copy)]
 pub enum ActionToExpect {
   /// A line in a function body
   ActionInBody,
   /// A assingment of some sort,
   /// like the contents of a variable or a function argument or the value of the return
-  ///
-  /// The str argument tells to return Ok instaid of unexected char if on end of parsing
-  /// The unexpected char must match some letter out of the argument string
-  Assignment(&'static str),
-}
-
-enum DetectedAction {
-  /// 1. Just a variable name `foo`
-  VarRefName,
-  /// 2. `foo = bar`
   Assignment,
-  /// 3. functions `foo()`
-  Function,
-  // 4. inline strings `"foo"`
-  // 5. inline numbers `1`
-  // 6. inline arrays `[foo, bar]`
-  // 7. inline structs `foo{bar: baz}`
 }
 
 enum LoopType {
@@ -158,11 +408,11 @@ enum LoopType {
   Loop,
 }
 
-impl Into<LoopType> for Keywords {
-  fn into(self) -> LoopType {
-    match self {
-      Self::For => LoopType::For,
-      Self::While => LoopType::While,
+impl From<Keywords> for LoopType {
+  fn from(val: Keywords) -> Self {
+    match val {
+      Keywords::For => LoopType::For,
+      Keywords::While => LoopType::While,
       _ => LoopType::Loop,
     }
   }
@@ -172,40 +422,146 @@ impl Into<LoopType> for Keywords {
 pub struct ActionWhile {
   pub actions: Actions,
   pub true_value: Box<Action>,
+  pub label: Option<String>,
 }
 
-impl Into<ActionType> for ActionWhile {
-  fn into(self) -> ActionType {
-    ActionType::While(self)
+impl From<ActionWhile> for ActionType {
+  fn from(val: ActionWhile) -> Self {
+    ActionType::While(val)
   }
 }
 
 #[derive(Debug, Clone)]
 pub struct ActionFor {
   pub actions: Actions,
+  /// The list to loop over or a range `0..10`
   pub list: Box<Action>,
   pub item_name: String,
+  /// The name of the index variable in `for i, item in list`
+  pub index_name: Option<String>,
+  pub label: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionLoop {
+  pub actions: Actions,
+  /// The label of the loop `'outer: loop {}`, used by break and continue to refer to this loop
+  pub label: Option<String>,
+}
+
+impl From<ActionLoop> for ActionType {
+  fn from(val: ActionLoop) -> Self {
+    ActionType::Loop(val)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActionRange {
+  pub start: Box<Action>,
+  pub end: Box<Action>,
+  /// True if the end is part of the range `0..=10`
+  pub inclusive: bool,
+  /// The amound to increase with every step `0..10 step 2`
+  pub step: Option<Box<Action>>,
 }
 
-impl Into<ActionType> for ActionFor {
-  fn into(self) -> ActionType {
-    ActionType::For(self)
+impl From<ActionRange> for ActionType {
+  fn from(val: ActionRange) -> Self {
+    ActionType::Range(val)
+  }
+}
+
+impl From<ActionFor> for ActionType {
+  fn from(val: ActionFor) -> Self {
+    ActionType::For(val)
   }
 }
 
 impl<'a> ParseAction<'a> {
   pub fn start(
-    t: &'a mut Tokenizer,
-    go_back_one: bool,
+    t: &mut Tokenizer,
     action_to_expect: ActionToExpect,
   ) -> Result<Action, LocationError> {
-    if go_back_one {
-      t.index -= 1;
+    if let ActionToExpect::ActionInBody = action_to_expect {
+      return Self::start_operand(t, action_to_expect);
+    }
+
+    // Parse all operands and operators `foo + bar * baz`
+    let mut operands = vec![Self::start_operand(t, action_to_expect)?];
+    let mut operators: Vec<Operator> = vec![];
+    let res = Self::parse_operators(t, &mut operands, &mut operators);
+    // Every operator nests the operation one level deeper, see parse_operators
+    for _ in 0..operators.len() {
+      t.leave();
+    }
+    res?;
+
+    Ok(Self::combine_operands(operands, operators))
+  }
+  /// Parses the operators and operands after the first operand,
+  /// enters a level for every operator so long chains like `1 + 1 + 1` count towards the max depth
+  fn parse_operators(
+    t: &mut Tokenizer,
+    operands: &mut Vec<Action>,
+    operators: &mut Vec<Operator>,
+  ) -> Result<(), LocationError> {
+    while let Some(operator) = parse_operator(t) {
+      t.enter()?;
+      operators.push(operator);
+      operands.push(Self::start_operand(t, ActionToExpect::Assignment)?);
     }
-    let mut s = Self {
+    Ok(())
+  }
+  /// Combines the operands into operations, starting with the operators that bind the strongest
+  fn combine_operands(mut operands: Vec<Action>, mut operators: Vec<Operator>) -> Action {
+    while !operators.is_empty() {
+      let mut index = 0;
+      for (i, operator) in operators.iter().enumerate() {
+        if operator.precedence() > operators[index].precedence() {
+          index = i;
+        }
+      }
+
+      let operator = operators.remove(index);
+      let right = operands.remove(index + 1);
+      let left = operands.remove(index);
+      operands.insert(
+        index,
+        Action::new(
+          left.location.to(&right.location),
+          ActionOperation {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+          }
+          .into(),
+        ),
+      );
+    }
+
+    operands.remove(0)
+  }
+  /// Parses a single action without the operators after it
+  fn start_operand(
+    t: &mut Tokenizer,
+    action_to_expect: ActionToExpect,
+  ) -> Result<Action, LocationError> {
+    // The depth is left here so it stays the same when parsing the action fails
+    t.enter()?;
+    let res = Self::parse_operand(t, action_to_expect);
+    t.leave();
+    res
+  }
+  fn parse_operand(
+    t: &mut Tokenizer,
+    action_to_expect: ActionToExpect,
+  ) -> Result<Action, LocationError> {
+    let start = t.location();
+    let mut s = ParseAction {
       action_to_expect,
       t,
       res: None,
+      start,
     };
     s.detect()?;
     if let Some(res) = s.res {
@@ -224,7 +580,7 @@ impl<'a> ParseAction<'a> {
         ActionType::Return(return_action)
       }
       ParseActionState::Assigment(meta) => {
-        if let None = meta.action {
+        if meta.action.is_none() {
           return self
             .t
             .error(TokenizeError::Custom("Missing variable assignment"));
@@ -239,392 +595,524 @@ impl<'a> ParseAction<'a> {
       ParseActionState::FunctionCall(meta) => ActionFunctionCall {
         name: meta.name,
         arguments: meta.arguments,
+        named_arguments: meta.named_arguments,
+        spread: meta.spread,
       }
       .into(),
       ParseActionState::If(if_) => ActionType::If(if_),
+      ParseActionState::Match(match_) => match_.into(),
+      ParseActionState::Throw(action) => ActionType::Throw(Box::new(action)),
+      ParseActionState::Defer(action) => ActionType::Defer(Box::new(action)),
       ParseActionState::VarRef(name) => ActionType::VarRef(name),
-      ParseActionState::Break => ActionType::Break,
-      ParseActionState::Continue => ActionType::Continue,
+      ParseActionState::Break(label) => ActionType::Break(label),
+      ParseActionState::Continue(label) => ActionType::Continue(label),
       ParseActionState::While(meta) => meta.into(),
       ParseActionState::For(meta) => meta.into(),
-      ParseActionState::Loop(actions) => ActionType::Loop(actions),
+      ParseActionState::Loop(meta) => meta.into(),
     };
 
-    self.res = Some(Action::here(self.t, type_));
+    self.res = Some(self.action(type_));
 
     Ok(())
   }
+  /// Commits the result of parse, every kind of action gets its own copy of this function
+  /// so the stack used while parsing a nested action only contains the state of that kind of action
+  fn parse_and_commit<S: Into<ParseActionState>>(
+    &mut self,
+    parse: impl FnOnce(&mut Self) -> Result<S, LocationError>,
+  ) -> Result<(), LocationError> {
+    let state = parse(self)?;
+    self.commit_state(state)
+  }
+  /// Returns an action with a location from the start of this action till the last parsed token
+  fn action(&self, type_: ActionType) -> Action {
+    Action::new(self.t.span_from(&self.start), type_)
+  }
 
   fn detect(&mut self) -> Result<(), LocationError> {
-    let matched_res = if let ActionToExpect::ActionInBody = self.action_to_expect {
-      self.t.try_match(vec![
-        &Keywords::Const,
-        &Keywords::Let,
-        &Keywords::Return,
-        &Keywords::Loop,
-        &Keywords::While,
-        &Keywords::For,
-        &Keywords::Break,
-        &Keywords::Continue,
-        &Keywords::If,
-        &Keywords::Pub,
-      ])
-    } else {
-      None
+    let in_body = matches!(self.action_to_expect, ActionToExpect::ActionInBody);
+
+    // Every kind of action is parsed by its own method, the actions nest inside each other
+    // so this keeps the part of the stack used for every nested action small
+    let token = self.t.next();
+    match token.kind {
+      TokenKind::Label(label) if in_body => self.parse_labeled_loop(label),
+      TokenKind::Keyword(Keywords::True) => self.parse_static(Boolean(true).into()),
+      TokenKind::Keyword(Keywords::False) => self.parse_static(Boolean(false).into()),
+      TokenKind::Keyword(keyword) if in_body => self.detect_keyword(keyword, &token),
+      TokenKind::String(content) => self.parse_static(String_ { content }.into()),
+      TokenKind::Number(number) => self.parse_number(number),
+      TokenKind::Punctuation("(") => self.parse_brackets(),
+      TokenKind::Punctuation("-") => self.parse_unary(UnaryOperator::Negate),
+      TokenKind::Punctuation("!") => self.parse_unary(UnaryOperator::Not),
+      TokenKind::Identifier(name) => self.parse_identifier(name, in_body),
+      _ => self.t.unexpected(&token),
+    }
+  }
+  /// Parses a loop with a label `'outer: for foo in bar {}`, this expects the label to be already parsed
+  fn parse_labeled_loop(&mut self, label: String) -> Result<(), LocationError> {
+    self.t.expect(":")?;
+    let loop_type = match self.t.next().kind {
+      TokenKind::Keyword(keyword @ Keywords::Loop)
+      | TokenKind::Keyword(keyword @ Keywords::While)
+      | TokenKind::Keyword(keyword @ Keywords::For) => keyword.into(),
+      _ => {
+        return self
+          .t
+          .error(TokenizeError::Custom("Labels can only be used on loops"))
+      }
     };
-
-    // Try to match a keyword and react to it
-    if let Some(matched) = matched_res {
-      match matched {
-        Keywords::Const | Keywords::Let => {
-          // Go to parsing the variable
-          let var_type = if let Keywords::Const = matched {
-            VarType::Const
-          } else {
-            VarType::Let
-          };
-          let new_var = parse_var(self.t, Some(var_type))?;
-          self.res = Some(Action::here(self.t, new_var.into()));
-        }
-        Keywords::Return => {
-          // Go to parsing the return
-          let to_commit = self.parse_return()?;
-          self.commit_state(to_commit)?;
-        }
-        Keywords::Loop | Keywords::While | Keywords::For => {
-          // Parse loop
-          let to_commit = self.parse_looper(matched.clone().into())?;
-          self.commit_state(to_commit)?;
-        }
-        Keywords::Break => self.commit_state(ParseActionState::Break)?,
-        Keywords::Continue => self.commit_state(ParseActionState::Continue)?,
-        Keywords::If => {
-          // Parse if statement
-          let to_commit = self.parse_if()?;
-          self.commit_state(to_commit)?;
-        }
-        Keywords::Pub => unimplemented!(), // TODO
-        Keywords::True
-        | Keywords::False
-        | Keywords::Fn
-        | Keywords::Struct
-        | Keywords::Enum
-        | Keywords::Type
-        | Keywords::Else
-        | Keywords::Import => return self.t.error(TokenizeError::UnexpectedResult),
+    self.parse_and_commit(|s| s.parse_looper(loop_type, Some(label)))
+  }
+  /// Sets the result to a static value like a string or boolean
+  fn parse_static(&mut self, type_: ActionType) -> Result<(), LocationError> {
+    self.res = Some(self.action(type_));
+    Ok(())
+  }
+  fn parse_number(&mut self, number: String) -> Result<(), LocationError> {
+    let number =
+      NumberParser::new_without_starting(self.t, number.into_bytes()).result(NumberTypes::Auto)?;
+    self.parse_static(number.into())
+  }
+  /// Parses a tuple or an action wrapped in brackets, this expects the ( to be already parsed
+  fn parse_brackets(&mut self) -> Result<(), LocationError> {
+    let mut items = self.parse_tuple()?;
+    self.res = Some(if items.len() == 1 {
+      items.remove(0)
+    } else {
+      self.action(ActionType::Tuple(items))
+    });
+    Ok(())
+  }
+  /// Parses a variable `foo`, an assignment `foo = bar` or a function call `foo()`
+  fn parse_identifier(&mut self, name: String, in_body: bool) -> Result<(), LocationError> {
+    let name = self.parse_path(name)?;
+    if self.t.eat_same_line("(") {
+      let res = self.parse_function(name)?;
+      self.commit_state(res)?;
+      self.parse_error_handling()
+    } else if in_body && self.t.eat_same_line("=") {
+      let res = self.parse_var_assignment(name)?;
+      self.commit_state(res)
+    } else {
+      self.commit_state(ParseActionState::VarRef(name))
+    }
+  }
+  /// Parses the statement started by keyword
+  fn detect_keyword(&mut self, keyword: Keywords, token: &Token) -> Result<(), LocationError> {
+    match keyword {
+      Keywords::Const => self.parse_variable(VarType::Const),
+      Keywords::Let => self.parse_variable(VarType::Let),
+      Keywords::Return => self.parse_and_commit(Self::parse_return),
+      Keywords::Loop | Keywords::While | Keywords::For => {
+        self.parse_and_commit(|s| s.parse_looper(keyword.into(), None))
       }
-      return Ok(());
+      Keywords::Break => {
+        let label = self.parse_loop_label_ref();
+        self.commit_state(ParseActionState::Break(label))
+      }
+      Keywords::Continue => {
+        let label = self.parse_loop_label_ref();
+        self.commit_state(ParseActionState::Continue(label))
+      }
+      Keywords::If => self.parse_and_commit(Self::parse_if),
+      Keywords::Match => self.parse_and_commit(Self::parse_match),
+      Keywords::Throw => self.parse_and_commit(|s| {
+        // Parse the error to throw
+        let action = ParseAction::start(s.t, ActionToExpect::Assignment)?;
+        Ok(ParseActionState::Throw(action))
+      }),
+      Keywords::Defer => self.parse_and_commit(|s| {
+        // Parse the function call to defer
+        let action = ParseAction::start(s.t, ActionToExpect::Assignment)?;
+        Ok(ParseActionState::Defer(action))
+      }),
+      Keywords::Pub => self
+        .t
+        .error(TokenizeError::Custom("pub is not supported yet")),
+      Keywords::True
+      | Keywords::False
+      | Keywords::Fn
+      | Keywords::Struct
+      | Keywords::Enum
+      | Keywords::Type
+      | Keywords::Else
+      | Keywords::Throws
+      | Keywords::Catch
+      | Keywords::Step
+      | Keywords::Import
+      | Keywords::Extern => self.t.unexpected(token),
     }
-
-    // We are in a wired state right now where a lot of things are possible like
-    // 1. variable assgiment `foo` or `foo = bar` (the second one is not allowed when ActionToExpect is ActionInBody)
-    // 2. functions `foo()`
-    // 3. inline strings `"foo"`
-    // 4. inline numbers `1`
-    // 5. inline arrays `[foo, bar]`
-    // 6. inline structs `foo{bar: baz}`
-    //
-    // The code underhere will detect what the action is,
-    // TODO: 5, 6
-    let mut name = NameBuilder::new();
-    let mut detected_action = DetectedAction::VarRefName;
-    let mut name_completed = false;
-
-    while let Some(c) = self.t.next_char().2 {
-      match c {
-        '"' if name.len() == 0 => {
-          // Parse a static string
-          let parsed = parse_static_str(self.t)?;
-          self.res = Some(Action::here(self.t, parsed.into()));
-          return Ok(());
-        }
-        ' ' | '\t' | '\n' => {
-          if name.len() > 0 {
-            name_completed = true;
-          }
-          // Else ignore this
-        }
-        '(' => {
-          // Detected start of a function call
-          detected_action = DetectedAction::Function;
-          break;
-        }
-        '=' => {
-          // Detected variable assigment
-          detected_action = DetectedAction::Assignment;
-          break;
-        }
-        _ if (valid_name_char(c) || c == '.') && !name_completed => name.push(c),
-        c => {
-          if name_completed {
-            self.t.index -= 1;
-            break;
-          }
-
-          if let ActionToExpect::Assignment(valid_unexpted_chars) = self.action_to_expect {
-            if valid_unexpted_chars.contains(c) {
-              self.t.index -= 1;
-              break;
-            }
-          }
-          return self.t.unexpected_char(c);
-        }
+  }
+  fn parse_variable(&mut self, var_type: VarType) -> Result<(), LocationError> {
+    // Check if this is a tuple destructuring `let (a, b) = foo()`
+    let new_var: ActionType = if self.t.peek_is("(") {
+      parse_var_tuple(self.t, var_type)?.into()
+    } else {
+      parse_var(self.t, var_type)?.into()
+    };
+    self.parse_static(new_var)
+  }
+  /// Parses the value after an unary operator `-foo`, this expects the operator to be already parsed.
+  /// A negated number becomes a negative number `-1`
+  fn parse_unary(&mut self, operator: UnaryOperator) -> Result<(), LocationError> {
+    let action = ParseAction::start_operand(self.t, ActionToExpect::Assignment)?;
+    let type_ = match action.type_ {
+      ActionType::StaticNumber(number) if operator == UnaryOperator::Negate => Number {
+        type_: match number.type_ {
+          NumberType::Int(value) => NumberType::Int(-value),
+          NumberType::Float(value) => NumberType::Float(-value),
+        },
+      }
+      .into(),
+      type_ => ActionUnary {
+        operator,
+        action: Box::new(Action {
+          type_,
+          location: action.location,
+          attributes: action.attributes,
+        }),
       }
+      .into(),
+    };
+    self.res = Some(self.action(type_));
+    Ok(())
+  }
+  /// Parses the rest of a name with dots `foo.bar.baz`, this expects the first part to be already parsed
+  fn parse_path(&mut self, mut name: String) -> Result<String, LocationError> {
+    while self.t.eat_same_line(".") {
+      name.push('.');
+      name.push_str(&self.t.expect_name()?);
     }
-
-    if let Some(number) = name.is_boolean() {
-      // The defined name is actually a boolean
-      self.res = Some(Action::here(self.t, number.into()));
+    Ok(name)
+  }
+  /// Parses the error handling after a function call `foo()?` or `foo() catch err {}`
+  fn parse_error_handling(&mut self) -> Result<(), LocationError> {
+    let action = if let Some(action) = self.res.take() {
+      action
+    } else {
       return Ok(());
-    }
+    };
 
-    if let Some(number_parser) = name.is_number(self.t) {
-      // The defined name is actually a number
-      let number = number_parser.result(NumberTypes::Auto)?;
-      self.res = Some(Action::here(self.t, number.into()));
-      return Ok(());
-    }
+    self.res = Some(if self.t.eat_same_line("?") {
+      self.action(ActionType::Propagate(Box::new(action)))
+    } else if let TokenKind::Keyword(Keywords::Catch) = self.t.peek_same_line() {
+      self.t.next();
 
-    let name_string = name.to_string(self.t)?;
+      // Parse the name of the error variable
+      let error_name = if let TokenKind::Identifier(_) = self.t.peek() {
+        Some(self.t.expect_name()?)
+      } else {
+        None
+      };
 
-    // Do things relative to the detected action
-    match detected_action {
-      DetectedAction::VarRefName => {
-        self.commit_state(ParseActionState::VarRef(name_string))?;
-      }
-      DetectedAction::Assignment => {
-        let res = self.parse_var_assignment(name_string, true)?;
-        self.commit_state(res)?;
-      }
-      DetectedAction::Function => {
-        let res = self.parse_function(name_string, false)?;
-        self.commit_state(res)?;
-      }
-    };
-    return Ok(());
+      self.t.expect("{")?;
+      let body = parse_actions(self.t)?;
+
+      self.action(
+        ActionCatch {
+          action: Box::new(action),
+          error_name,
+          body,
+        }
+        .into(),
+      )
+    } else {
+      action
+    });
+    Ok(())
   }
+  /// Parses the arguments of a function call, this expects the opening ( to be already parsed
   fn parse_function(
     &mut self,
     name: String,
-    check_for_function_open_sign: bool,
   ) -> Result<ParseActionStateFunctionCall, LocationError> {
     let mut res = ParseActionStateFunctionCall {
       name,
       arguments: vec![],
+      named_arguments: vec![],
+      spread: None,
     };
 
-    if check_for_function_open_sign {
-      match self.t.must_next_char()? {
-        '(' => {} // This is what we expect. return no error
-        c => return self.t.unexpected_char(c),
+    loop {
+      if self.t.eat(")") {
+        break;
       }
-    }
 
-    loop {
-      match self.t.next_while(" \t\n") {
-        Some(')') | None => {
-          self.t.index -= 1;
-          break;
-        }
-        _ => {}
+      if self.t.eat("...") {
+        // This spreads an array over a variadic argument `foo(...bar)`
+        let action = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+        res.spread = Some(Box::new(action));
+        self.t.expect(")")?;
+        break;
       }
 
-      let action = ParseAction::start(self.t, true, ActionToExpect::Assignment(",)"))?;
-      res.arguments.push(action);
-      match self.t.next_while(" \t\n") {
-        Some(',') => continue,
-        _ => {
-          self.t.index -= 1;
-          break;
+      if let (TokenKind::Identifier(_), TokenKind::Punctuation(":")) =
+        (self.t.peek(), self.t.peek_second())
+      {
+        // This is a named argument `foo(bar: baz)`
+        let name = self.t.expect_name()?;
+        self.t.expect(":")?;
+        let value = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+        res.named_arguments.push((name, value));
+      } else {
+        if !res.named_arguments.is_empty() {
+          return self.t.error(TokenizeError::Custom(
+            "Arguments without a name must be placed before the named arguments",
+          ));
         }
+        let action = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+        res.arguments.push(action);
       }
-    }
 
-    match self.t.must_next_while_empty()? {
-      ')' => {} // This is what we expect. return no error
-      c => return self.t.unexpected_char(c),
+      if !self.t.eat(",") {
+        self.t.expect(")")?;
+        break;
+      }
     }
 
     Ok(res)
   }
-  fn parse_var_assignment(
-    &mut self,
-    name: String,
-    check_for_equal_sign: bool,
-  ) -> Result<ParseActionStateAssigment, LocationError> {
-    let mut res = ParseActionStateAssigment { name, action: None };
+  /// Parses the items of a tuple, this expects the opening ( to be already parsed
+  fn parse_tuple(&mut self) -> Result<Vec<Action>, LocationError> {
+    let mut items: Vec<Action> = vec![];
 
-    if check_for_equal_sign {
-      match self.t.must_next_while_empty()? {
-        '=' => {}
-        c => return self.t.unexpected_char(c),
+    loop {
+      if self.t.eat(")") {
+        break;
+      }
+
+      let action = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+      items.push(action);
+
+      if !self.t.eat(",") {
+        self.t.expect(")")?;
+        break;
       }
     }
 
-    self.t.must_next_while_empty()?;
-    let action = ParseAction::start(self.t, true, ActionToExpect::Assignment(""))?;
-    res.action = Some(action);
+    if items.is_empty() {
+      return self.t.error(TokenizeError::Custom("Empty tuple"));
+    }
 
-    Ok(res)
+    Ok(items)
+  }
+  /// Parses the value of an assignment, this expects the = to be already parsed
+  fn parse_var_assignment(
+    &mut self,
+    name: String,
+  ) -> Result<ParseActionStateAssigment, LocationError> {
+    let action = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+    Ok(ParseActionStateAssigment {
+      name,
+      action: Some(action),
+    })
   }
   fn parse_if(&mut self) -> Result<ParseActionState, LocationError> {
-    self.t.must_next_while_empty()?;
-
     let if_ = parse_if_check_and_body(self.t)?;
 
-    // Parse the else if(s) and check if we need to parse the last else
+    // Parse the else if(s) and the last else
     let mut else_ifs: Vec<IfCheckAndBody> = vec![];
-    let mut must_parse_else = false;
-    loop {
-      // Check for else
-      let mut c = self.t.must_next_while_empty()?;
-      if c != 'e' {
-        self.t.index -= 1;
+    let mut else_body: Option<Actions> = None;
+    while self.t.eat_keyword(Keywords::Else) {
+      if self.t.eat_keyword(Keywords::If) {
+        else_ifs.push(parse_if_check_and_body(self.t)?);
+      } else {
+        self.t.expect("{")?;
+        else_body = Some(parse_actions(self.t)?);
         break;
       }
-      c = self.t.must_next_char()?;
-      if c != 'l' {
-        self.t.index -= 2;
-        break;
-      }
-      c = self.t.must_next_char()?;
-      if c != 's' {
-        self.t.index -= 3;
-        break;
-      }
-      c = self.t.must_next_char()?;
-      if c != 'e' {
-        self.t.index -= 4;
-        break;
-      }
-      c = self.t.must_next_char()?;
-      match c {
-        '{' => {
-          must_parse_else = true;
-          break;
-        }
-        ' ' | '\t' | '\n' => {}
-        _ => {
-          self.t.index -= 5;
-          break;
-        }
-      }
-
-      c = self.t.must_next_while_empty()?;
-      if c == '{' {
-        must_parse_else = true;
-        break;
-      }
-      if c != 'i' {
-        return self.t.unexpected_char(c);
-      }
-      c = self.t.must_next_char()?;
-      if c != 'f' {
-        return self.t.unexpected_char(c);
-      }
-
-      let else_if = parse_if_check_and_body(self.t)?;
-      else_ifs.push(else_if);
     }
 
-    let else_body = if must_parse_else {
-      Some(parse_actions(self.t)?)
-    } else {
-      None
-    };
-
     Ok(ParseActionState::If(ActionIf {
       if_,
       else_ifs,
       else_body,
     }))
   }
-  fn parse_looper(&mut self, loop_type: LoopType) -> Result<ParseActionState, LocationError> {
-    self.t.must_next_while_empty()?;
+  fn parse_match(&mut self) -> Result<ParseActionState, LocationError> {
+    let value = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+    self.t.expect("{")?;
+
+    let mut arms: Vec<MatchArm> = vec![];
+    loop {
+      while self.t.eat(",") {}
+      if self.t.eat("}") {
+        break;
+      }
+      let location = self.t.location();
+
+      // Parse the pattern
+      let pattern_action = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+      let pattern = match pattern_action.type_ {
+        ActionType::VarRef(name) if name == "_" => MatchPattern::Default,
+        ActionType::FunctionCall(call)
+          if call.named_arguments.is_empty() && call.spread.is_none() =>
+        {
+          let mut names: Vec<String> = vec![];
+          for argument in call.arguments {
+            match argument.type_ {
+              ActionType::VarRef(name) if !name.contains('.') => names.push(name),
+              _ => {
+                return self.t.error(TokenizeError::Custom(
+                  "Expected a variable name to destructure into",
+                ))
+              }
+            }
+          }
+          MatchPattern::Destructure(call.name, names)
+        }
+        _ => MatchPattern::Value(Box::new(pattern_action)),
+      };
 
+      self.t.expect("=>")?;
+
+      // Parse the body, this is a block `{}` or a single action
+      let body = if self.t.eat("{") {
+        parse_actions(self.t)?
+      } else {
+        Actions {
+          actions: vec![ParseAction::start(self.t, ActionToExpect::ActionInBody)?],
+        }
+      };
+
+      arms.push(MatchArm {
+        pattern,
+        body,
+        location: self.t.span_from(&location),
+      });
+    }
+
+    Ok(ParseActionState::Match(ActionMatch {
+      value: Box::new(value),
+      arms,
+    }))
+  }
+  /// Parses the optional label after a break or continue `break 'outer`
+  fn parse_loop_label_ref(&mut self) -> Option<String> {
+    let label = match self.t.peek_same_line() {
+      TokenKind::Label(label) => label.clone(),
+      _ => return None,
+    };
+    self.t.next();
+    Some(label)
+  }
+  fn parse_looper(
+    &mut self,
+    loop_type: LoopType,
+    label: Option<String>,
+  ) -> Result<ParseActionState, LocationError> {
     let mut for_item_name: Option<String> = None;
+    let mut for_index_name: Option<String> = None;
 
     // Parse the bit between the "for"/"while" and "{"
     let loop_based_on = match loop_type {
-      LoopType::While => Some(ParseAction::start(
-        self.t,
-        true,
-        ActionToExpect::Assignment("{"),
-      )?),
+      LoopType::While => Some(ParseAction::start(self.t, ActionToExpect::Assignment)?),
       LoopType::For => {
-        self.t.index -= 1;
-        let mut name = NameBuilder::new();
-        loop {
-          match self.t.must_next_char()? {
-            ' ' | '\t' | '\n' => break,
-            c if valid_name_char(c) => name.push(c),
-            c => return self.t.unexpected_char(c),
-          }
-        }
+        let mut name = self.t.expect_name()?;
 
-        for_item_name = Some(name.to_string(self.t)?);
-        self.t.expect("in")?;
+        // Check for an index variable `for i, item in list`
+        if self.t.eat(",") {
+          for_index_name = Some(name);
+          name = self.t.expect_name()?;
+        }
+        for_item_name = Some(name);
 
-        self.t.must_next_while_empty()?;
+        if !self.t.eat_word("in") {
+          let token = self.t.next();
+          return self.t.unexpected(&token);
+        }
 
-        Some(ParseAction::start(
-          self.t,
-          true,
-          ActionToExpect::Assignment("{"),
-        )?)
-      }
-      LoopType::Loop => {
-        self.t.index -= 1;
-        None
+        let list = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+        if self.t.peek_is("..") || self.t.peek_is("..=") {
+          if for_index_name.is_some() {
+            return self.t.error(TokenizeError::Custom(
+              "A range can't be combined with an index variable",
+            ));
+          }
+          let location = list.location.clone();
+          let range = self.parse_range(list)?;
+          Some(Action::new(self.t.span_from(&location), range.into()))
+        } else {
+          Some(list)
+        }
       }
+      LoopType::Loop => None,
     };
 
-    match self.t.must_next_while_empty()? {
-      '{' => {}
-      c => return self.t.unexpected_char(c),
-    };
-
+    self.t.expect("{")?;
     let actions = parse_actions(self.t)?;
 
     Ok(match loop_type {
       LoopType::For => ParseActionState::For(ActionFor {
         actions,
         list: Box::new(loop_based_on.unwrap()),
-        item_name: for_item_name.unwrap_or(String::new()),
+        item_name: for_item_name.unwrap_or_default(),
+        index_name: for_index_name,
+        label,
       }),
       LoopType::While => ParseActionState::While(ActionWhile {
         actions,
         true_value: Box::new(loop_based_on.unwrap()),
+        label,
       }),
-      LoopType::Loop => ParseActionState::Loop(actions),
+      LoopType::Loop => ParseActionState::Loop(ActionLoop { actions, label }),
+    })
+  }
+  /// Parses the rest of a range after the start `0..10`, `0..=10` or `0..10 step 2`
+  fn parse_range(&mut self, start: Action) -> Result<ActionRange, LocationError> {
+    let inclusive = self.t.next().kind == TokenKind::Punctuation("..=");
+    let end = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+
+    let step = if self.t.eat_word("step") {
+      Some(Box::new(ParseAction::start(
+        self.t,
+        ActionToExpect::Assignment,
+      )?))
+    } else {
+      None
+    };
+
+    Ok(ActionRange {
+      start: Box::new(start),
+      end: Box::new(end),
+      inclusive,
+      step,
     })
   }
   fn parse_return(&mut self) -> Result<ParseActionStateReturn, LocationError> {
     let mut res = ParseActionStateReturn { action: None };
 
-    match self.t.must_next_while_empty()? {
-      '}' => {}
-      _ => {
-        let action = ParseAction::start(self.t, true, ActionToExpect::Assignment("}"))?;
-        res.action = Some(action);
+    // A return without a value ends the line or the block
+    match self.t.peek_same_line() {
+      TokenKind::Newline | TokenKind::Eof | TokenKind::Punctuation("}") => return Ok(res),
+      _ => {}
+    }
+
+    let mut items: Vec<Action> = vec![];
+    loop {
+      let action = ParseAction::start(self.t, ActionToExpect::Assignment)?;
+      items.push(action);
+
+      // Go style multiple return values `return foo, bar`
+      if !self.t.eat_same_line(",") {
+        break;
       }
     }
+
+    res.action = Some(if items.len() == 1 {
+      items.remove(0)
+    } else {
+      let location = items[0].location.clone();
+      Action::new(self.t.span_from(&location), ActionType::Tuple(items))
+    });
     Ok(res)
   }
 }
 
 fn parse_if_check_and_body(t: &mut Tokenizer) -> Result<IfCheckAndBody, LocationError> {
-  t.must_next_while_empty()?;
-
-  t.index -= 1;
-
-  let check = ParseAction::start(t, true, ActionToExpect::Assignment("{"))?;
-
-  let c = t.must_next_while_empty()?;
-  if '{' != c {
-    return t.unexpected_char(c);
-  }
-
+  let check = ParseAction::start(t, ActionToExpect::Assignment)?;
+  t.expect("{")?;
   let body = parse_actions(t)?;
   Ok(IfCheckAndBody {
     check: Box::new(check),
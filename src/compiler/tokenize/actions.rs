@@ -1,7 +1,7 @@
 use super::*;
 use action::{ActionToExpect, ParseAction};
+use attributes::parse_attributes;
 use errors::LocationError;
-use statics::valid_name_char;
 
 #[derive(Debug, Clone)]
 pub struct Actions {
@@ -12,21 +12,40 @@ impl Actions {
   pub fn empty() -> Self {
     Self { actions: vec![] }
   }
+  /// Calls f for every action and all actions inside of them
+  pub fn walk<F: FnMut(&Action)>(&self, f: &mut F) {
+    for action in &self.actions {
+      action.walk(f);
+    }
+  }
+  /// Calls f for this block and all blocks inside of its actions, f can change the blocks
+  pub fn walk_blocks_mut<F: FnMut(&mut Actions)>(&mut self, f: &mut F) {
+    f(self);
+    for action in &mut self.actions {
+      action.walk_blocks_mut(f);
+    }
+  }
+  /// Removes the actions for which f returns false, this includes the actions inside of other actions
+  pub fn retain<F: Fn(&Action) -> bool>(&mut self, f: &F) {
+    self.walk_blocks_mut(&mut |block| block.actions.retain(f));
+  }
 }
 
+/// Parses the actions of a block, this expects the opening { to be already parsed
 pub fn parse_actions(t: &mut Tokenizer) -> Result<Actions, LocationError> {
   let mut res = Actions::empty();
 
-  loop {
-    let c = t.must_next_while_empty()?;
-    if c == '}' {
-      return Ok(res);
-    }
-    if !valid_name_char(c) {
-      return t.unexpected_char(c);
-    }
+  while !t.eat("}") {
+    let attributes = if t.eat("#") {
+      parse_attributes(t)?
+    } else {
+      vec![]
+    };
 
-    let action = ParseAction::start(t, true, ActionToExpect::ActionInBody)?;
+    let mut action = ParseAction::start(t, ActionToExpect::ActionInBody)?;
+    action.attributes = attributes;
     res.actions.push(action);
   }
+
+  Ok(res)
 }
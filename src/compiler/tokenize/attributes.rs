@@ -0,0 +1,103 @@
+use super::*;
+use errors::LocationError;
+use files::Span;
+use lexer::TokenKind;
+
+/// An attribute above an item or statement like `#[target(go)]` or `#[deprecated]`
+#[derive(Debug, Clone)]
+pub struct Attribute {
+  pub name: String,
+  /// The values between the brackets `#[target(go, js)]`, strings are stored without quotes
+  pub args: Vec<String>,
+  pub location: Span,
+}
+
+/// Returns the arguments of the `target` attributes, None if there are no target attributes
+pub fn attribute_targets(attributes: &[Attribute]) -> Option<Vec<&str>> {
+  let mut res: Option<Vec<&str>> = None;
+  for attribute in attributes {
+    if attribute.name == "target" {
+      let targets = res.get_or_insert(vec![]);
+      for arg in &attribute.args {
+        targets.push(arg);
+      }
+    }
+  }
+  res
+}
+
+/// Returns the `#[deprecated]` attribute if there is one
+pub fn deprecated_attribute(attributes: &[Attribute]) -> Option<&Attribute> {
+  attributes
+    .iter()
+    .find(|attribute| attribute.name == "deprecated")
+}
+
+/// The native function an extern function calls for a target `#[bind(go, "strings.ToUpper", "strings")]`
+pub struct Binding<'a> {
+  pub symbol: &'a str,
+  /// The package or module that needs to be imported to use the symbol
+  pub import: Option<&'a str>,
+}
+
+/// Returns the binding of an extern function for target
+pub fn binding<'a>(attributes: &'a [Attribute], target: &str) -> Option<Binding<'a>> {
+  let attribute = attributes.iter().find(|attribute| {
+    attribute.name == "bind" && attribute.args.len() > 1 && attribute.args[0] == target
+  })?;
+  Some(Binding {
+    symbol: &attribute.args[1],
+    import: attribute.args.get(2).map(|import| import.as_str()),
+  })
+}
+
+/// Parses one or more attributes `#[foo] #[bar(a, "b")]`,
+/// this expects the first `#` to already be consumed
+pub fn parse_attributes(t: &mut Tokenizer) -> Result<Vec<Attribute>, LocationError> {
+  let mut res: Vec<Attribute> = vec![];
+
+  loop {
+    let location = t.last_location();
+    t.expect("[")?;
+    let name = t.expect_name()?;
+    let args = if t.eat("(") {
+      parse_attribute_args(t)?
+    } else {
+      vec![]
+    };
+    t.expect("]")?;
+
+    res.push(Attribute {
+      name,
+      args,
+      location: t.span_from(&location),
+    });
+
+    // Check if there is another attribute
+    if !t.eat("#") {
+      return Ok(res);
+    }
+  }
+}
+
+/// Parses the arguments of an attribute, this expects the opening ( to be already parsed
+fn parse_attribute_args(t: &mut Tokenizer) -> Result<Vec<String>, LocationError> {
+  let mut res: Vec<String> = vec![];
+
+  loop {
+    if res.is_empty() && t.eat(")") {
+      return Ok(res);
+    }
+
+    let token = t.next();
+    match token.kind {
+      TokenKind::String(arg) | TokenKind::Identifier(arg) | TokenKind::Number(arg) => res.push(arg),
+      _ => return t.unexpected(&token),
+    }
+
+    if !t.eat(",") {
+      t.expect(")")?;
+      return Ok(res);
+    }
+  }
+}
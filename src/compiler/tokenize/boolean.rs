@@ -1,20 +1,17 @@
 use super::*;
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct Boolean(pub bool);
 
-impl Boolean {
-  pub fn to_string(&self) -> String {
-    if self.0 {
-      "true".to_string()
-    } else {
-      "false".to_string()
-    }
+impl fmt::Display for Boolean {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
   }
 }
 
-impl Into<ActionType> for Boolean {
-  fn into(self) -> ActionType {
-    ActionType::StaticBoolean(self)
+impl From<Boolean> for ActionType {
+  fn from(val: Boolean) -> Self {
+    ActionType::StaticBoolean(val)
   }
 }
@@ -1,17 +1,38 @@
 use super::*;
+use action::{ActionToExpect, ParseAction};
 use actions::parse_actions;
 use errors::{LocationError, TokenizeError};
-use files::CodeLocation;
-use statics::{valid_name_char, NameBuilder};
+use files::Span;
+use lexer::TokenKind;
 use types::parse_type;
 
 #[derive(Debug, Clone)]
 pub struct Function {
-  pub location: CodeLocation,
+  pub location: Span,
   pub name: Option<String>,
-  pub args: Vec<(String, Type)>,
+  pub args: Vec<FunctionArg>,
   pub res: Option<Type>,
+  /// True if the function can fail `fn foo() int throws {}`
+  pub throws: bool,
+  /// True if the function is implemented by the target language `extern fn foo()`
+  pub external: bool,
   pub body: Actions,
+  /// The `///` doc comment above the function
+  pub doc: Option<String>,
+  /// The attributes above the function `#[target(go)]`
+  pub attributes: Vec<Attribute>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionArg {
+  pub name: String,
+  pub type_: Type,
+  /// The value used when the argument is not set by the caller `port int = 80`
+  pub default: Option<Action>,
+  /// True if the argument takes all remaining values of a call `parts ...string`,
+  /// inside the function the argument is an array of the type
+  pub variadic: bool,
+  pub location: Span,
 }
 
 impl GetName for Function {
@@ -20,118 +41,123 @@ impl GetName for Function {
   }
 }
 
+impl GetAttributes for Function {
+  fn attributes(&self) -> &[Attribute] {
+    &self.attributes
+  }
+}
+
 impl GetLocation for Function {
-  fn location(&self) -> CodeLocation {
+  fn location(&self) -> Span {
     self.location.clone()
   }
 }
 
-pub fn parse_function(t: &mut Tokenizer, anonymous: bool) -> Result<Function, LocationError> {
-  let location = t.last_index_location();
+/// Parses a function, this expects the fn keyword to be already parsed
+pub fn parse_function(
+  t: &mut Tokenizer,
+  anonymous: bool,
+  external: bool,
+) -> Result<Function, LocationError> {
+  let location = t.last_location();
 
   // Parse the function name
-  let mut name_builder: Option<NameBuilder> = None;
-  loop {
-    match t.must_next_char()? {
-      '\t' | '\n' | ' ' => {
-        if let Some(_) = name_builder {
-          // Not a valid name char return error
-          return t.error(TokenizeError::InvalidNameChar);
-        }
-      }
-      '(' => {
-        // end of function name, start parsing arguments
-        break;
-      }
-      c if valid_name_char(c) => {
-        // Parsing the function name
-        if let Some(name) = &mut name_builder {
-          name.push(c);
-        } else {
-          name_builder = Some(NameBuilder::new_with_char(c));
-        }
-      }
-      _ => {
-        // Not a valid name char return error
-        return t.error(TokenizeError::InvalidNameChar);
-      }
-    }
-  }
-  let name = if let Some(name) = name_builder {
+  let name = if let TokenKind::Identifier(_) = t.peek() {
     if anonymous {
       return t.error(TokenizeError::Custom("anonymous function with name"));
     }
-    Some(name.to_string(t)?)
+    Some(t.expect_name()?)
   } else {
     if !anonymous {
       return t.error(TokenizeError::Custom("function without name"));
     }
     None
   };
+  t.expect("(")?;
 
   // Parse the function args
-  let mut args: Vec<(String, Type)> = vec![];
-  'argsLoop: loop {
-    let mut name = NameBuilder::new();
-    loop {
-      match t.must_next_char()? {
-        '\t' | '\n' | ' ' => {
-          if name.len() > 0 {
-            break;
-          }
-        }
-        ')' => {
-          if name.len() > 0 {
-            // Argument not completed
-            return t.error(TokenizeError::IncompletedArgument);
-          }
-
-          // end of argument, start parsing response
-          break 'argsLoop;
-        }
-
-        c if valid_name_char(c) => {
-          // Parsing the function name
-          name.push(c);
-        }
-        _ => {
-          // Not a valid name char return error
-          return t.error(TokenizeError::InvalidNameChar);
-        }
-      }
+  let mut args: Vec<FunctionArg> = vec![];
+  loop {
+    if t.eat(")") {
+      break;
     }
 
-    let type_ = parse_type(t, false)?;
-
-    let break_after = match t.must_next_while_empty()? {
-      ')' => true,
-      ',' => false,
-      _ => return t.error(TokenizeError::InvalidNameChar),
+    let location = t.location();
+    let name = t.expect_name()?;
+    let variadic = t.eat("...");
+    let type_ = parse_type(t)?;
+    let default = if t.eat("=") {
+      Some(ParseAction::start(t, ActionToExpect::Assignment)?)
+    } else {
+      None
     };
 
-    args.push((name.to_string(t)?, type_));
+    args.push(FunctionArg {
+      name,
+      type_,
+      default,
+      variadic,
+      location: t.span_from(&location),
+    });
 
-    if break_after {
+    if !t.eat(",") {
+      t.expect(")")?;
       break;
     }
   }
 
+  if external {
+    let res = parse_extern_response(t)?;
+    return Ok(Function {
+      location: t.span_from(&location),
+      name,
+      args,
+      res,
+      throws: false,
+      external,
+      body: Actions::empty(),
+      doc: None,
+      attributes: vec![],
+    });
+  }
+
   let mut res: Option<Type> = None;
-  if t.must_next_while_empty()? != '{' {
-    res = Some(parse_type(t, true)?);
-    let c = t.must_next_while_empty()?;
-    if c != '{' {
-      return t.unexpected_char(c);
+  let mut throws = false;
+  if !t.eat("{") {
+    if t.eat_keyword(Keywords::Throws) {
+      throws = true;
+    } else {
+      res = Some(parse_type(t)?);
+      throws = t.eat_keyword(Keywords::Throws);
     }
+    t.expect("{")?;
   }
 
   let body = parse_actions(t)?;
 
   Ok(Function {
-    location,
+    location: t.span_from(&location),
     name,
     args,
     res,
+    throws,
+    external,
     body,
+    doc: None,
+    attributes: vec![],
   })
 }
+
+/// Extern functions don't have a body so their signature ends at the end of the line
+fn parse_extern_response(t: &mut Tokenizer) -> Result<Option<Type>, LocationError> {
+  if let TokenKind::Newline | TokenKind::Eof = t.peek_same_line() {
+    return Ok(None);
+  }
+
+  if t.eat_keyword(Keywords::Throws) {
+    return t.error(TokenizeError::Custom("extern functions can't throw"));
+  }
+  let res = parse_type(t)?;
+  t.expect_line_end()?;
+  Ok(Some(res))
+}
@@ -1,18 +1,31 @@
 use super::*;
+use attributes::parse_attributes;
 use errors::{LocationError, StateError, TokenizeError};
-use files::{CodeLocation, File};
+use files::{File, Span};
 use function::parse_function;
 use import::parse_import;
+use lexer::{lex, Token, TokenKind};
 use std::collections::HashMap;
 use std::fmt;
 use types::{parse_enum, parse_global_type, parse_struct};
-use utils::MatchString;
 use variable::parse_var;
 
+/// The max amound of actions and types that can be nested inside each other `((((a))))`,
+/// this stops the parser from running out of stack space on wired input.
+/// Unoptimized builds use up to 25kb of stack for every level when parsing, checking and building the code,
+/// so this fits within the 2mb stack threads get by default
+pub const MAX_DEPTH: usize = 64;
+
 pub struct Tokenizer {
   pub file: File,
-  pub index: usize,
-  pub y: u16,
+  /// The tokens of the file made by the lexer, the last token is always EOF
+  tokens: Vec<Token>,
+  /// The doc comments by the position of the token they document
+  docs: HashMap<usize, String>,
+  /// The position of the next token to parse
+  pos: usize,
+  /// How deep the parser currently is inside of nested actions and types
+  depth: usize,
   pub functions: Vec<Function>,
   pub vars: Vec<Variable>,
   pub structs: Vec<Struct>,
@@ -21,35 +34,27 @@ pub struct Tokenizer {
   pub imports: Vec<Import>,
 }
 
-#[derive(Debug)]
-struct SimpleTokenizer<'a> {
-  pub functions: &'a Vec<Function>,
-  pub vars: &'a Vec<Variable>,
-  pub structs: &'a Vec<Struct>,
-  pub enums: &'a Vec<Enum>,
-  pub types: &'a Vec<GlobalType>,
-  pub imports: &'a Vec<Import>,
-}
-
-impl<'a> fmt::Debug for Tokenizer {
+impl fmt::Debug for Tokenizer {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    let simple_tokenized = SimpleTokenizer {
-      functions: &self.functions,
-      vars: &self.vars,
-      structs: &self.structs,
-      enums: &self.enums,
-      types: &self.types,
-      imports: &self.imports,
-    };
-    writeln!(f, "{:#?}", simple_tokenized)
+    f.debug_struct("Tokenizer")
+      .field("functions", &self.functions)
+      .field("vars", &self.vars)
+      .field("structs", &self.structs)
+      .field("enums", &self.enums)
+      .field("types", &self.types)
+      .field("imports", &self.imports)
+      .finish()
   }
 }
 
 impl Tokenizer {
   pub fn tokenize(file: File) -> Result<Self, LocationError> {
+    let (tokens, docs) = attach_doc_comments(&file, lex(&file)?);
     let mut tokenizer = Self {
-      index: 0,
-      y: 1,
+      tokens,
+      docs,
+      pos: 0,
+      depth: 0,
       file,
       functions: vec![],
       vars: vec![],
@@ -63,352 +68,306 @@ impl Tokenizer {
     Ok(tokenizer)
   }
 
+  /// Returns an error located at the last consumed token
   pub fn error<T>(&self, error: impl Into<StateError>) -> Result<T, LocationError> {
-    self
-      .file
-      .error(error, CodeLocation::new(self.index, self.y))
+    self.file.error(error, self.last_location())
   }
 
-  pub fn unexpected_char<T>(&self, c: char) -> Result<T, LocationError> {
-    self.error(TokenizeError::UnexpectedChar(c))
+  /// Returns an error for a token the parser didn't expect
+  pub fn unexpected<T>(&self, token: &Token) -> Result<T, LocationError> {
+    let error = match &token.kind {
+      TokenKind::Eof => TokenizeError::UnexpectedEOF,
+      kind => TokenizeError::UnexpectedToken(kind.to_string()),
+    };
+    self.file.error(error, token.span.clone())
   }
 
-  pub fn unexpected_eof<T>(&self) -> Result<T, LocationError> {
-    self.error(TokenizeError::UnexpectedEOF)
+  /// Must be called before parsing something that can contain itself like an action or type,
+  /// returns an error if things are nested too deep, in that case leave must not be called
+  pub fn enter(&mut self) -> Result<(), LocationError> {
+    if self.depth >= MAX_DEPTH {
+      let location = self.location();
+      return self
+        .file
+        .error(TokenizeError::Custom("Code is nested too deep"), location);
+    }
+    self.depth += 1;
+    Ok(())
   }
 
-  pub fn last_char(&self) -> char {
-    let letter = self
-      .file
-      .bytes
-      .get(if self.index == 0 { 0 } else { self.index - 1 });
+  /// Must be called after the thing started with enter is parsed, also if parsing it failed
+  pub fn leave(&mut self) {
+    self.depth -= 1;
+  }
 
-    if let Some(c) = letter {
-      *c as char
-    } else {
-      0 as char
-    }
+  /// Returns the token at pos, reading past the end returns the EOF token
+  fn token_at(&self, pos: usize) -> &Token {
+    &self.tokens[pos.min(self.tokens.len() - 1)]
   }
 
-  pub fn must_next_char(&mut self) -> Result<char, LocationError> {
-    if let Some(c) = self.next_char().2 {
-      Ok(c)
-    } else {
-      self.unexpected_eof()
+  /// Returns the position of the first token from pos that isn't a newline
+  fn skip_newlines(&self, mut pos: usize) -> usize {
+    while let TokenKind::Newline = self.token_at(pos).kind {
+      pos += 1;
     }
+    pos
   }
 
-  /// Returns (added to index, added to y, char)
-  pub fn next_char(&mut self) -> (usize, u16, Option<char>) {
-    let letter = if let Some(l) = self.file.bytes.get(self.index) {
-      *l as char
-    } else {
-      return (0, 0, None);
-    };
+  /// Returns the next token without consuming it, newlines are skipped
+  pub fn peek(&self) -> &TokenKind {
+    &self.token_at(self.skip_newlines(self.pos)).kind
+  }
 
-    let start_index = self.index;
-    let start_y = self.y;
+  /// Returns the token after the next token without consuming anything, newlines are skipped
+  pub fn peek_second(&self) -> &TokenKind {
+    let first = self.skip_newlines(self.pos);
+    &self.token_at(self.skip_newlines(first + 1)).kind
+  }
 
-    self.index += 1;
-    if letter == '\n' {
-      self.y += 1;
+  /// Returns the next token without consuming it,
+  /// this is a newline if the next token is not on the current line
+  pub fn peek_same_line(&self) -> &TokenKind {
+    &self.token_at(self.pos).kind
+  }
+
+  /// Returns true if the next token is the punctuation, newlines are skipped
+  pub fn peek_is(&self, punctuation: &str) -> bool {
+    match self.peek() {
+      TokenKind::Punctuation(found) => *found == punctuation,
+      _ => false,
     }
+  }
 
-    // check for the start of a comment
-    if letter != '/' {
-      return (self.index - start_index, self.y - start_y, Some(letter));
+  /// Returns true if the next token is the punctuation and on the current line
+  pub fn peek_same_line_is(&self, punctuation: &str) -> bool {
+    match self.peek_same_line() {
+      TokenKind::Punctuation(found) => *found == punctuation,
+      _ => false,
     }
+  }
 
-    // check for next forward slash
-    match self.file.bytes.get(self.index) {
-      Some(b'/') => {
-        // detected single line comment
-        loop {
-          let next = if let Some(l) = self.file.bytes.get(self.index) {
-            *l as char
-          } else {
-            return (self.index - start_index, self.y - start_y, None);
-          };
-
-          self.index += 1;
-
-          // check for newline (end of comment)
-          if next == '\n' {
-            self.y += 1;
-
-            return (
-              self.index - start_index,
-              self.y - start_y,
-              self.next_char().2,
-            );
-          }
-        }
-      }
-      Some(b'*') => {
-        // detected multi-line comment
-        loop {
-          match self.file.bytes.get(self.index) {
-            Some(b'\n') => {
-              self.y += 1;
-              self.index += 1;
-            }
-            Some(b'*') => {
-              self.index += 1;
-
-              // * detected
-              if let Some(b'/') = self.file.bytes.get(self.index) {
-                // */ detected
-                self.index += 1;
-
-                return (
-                  self.index - start_index,
-                  self.y - start_y,
-                  self.next_char().2,
-                );
-              }
-            }
-            Some(_) => {
-              self.index += 1;
-            }
-            None => return (self.index - start_index, self.y - start_y, None),
-          }
-        }
-      }
-      _ => return (self.index - start_index, self.y - start_y, Some(letter)),
+  /// Consumes and returns the next token, newlines are skipped
+  pub fn next(&mut self) -> Token {
+    self.pos = self.skip_newlines(self.pos);
+    let token = self.token_at(self.pos).clone();
+    if self.pos < self.tokens.len() {
+      self.pos += 1;
     }
+    token
   }
 
-  fn seek_next_char(&self) -> Option<char> {
-    let letter = self.file.bytes.get(self.index)?;
-    Some(*letter as char)
+  /// Consumes the next token if it's the punctuation, newlines are skipped
+  pub fn eat(&mut self, punctuation: &str) -> bool {
+    if self.peek_is(punctuation) {
+      self.next();
+      true
+    } else {
+      false
+    }
   }
 
-  pub fn must_next_while(&mut self, chars: &'static str) -> Result<char, LocationError> {
-    if let Some(c) = self.next_while(chars) {
-      Ok(c)
+  /// Consumes the next token if it's the punctuation and on the current line
+  pub fn eat_same_line(&mut self, punctuation: &str) -> bool {
+    if self.peek_same_line_is(punctuation) {
+      self.next();
+      true
     } else {
-      self.unexpected_eof()
+      false
     }
   }
 
-  pub fn must_next_while_empty(&mut self) -> Result<char, LocationError> {
-    if let Some(c) = self.next_while(" \t\n") {
-      Ok(c)
+  /// Consumes the next token if it's the keyword, newlines are skipped
+  pub fn eat_keyword(&mut self, keyword: Keywords) -> bool {
+    if self.peek() == &TokenKind::Keyword(keyword) {
+      self.next();
+      true
     } else {
-      self.unexpected_eof()
+      false
     }
   }
 
-  pub fn next_while(&mut self, chars: &'static str) -> Option<char> {
-    while let Some(c) = self.next_char().2 {
-      if !chars.contains(c) {
-        return Some(c);
+  /// Consumes the next token if it's the name word,
+  /// used for words that are only keywords in some places like the `in` of a for loop
+  pub fn eat_word(&mut self, word: &str) -> bool {
+    match self.peek() {
+      TokenKind::Identifier(name) if name == word => {
+        self.next();
+        true
       }
+      _ => false,
     }
-    None
   }
 
-  /// Tries to match something
-  pub fn try_match<'a, T>(&mut self, options: Vec<&'a T>) -> Option<&'a T>
-  where
-    T: MatchString,
-  {
-    if options.len() == 0 {
-      return None;
+  /// Consumes the next token and returns an error if it's not the punctuation
+  pub fn expect(&mut self, punctuation: &str) -> Result<Token, LocationError> {
+    let token = self.next();
+    match &token.kind {
+      TokenKind::Punctuation(found) if *found == punctuation => Ok(token),
+      _ => self.unexpected(&token),
     }
+  }
 
-    let mut meta_map: HashMap<&'static str, &'a T> = HashMap::with_capacity(options.len());
-    let mut options_vec: Vec<&str> = vec![];
+  /// Consumes a name like a variable or function name
+  pub fn expect_name(&mut self) -> Result<String, LocationError> {
+    let token = self.next();
+    match token.kind {
+      TokenKind::Identifier(name) => Ok(name),
+      TokenKind::Number(_) => self.error(TokenizeError::Custom("name cannot start with a number")),
+      _ => self.unexpected(&token),
+    }
+  }
 
-    for option in options {
-      let option_str = option.get_string();
-      if option_str.len() == 0 {
-        continue;
+  /// Returns an error if there is something else than a newline or the end of the file on the current line
+  pub fn expect_line_end(&mut self) -> Result<(), LocationError> {
+    match self.peek_same_line() {
+      TokenKind::Newline | TokenKind::Eof => Ok(()),
+      _ => {
+        let token = self.next();
+        self.unexpected(&token)
       }
-      options_vec.push(option_str);
-      meta_map.insert(option_str, option);
     }
+  }
 
-    let start_index = self.index;
-    let start_y = self.y;
-
-    let mut char_count: usize = 0;
-    let mut next_char = self.next_char();
-
-    loop {
-      if let Some(c) = next_char.2 {
-        let mut new_options_vec: Vec<&str> = vec![];
-        for option in options_vec {
-          if option.len() <= char_count {
-            continue;
-          }
-          match option.as_bytes().get(char_count) {
-            Some(found_char) if *found_char as char == c => {
-              if option.len() != char_count + 1 {
-                new_options_vec.push(&option);
-                continue;
-              }
-
-              match meta_map.get(option) {
-                Some(meta) => {
-                  if let Some(next_char_needs_to_match) = meta.after() {
-                    // This option contains a surfix match, test test it here
-                    let next_char = self.seek_next_char();
-                    if let None = next_char {
-                      continue;
-                    } else if !next_char_needs_to_match.contains(next_char.unwrap()) {
-                      continue;
-                    }
-                  }
-
-                  return Some(meta)
-                }
-                None => panic!("A critical error has occured, please create an issue at https://github.com/talpalang/talpa/issues with your code so we can resolve this"),
-              }
-            }
-            _ => {}
-          }
-        }
-
-        next_char = self.next_char();
-        if new_options_vec.len() == 0 {
-          break;
-        }
-        options_vec = new_options_vec;
-        char_count += 1;
-      } else {
-        self.index = start_index;
-        self.y = start_y;
-        return None;
+  /// Returns the span of the last consumed token
+  pub fn last_location(&self) -> Span {
+    let mut pos = self.pos;
+    while pos > 0 {
+      pos -= 1;
+      if let TokenKind::Newline = self.tokens[pos].kind {
+        continue;
       }
+      return self.tokens[pos].span.clone();
     }
+    self.token_at(0).span.clone()
+  }
 
-    // Reset the index if we havent found the requested item
-    self.index = start_index;
-    self.y = start_y;
+  /// Returns the span of the next token, newlines are skipped
+  pub fn location(&self) -> Span {
+    self.token_at(self.skip_newlines(self.pos)).span.clone()
+  }
 
-    None
+  /// Returns a span from start till the end of the last consumed token
+  pub fn span_from(&self, start: &Span) -> Span {
+    start.to(&self.last_location())
   }
 
   fn parse_nothing(&mut self) -> Result<(), LocationError> {
     loop {
-      if let Some(_) = self.next_while(" \n\t") {
-        self.index -= 1;
-        match self.try_match(vec![
-          &Keywords::Const,
-          &Keywords::Fn,
-          &Keywords::Struct,
-          &Keywords::Enum,
-          &Keywords::Type,
-          &Keywords::Import,
-        ]) {
-          Some(Keywords::Import) => {
-            let mut parsed_import = parse_import(self)?;
-            self.imports.append(&mut parsed_import);
-          }
-          Some(Keywords::Const) => {
-            let parsed_variable = parse_var(self, Some(VarType::Const))?;
-            self.vars.push(parsed_variable);
-          }
-          Some(Keywords::Fn) => {
-            let parsed_function = parse_function(self, false)?;
-            self.functions.push(parsed_function);
-          }
-          Some(Keywords::Struct) => {
-            let parsed_struct = parse_struct(self, false, false)?;
-            self.structs.push(parsed_struct);
-          }
-          Some(Keywords::Enum) => {
-            let parsed_enum = parse_enum(self, false, false)?;
-            self.enums.push(parsed_enum);
-          }
-          Some(Keywords::Type) => {
-            let parsed_type = parse_global_type(self)?;
-            self.types.push(parsed_type);
+      let doc = self.doc_comment();
+      let attributes = if self.eat("#") {
+        parse_attributes(self)?
+      } else {
+        vec![]
+      };
+
+      let token = self.next();
+      match token.kind {
+        TokenKind::Keyword(Keywords::Import) => {
+          if !attributes.is_empty() {
+            return self.error(TokenizeError::Custom("Attributes can't be used on imports"));
           }
-          _ => {
-            let c = self.last_char();
-            if c != 0 as char {
-              return self.unexpected_char(c);
-            } else {
-              return self.unexpected_eof();
-            }
+          let mut parsed_import = parse_import(self)?;
+          self.imports.append(&mut parsed_import);
+        }
+        TokenKind::Keyword(Keywords::Const) => {
+          let mut parsed_variable = parse_var(self, VarType::Const)?;
+          parsed_variable.doc = doc;
+          parsed_variable.attributes = attributes;
+          self.vars.push(parsed_variable);
+        }
+        TokenKind::Keyword(Keywords::Fn) => {
+          let mut parsed_function = parse_function(self, false, false)?;
+          parsed_function.doc = doc;
+          parsed_function.attributes = attributes;
+          self.functions.push(parsed_function);
+        }
+        TokenKind::Keyword(Keywords::Extern) => {
+          if !self.eat_keyword(Keywords::Fn) {
+            return self.error(TokenizeError::Custom(
+              "Expected a function after extern for example: \"extern fn foo()\"",
+            ));
           }
+          let mut parsed_function = parse_function(self, false, true)?;
+          parsed_function.doc = doc;
+          parsed_function.attributes = attributes;
+          self.functions.push(parsed_function);
         }
-      } else {
-        return Ok(());
+        TokenKind::Keyword(Keywords::Struct) => {
+          let mut parsed_struct = parse_struct(self, false)?;
+          parsed_struct.doc = doc;
+          parsed_struct.attributes = attributes;
+          self.structs.push(parsed_struct);
+        }
+        TokenKind::Keyword(Keywords::Enum) => {
+          let mut parsed_enum = parse_enum(self, false)?;
+          parsed_enum.doc = doc;
+          parsed_enum.attributes = attributes;
+          self.enums.push(parsed_enum);
+        }
+        TokenKind::Keyword(Keywords::Type) => {
+          let mut parsed_type = parse_global_type(self)?;
+          parsed_type.doc = doc;
+          parsed_type.attributes = attributes;
+          self.types.push(parsed_type);
+        }
+        TokenKind::Eof if attributes.is_empty() => return Ok(()),
+        _ => return self.unexpected(&token),
       }
     }
   }
 
-  pub fn expect(&mut self, text: &str) -> Result<(), LocationError> {
-    for letter in text.chars() {
-      match self.next_char().2 {
-        Some(v) if v == letter => {}
-        Some(c) => return self.unexpected_char(c),
-        None => return self.unexpected_eof(),
-      }
-    }
-    Ok(())
+  /// Returns the `///` doc comment above the next token, newlines are skipped
+  pub fn doc_comment(&self) -> Option<String> {
+    self.docs.get(&self.skip_newlines(self.pos)).cloned()
   }
+}
 
-  /// This return the last location
-  /// return values: index, y
-  pub fn last_index(&self) -> (usize, u16) {
-    if self.index == 0 {
-      return (0, 0);
+/// Takes the doc comments out of the tokens and returns them by the position of the token on the line below them,
+/// doc comments on the lines directly above each other are joined
+fn attach_doc_comments(file: &File, lexed: Vec<Token>) -> (Vec<Token>, HashMap<usize, String>) {
+  // Returns true if there is exactly one line break between the end and start index
+  let next_line = |end: usize, start: usize| {
+    file.bytes[end..start]
+      .iter()
+      .filter(|c| **c == b'\n')
+      .count()
+      == 1
+  };
+
+  let mut tokens: Vec<Token> = vec![];
+  let mut docs: HashMap<usize, String> = HashMap::new();
+  // The lines of the doc comment we are in and the end of its last line
+  let mut doc: Option<(Vec<String>, usize)> = None;
+  for token in lexed {
+    match token.kind {
+      TokenKind::DocComment(text) => {
+        doc = Some(match doc.take() {
+          Some((mut lines, end)) if next_line(end, token.span.start) => {
+            lines.push(text);
+            (lines, token.span.end)
+          }
+          _ => (vec![text], token.span.end),
+        });
+        continue;
+      }
+      TokenKind::Newline => {
+        if let Some(Token {
+          kind: TokenKind::Newline,
+          ..
+        }) = tokens.last()
+        {
+          continue;
+        }
+      }
+      _ => {
+        if let Some((lines, end)) = doc.take() {
+          if next_line(end, token.span.start) {
+            docs.insert(tokens.len(), lines.join("\n"));
+          }
+        }
+      }
     }
-
-    (
-      self.index - 1,
-      if let Some('\n') = self.seek_next_char() {
-        self.y - 1
-      } else {
-        self.y
-      },
-    )
-  }
-
-  pub fn last_index_location(&self) -> CodeLocation {
-    let (index, y) = self.last_index();
-    CodeLocation::new(index, y)
+    tokens.push(token);
   }
-
-  /*
-      Functions written but not used so commented out
-  */
-
-  // pub fn match_name(&mut self) -> Result<(String, usize), ParsingError> {
-  //   let mut name = NameBuilder::new();
-
-  //   while let Some(c) = self.next_char() {
-  //     match c {
-  //       ' ' | '\t' | '\n' if name.len() == 0 => {} // Ignore this char
-  //       _ if legal_name_char(c) => name.push(c),
-  //       _ => break,
-  //     }
-  //   }
-
-  //   self.index -= 1;
-  //   let name_len = name.len();
-  //   let res_name = name.to_string(self)?;
-  //   Ok((res_name, name_len))
-  // }
-
-  // fn forward_until(
-  //     &mut self,
-  //     allowed_chars: impl Into<String>,
-  //     until: char,
-  // ) -> Result<(), ParsingError> {
-  //     let allowed_chars_string = allowed_chars.into();
-  //     while let Some(c) = self.next_char() {
-  //         if c == until {
-  //             return Ok(());
-  //         }
-  //         if !allowed_chars_string.contains(c) {
-  //             return self.error(ParsingErrorType::UnexpectedChar);
-  //         }
-  //     }
-  //     self.error(ParsingErrorType::UnexpectedEOF)
-  // }
+  (tokens, docs)
 }
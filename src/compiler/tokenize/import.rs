@@ -1,14 +1,14 @@
 use super::*;
 use errors::LocationError;
-use files::CodeLocation;
-use statics::{valid_name_char, NameBuilder};
+use files::Span;
+use lexer::TokenKind;
 use strings::parse_static_str;
 
 #[derive(Debug, Clone)]
 pub struct Import {
   pub name: String,
   pub path: String_,
-  pub location: CodeLocation,
+  pub location: Span,
 }
 
 impl GetName for Import {
@@ -18,59 +18,24 @@ impl GetName for Import {
 }
 
 impl GetLocation for Import {
-  fn location(&self) -> CodeLocation {
+  fn location(&self) -> Span {
     self.location.clone()
   }
 }
 
+/// Parses the imports after the import keyword, every import is a name followed by a path `foo "foo"`
 pub fn parse_import(t: &mut Tokenizer) -> Result<Vec<Import>, LocationError> {
   let mut res: Vec<Import> = vec![];
 
-  // Parse all import
-  'main: loop {
-    let index = t.index;
-    let y = t.y;
-    let restore_location = |t: &mut Tokenizer| {
-      t.index = index;
-      t.y = y;
-    };
-
-    // Parse a single import
-    let location = t.last_index_location();
-    let c_option = t.next_while(" \t\n");
-    let mut c = if let Some(c) = c_option {
-      c
-    } else {
-      break;
-    };
-
-    let mut import_name = NameBuilder::new();
-    loop {
-      match c {
-        ' ' | '\t' | '\n' => break,
-        cr if !valid_name_char(cr) => {
-          restore_location(t);
-          break 'main;
-        }
-        cr => {
-          import_name.push(cr);
-          c = t.must_next_char()?;
-        }
-      };
-    }
-
-    c = t.must_next_while(" \t\n")?;
-    if c != '"' {
-      restore_location(t);
-      break 'main;
-    }
-
+  while let (TokenKind::Identifier(_), TokenKind::String(_)) = (t.peek(), t.peek_second()) {
+    let location = t.location();
+    let name = t.expect_name()?;
     let path = parse_static_str(t)?;
 
     res.push(Import {
-      name: import_name.to_string(t)?,
+      name,
       path,
-      location,
+      location: t.span_from(&location),
     })
   }
 
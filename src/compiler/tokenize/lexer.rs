@@ -0,0 +1,233 @@
+use super::*;
+use errors::{LocationError, TokenizeError};
+use files::{File, Span};
+use statics::valid_name_char;
+use std::fmt;
+use utils::MatchString;
+
+/// All punctuation the lexer knows, longer punctuation is listed before the punctuation it starts with
+/// so the longest match wins `..=` over `..` over `.`
+static PUNCTUATION: [&str; 30] = [
+  "...", "..=", "..", "=>", "==", "!=", "<=", ">=", "&&", "||", "(", ")", "{", "}", "[", "]", ",",
+  ":", ".", "?", "#", "=", "<", ">", "+", "-", "*", "/", "%", "!",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+  pub kind: TokenKind,
+  pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+  Keyword(Keywords),
+  /// A name of a variable, function, type or field `foo`
+  Identifier(String),
+  /// A number as written in the source `10` or `1.5`, the parser converts it into a value
+  Number(String),
+  /// A static string without the quotes `"foo"`
+  String(String),
+  /// A loop label `'outer`
+  Label(String),
+  /// Operators and other symbols like `{` or `==`
+  Punctuation(&'static str),
+  /// The text of a `///` doc comment line without the slashes,
+  /// only comments that are the first thing on their line are doc comments
+  DocComment(String),
+  /// One or more newlines, statements and some signatures end at the end of a line
+  Newline,
+  Eof,
+}
+
+impl fmt::Display for TokenKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Keyword(keyword) => write!(f, "{}", keyword.get_string()),
+      Self::Identifier(name) | Self::Number(name) => write!(f, "{}", name),
+      Self::String(content) => write!(f, "\"{}\"", content),
+      Self::Label(name) => write!(f, "'{}", name),
+      Self::DocComment(text) => write!(f, "///{}", text),
+      Self::Punctuation(punctuation) => write!(f, "{}", punctuation),
+      Self::Newline => write!(f, "\\n"),
+      Self::Eof => write!(f, "EOF"),
+    }
+  }
+}
+
+/// Splits the contents of a file into tokens, comments except for doc comments and whitespace are dropped.
+/// The list always ends with an EOF token
+pub fn lex(file: &File) -> Result<Vec<Token>, LocationError> {
+  let mut lexer = Lexer {
+    file,
+    bytes: &file.bytes,
+    index: 0,
+    tokens: vec![],
+  };
+  lexer.lex()?;
+  Ok(lexer.tokens)
+}
+
+struct Lexer<'a> {
+  file: &'a File,
+  bytes: &'a [u8],
+  index: usize,
+  tokens: Vec<Token>,
+}
+
+impl<'a> Lexer<'a> {
+  fn lex(&mut self) -> Result<(), LocationError> {
+    while let Some(c) = self.peek(0) {
+      let start = self.index;
+
+      let kind = match c {
+        ' ' | '\t' => {
+          self.index += 1;
+          continue;
+        }
+        '\n' => {
+          self.index += 1;
+          if let Some(Token {
+            kind: TokenKind::Newline,
+            ..
+          }) = self.tokens.last()
+          {
+            continue;
+          }
+          TokenKind::Newline
+        }
+        '/' if self.peek(1) == Some('/') => {
+          // Single line comment, the newline is kept as it might end a statement
+          let doc = self.peek(2) == Some('/') && self.peek(3) != Some('/') && self.at_line_start();
+          while let Some(c) = self.peek(0) {
+            if c == '\n' {
+              break;
+            }
+            self.index += 1;
+          }
+          if !doc {
+            continue;
+          }
+          let text = String::from_utf8_lossy(&self.bytes[start + 3..self.index]);
+          let text = text.trim_end();
+          TokenKind::DocComment(text.strip_prefix(' ').unwrap_or(text).to_string())
+        }
+        '/' if self.peek(1) == Some('*') => {
+          // Multi-line comment
+          self.index += 2;
+          while let Some(c) = self.peek(0) {
+            self.index += 1;
+            if c == '*' && self.peek(0) == Some('/') {
+              self.index += 1;
+              break;
+            }
+          }
+          continue;
+        }
+        '"' => self.string()?,
+        '\'' => {
+          self.index += 1;
+          let name = self.word();
+          if name.is_empty() {
+            return self.error(TokenizeError::Custom(
+              "Expected a label name for example: \"'outer\"",
+            ));
+          }
+          TokenKind::Label(name)
+        }
+        '0'..='9' => {
+          let mut number = self.word();
+          // Only take the dot if a digit follows so `0..10` stays a range
+          if self.peek(0) == Some('.') && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.index += 1;
+            number.push('.');
+            number.push_str(&self.word());
+          }
+          TokenKind::Number(number)
+        }
+        c if valid_name_char(c) => {
+          let word = self.word();
+          match Keywords::from_word(&word) {
+            Some(keyword) => TokenKind::Keyword(keyword),
+            None => TokenKind::Identifier(word),
+          }
+        }
+        c => match PUNCTUATION
+          .iter()
+          .find(|punctuation| self.bytes[self.index..].starts_with(punctuation.as_bytes()))
+        {
+          Some(punctuation) => {
+            self.index += punctuation.len();
+            TokenKind::Punctuation(punctuation)
+          }
+          None => return self.error(TokenizeError::UnexpectedChar(c)),
+        },
+      };
+
+      self.tokens.push(Token {
+        kind,
+        span: Span::new(self.file.id, start, self.index),
+      });
+    }
+
+    self.tokens.push(Token {
+      kind: TokenKind::Eof,
+      span: Span::new(self.file.id, self.index, self.index),
+    });
+    Ok(())
+  }
+
+  /// Returns true if there are no tokens before the current position on its line
+  fn at_line_start(&self) -> bool {
+    matches!(
+      self.tokens.last(),
+      None
+        | Some(Token {
+          kind: TokenKind::Newline,
+          ..
+        })
+    )
+  }
+
+  fn peek(&self, offset: usize) -> Option<char> {
+    self.bytes.get(self.index + offset).map(|c| *c as char)
+  }
+
+  /// Consumes all name chars from the current position
+  fn word(&mut self) -> String {
+    let start = self.index;
+    while self.peek(0).is_some_and(valid_name_char) {
+      self.index += 1;
+    }
+    String::from_utf8_lossy(&self.bytes[start..self.index]).into_owned()
+  }
+
+  /// Parses a static string, the backslash escapes the char after it
+  fn string(&mut self) -> Result<TokenKind, LocationError> {
+    self.index += 1;
+    let mut content: Vec<u8> = vec![];
+    let mut escaped = false;
+    loop {
+      let c = match self.bytes.get(self.index) {
+        Some(c) => *c,
+        None => return self.error(TokenizeError::UnexpectedEOF),
+      };
+      self.index += 1;
+      match c {
+        b'\\' if !escaped => escaped = true,
+        b'"' if !escaped => break,
+        c => {
+          content.push(c);
+          escaped = false;
+        }
+      }
+    }
+    Ok(TokenKind::String(
+      String::from_utf8_lossy(&content).into_owned(),
+    ))
+  }
+
+  fn error<T>(&self, error: TokenizeError) -> Result<T, LocationError> {
+    let location = Span::new(self.file.id, self.index, self.index + 1);
+    self.file.error(error, location)
+  }
+}
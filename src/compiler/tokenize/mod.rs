@@ -1,30 +1,37 @@
 pub mod action;
 pub mod actions;
+pub mod attributes;
 pub mod boolean;
 pub mod function;
 pub mod globals;
 pub mod import;
+pub mod lexer;
 pub mod numbers;
+pub mod operators;
 pub mod statics;
 pub mod strings;
 pub mod types;
 pub mod utils;
 pub mod variable;
 
-pub use super::anylize::utils::{GetLocation, GetName};
+pub use super::anylize::utils::{GetAttributes, GetLocation, GetName};
 use super::errors;
 use super::files;
 pub use action::{
-  Action, ActionAssigment, ActionFor, ActionFunctionCall, ActionIf, ActionType, ActionWhile,
-  IfCheckAndBody,
+  Action, ActionCatch, ActionFor, ActionFunctionCall, ActionIf, ActionLoop, ActionMatch,
+  ActionOperation, ActionRange, ActionType, ActionUnary, ActionWhile, MatchPattern,
 };
 pub use actions::Actions;
+pub use attributes::Attribute;
 pub use boolean::Boolean;
-pub use function::Function;
+pub use function::{Function, FunctionArg};
 pub use globals::Tokenizer;
 pub use import::Import;
 pub use numbers::{Number, NumberType};
+pub use operators::{Operator, UnaryOperator};
 pub use statics::Keywords;
 pub use strings::String_;
-pub use types::{Enum, GlobalType, Struct, Type, TypeType};
-pub use variable::{VarType, Variable};
+pub use types::{
+  Enum, EnumField, GlobalType, Struct, StructField, Type, TypeId, TypeRef, TypeSymbol, TypeType,
+};
+pub use variable::{VarType, Variable, VariableTuple};
@@ -1,11 +1,9 @@
 use super::*;
 use errors::{LocationError, TokenizeError};
-use files::CodeLocation;
 
 #[derive(Debug, Clone)]
 pub struct Number {
   pub type_: NumberType,
-  pub location: CodeLocation,
 }
 
 #[derive(Debug, Clone)]
@@ -26,39 +24,34 @@ pub enum NumberTypes {
   // Float,
 }
 
-impl Into<ActionType> for Number {
-  fn into(self) -> ActionType {
-    ActionType::StaticNumber(self)
+impl From<Number> for ActionType {
+  fn from(val: Number) -> Self {
+    ActionType::StaticNumber(val)
   }
 }
 
 pub struct NumberParser<'a> {
   t: &'a mut Tokenizer,
   buff: Vec<u8>,
-  location: CodeLocation,
 }
 
 impl<'a> NumberParser<'a> {
   pub fn new_without_starting(t: &'a mut Tokenizer, buff: Vec<u8>) -> Self {
-    let location = t.last_index_location();
-    Self { t, buff, location }
+    Self { t, buff }
   }
   pub fn result(&self, type_: NumberTypes) -> Result<Number, LocationError> {
     let type_ = match type_ {
       // NumberTypes::Float => Number::Float(self.to_float()?),
       // NumberTypes::Int => Number::Int(self.to_int()?),
       NumberTypes::Auto => {
-        if self.buff.contains(&('.' as u8)) {
+        if self.buff.contains(&b'.') {
           NumberType::Float(self.to_float()?)
         } else {
           NumberType::Int(self.to_int()?)
         }
       }
     };
-    Ok(Number {
-      type_,
-      location: self.location.clone(),
-    })
+    Ok(Number { type_ })
   }
   fn to_float(&self) -> Result<f64, LocationError> {
     self.err(self.to_string()?.parse::<f64>())
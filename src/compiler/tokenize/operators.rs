@@ -0,0 +1,123 @@
+use super::*;
+use lexer::TokenKind;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operator {
+  /// `a + b`
+  Add,
+  /// `a - b`
+  Subtract,
+  /// `a * b`
+  Multiply,
+  /// `a / b`
+  Divide,
+  /// `a % b`
+  Remainder,
+  /// `a == b`
+  Equal,
+  /// `a != b`
+  NotEqual,
+  /// `a < b`
+  Less,
+  /// `a <= b`
+  LessOrEqual,
+  /// `a > b`
+  Greater,
+  /// `a >= b`
+  GreaterOrEqual,
+  /// `a && b`
+  And,
+  /// `a || b`
+  Or,
+}
+
+impl Operator {
+  /// Returns how strong the operator binds, operators with a higher precedence are executed first
+  pub fn precedence(&self) -> u8 {
+    match self {
+      Self::Or => 1,
+      Self::And => 2,
+      Self::Equal
+      | Self::NotEqual
+      | Self::Less
+      | Self::LessOrEqual
+      | Self::Greater
+      | Self::GreaterOrEqual => 3,
+      Self::Add | Self::Subtract => 4,
+      Self::Multiply | Self::Divide | Self::Remainder => 5,
+    }
+  }
+  /// Returns the operator written as s `+`
+  pub fn from_str(s: &str) -> Option<Self> {
+    Some(match s {
+      "+" => Self::Add,
+      "-" => Self::Subtract,
+      "*" => Self::Multiply,
+      "/" => Self::Divide,
+      "%" => Self::Remainder,
+      "==" => Self::Equal,
+      "!=" => Self::NotEqual,
+      "<" => Self::Less,
+      "<=" => Self::LessOrEqual,
+      ">" => Self::Greater,
+      ">=" => Self::GreaterOrEqual,
+      "&&" => Self::And,
+      "||" => Self::Or,
+      _ => return None,
+    })
+  }
+  /// Returns true for operators that calculate a new value from their operands `a + b`,
+  /// all other operators result in a boolean
+  pub fn is_arithmetic(&self) -> bool {
+    matches!(
+      self,
+      Self::Add | Self::Subtract | Self::Multiply | Self::Divide | Self::Remainder
+    )
+  }
+  pub fn to_str(&self) -> &'static str {
+    match self {
+      Self::Add => "+",
+      Self::Subtract => "-",
+      Self::Multiply => "*",
+      Self::Divide => "/",
+      Self::Remainder => "%",
+      Self::Equal => "==",
+      Self::NotEqual => "!=",
+      Self::Less => "<",
+      Self::LessOrEqual => "<=",
+      Self::Greater => ">",
+      Self::GreaterOrEqual => ">=",
+      Self::And => "&&",
+      Self::Or => "||",
+    }
+  }
+}
+
+/// An operator in front of a single value `-a`
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnaryOperator {
+  /// `-a`
+  Negate,
+  /// `!a`
+  Not,
+}
+
+impl UnaryOperator {
+  pub fn to_str(&self) -> &'static str {
+    match self {
+      Self::Negate => "-",
+      Self::Not => "!",
+    }
+  }
+}
+
+/// Tries to parse an operator on the current line,
+/// if there is no operator the tokenizer is left untouched and None is returned
+pub fn parse_operator(t: &mut Tokenizer) -> Option<Operator> {
+  let operator = match t.peek_same_line() {
+    TokenKind::Punctuation(punctuation) => Operator::from_str(punctuation)?,
+    _ => return None,
+  };
+  t.next();
+  Some(operator)
+}
@@ -1,73 +1,14 @@
 use super::*;
-use errors::{LocationError, TokenizeError};
-use numbers::NumberParser;
 use utils::MatchString;
 
-pub static VALID_NAME_CHARS: &'static str =
+pub static VALID_NAME_CHARS: &str =
   "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890_";
 
 pub fn valid_name_char(c: char) -> bool {
   VALID_NAME_CHARS.contains(c)
 }
 
-#[derive(Debug)]
-pub struct NameBuilder(Vec<u8>);
-
-impl NameBuilder {
-  pub fn new() -> Self {
-    Self(vec![])
-  }
-  /// new_with_char creates a new name builder with a start char.
-  /// you will need to check first_char if it's a valid name char
-  pub fn new_with_char(first_char: char) -> Self {
-    Self(vec![first_char as u8])
-  }
-  pub fn is_boolean(&self) -> Option<Boolean> {
-    if self.0.clone() == b"true" {
-      Some(Boolean(true))
-    } else if self.0.clone() == b"false" {
-      Some(Boolean(false))
-    } else {
-      None
-    }
-  }
-  pub fn is_number<'a, 'b>(&self, t: &'a mut Tokenizer) -> Option<NumberParser<'a>> {
-    for letter in &self.0 {
-      match *letter as char {
-        '.' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' | '0' => {}
-        _ => return None,
-      }
-    }
-    let parser = NumberParser::new_without_starting(t, self.0.clone());
-    Some(parser)
-  }
-  pub fn to_string<'a>(&self, t: &'a Tokenizer) -> Result<String, LocationError> {
-    if self.len() == 0 {
-      return Ok(String::new());
-    }
-    if let Some(c) = self.0.get(0) {
-      match *c as char {
-        '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' | '0' => {
-          return t.error(TokenizeError::Custom("name cannot start with a number"))
-        }
-        _ => {}
-      }
-    }
-
-    match String::from_utf8(self.0.clone()) {
-      Ok(parsed_string) => Ok(parsed_string),
-      Err(_) => t.error(TokenizeError::Custom("Invalid utf8 string")),
-    }
-  }
-  pub fn len(&self) -> usize {
-    self.0.len()
-  }
-  pub fn push(&mut self, value: char) {
-    self.0.push(value as u8);
-  }
-}
-
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Keywords {
   Fn,
   If,
@@ -75,29 +16,68 @@ pub enum Keywords {
   Pub,
   For,
   Loop,
+  Step,
   Else,
   Enum,
   Type,
   True,
+  Match,
+  Throw,
+  Defer,
+  Catch,
   False,
   Const,
   While,
   Break,
   Return,
+  Throws,
   Struct,
   Import,
+  Extern,
   Continue,
 }
 
 impl Keywords {
   pub fn is_keyword(word: &str) -> bool {
     let lower_word = word.to_lowercase();
+    // step is not listed here because it's only a keyword inside ranges
     let words = [
       "fn", "let", "for", "loop", "type", "enum", "const", "while", "break", "struct", "return",
-      "continue", "if", "else", "true", "false", "import", "pub",
+      "continue", "if", "else", "true", "false", "import", "pub", "match", "throw", "throws",
+      "catch", "extern", "defer",
     ];
     words.contains(&lower_word.as_str())
   }
+  /// Returns the keyword written as word, step is only a keyword inside ranges so it's returned as a name
+  pub fn from_word(word: &str) -> Option<Self> {
+    Some(match word {
+      "fn" => Self::Fn,
+      "if" => Self::If,
+      "let" => Self::Let,
+      "pub" => Self::Pub,
+      "for" => Self::For,
+      "loop" => Self::Loop,
+      "else" => Self::Else,
+      "enum" => Self::Enum,
+      "type" => Self::Type,
+      "true" => Self::True,
+      "match" => Self::Match,
+      "throw" => Self::Throw,
+      "defer" => Self::Defer,
+      "catch" => Self::Catch,
+      "false" => Self::False,
+      "const" => Self::Const,
+      "while" => Self::While,
+      "break" => Self::Break,
+      "return" => Self::Return,
+      "throws" => Self::Throws,
+      "struct" => Self::Struct,
+      "import" => Self::Import,
+      "extern" => Self::Extern,
+      "continue" => Self::Continue,
+      _ => return None,
+    })
+  }
 }
 
 impl MatchString for Keywords {
@@ -109,27 +89,31 @@ impl MatchString for Keywords {
       Self::For => "for",
       Self::Pub => "pub",
       Self::Loop => "loop",
+      Self::Step => "step",
       Self::Else => "else",
       Self::Type => "type",
       Self::Enum => "enum",
       Self::True => "true",
+      Self::Match => "match",
+      Self::Throw => "throw",
+      Self::Defer => "defer",
+      Self::Catch => "catch",
       Self::False => "false",
       Self::Const => "const",
       Self::While => "while",
       Self::Break => "break",
       Self::Struct => "struct",
       Self::Import => "import",
+      Self::Extern => "extern",
       Self::Return => "return",
+      Self::Throws => "throws",
       Self::Continue => "continue",
     }
   }
-  fn after(&self) -> Option<&'static str> {
-    Some(" \t\n")
-  }
 }
 
 impl<'a> From<&'a Keywords> for &'static str {
   fn from(keywords: &'a Keywords) -> &'static str {
-    keywords.into()
+    keywords.get_string()
   }
 }
@@ -1,40 +1,23 @@
 use super::*;
 use errors::LocationError;
-use files::CodeLocation;
+use lexer::TokenKind;
 
 #[derive(Debug, Clone)]
 pub struct String_ {
-  pub location: CodeLocation,
   pub content: String,
 }
 
-impl Into<ActionType> for String_ {
-  fn into(self) -> ActionType {
-    ActionType::StaticString(self)
+impl From<String_> for ActionType {
+  fn from(val: String_) -> Self {
+    ActionType::StaticString(val)
   }
 }
 
+/// Parses a static string `"foo"`
 pub fn parse_static_str(t: &mut Tokenizer) -> Result<String_, LocationError> {
-  let mut res = String_ {
-    location: t.last_index_location(),
-    content: String::new(),
-  };
-  let mut string_content: Vec<u8> = vec![];
-
-  let mut escaped = false;
-  loop {
-    match t.must_next_char()? {
-      '\\' if !escaped => escaped = true,
-      '"' if !escaped => {
-        res.content = String::from_utf8(string_content).unwrap();
-        return Ok(res);
-      }
-      c => {
-        string_content.push(c as u8);
-        if escaped {
-          escaped = false;
-        }
-      }
-    }
+  let token = t.next();
+  match token.kind {
+    TokenKind::String(content) => Ok(String_ { content }),
+    _ => t.unexpected(&token),
   }
 }
@@ -1,21 +1,42 @@
 use super::*;
 use action::{ActionToExpect, ParseAction};
 use errors::{LocationError, TokenizeError};
-use files::CodeLocation;
-use statics::{valid_name_char, NameBuilder};
-use utils::MatchString;
+use files::Span;
+use lexer::TokenKind;
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct Type {
-  pub location: CodeLocation,
+  pub location: Span,
   pub type_: TypeType,
 }
 
-impl Type {
-  fn here(t: &mut Tokenizer, type_: TypeType) -> Self {
-    Self {
-      location: t.last_index_location(),
-      type_,
+impl fmt::Display for Type {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.type_ {
+      TypeType::Int => write!(f, "int"),
+      TypeType::I8 => write!(f, "i8"),
+      TypeType::I16 => write!(f, "i16"),
+      TypeType::I32 => write!(f, "i32"),
+      TypeType::I64 => write!(f, "i64"),
+      TypeType::UInt => write!(f, "uint"),
+      TypeType::U8 => write!(f, "u8"),
+      TypeType::U16 => write!(f, "u16"),
+      TypeType::U32 => write!(f, "u32"),
+      TypeType::U64 => write!(f, "u64"),
+      TypeType::F32 => write!(f, "f32"),
+      TypeType::F64 => write!(f, "f64"),
+      TypeType::String => write!(f, "string"),
+      TypeType::Char => write!(f, "char"),
+      TypeType::Bool => write!(f, "bool"),
+      TypeType::Struct(struct_) => write!(f, "struct {}", struct_.name.as_deref().unwrap_or("{}")),
+      TypeType::Enum(enum_) => write!(f, "enum {}", enum_.name.as_deref().unwrap_or("{}")),
+      TypeType::Array(type_) => write!(f, "[]{}", type_),
+      TypeType::Tuple(types) => {
+        let types: Vec<String> = types.iter().map(|type_| type_.to_string()).collect();
+        write!(f, "({})", types.join(", "))
+      }
+      TypeType::TypeRef(type_ref) => write!(f, "{}", type_ref.name),
     }
   }
 }
@@ -42,158 +63,163 @@ pub enum TypeType {
   U32,
   /// 64 bit unsigned intager
   U64,
+  /// 32 bit floating point number
+  F32,
+  /// 64 bit floating point number
+  F64,
 
   String,
   Char,
+  /// true or false
+  Bool,
   Struct(Struct),
   Enum(Enum),
   Array(Box<Type>),
+  /// A fixed list of types, mostly used for returning multiple values `(int, string)`
+  Tuple(Vec<Type>),
 
   /// This references to another type
-  TypeRef(String),
+  TypeRef(TypeRef),
 }
 
-pub enum DetectType {
-  Int,
-  I8,
-  I16,
-  I32,
-  I64,
-  UInt,
-  U8,
-  U16,
-  U32,
-  U64,
-  String,
-  Char,
-  Struct,
-  Enum,
-  Array,
+/// A reference to a type by its name `Foo` or a type of an imported module `utils.Foo`
+#[derive(Debug, Clone)]
+pub struct TypeRef {
+  pub name: String,
+  /// What the name points to, this is filled in by the anylize stage
+  pub symbol: Option<TypeSymbol>,
+  /// The item the name points to, filled in together with the symbol
+  pub id: Option<TypeId>,
 }
 
-impl DetectType {
-  fn to_type(&self) -> Option<TypeType> {
-    Some(match self {
-      Self::Int => TypeType::Int,
-      Self::I8 => TypeType::I8,
-      Self::I16 => TypeType::I16,
-      Self::I32 => TypeType::I32,
-      Self::I64 => TypeType::I64,
-      Self::UInt => TypeType::UInt,
-      Self::U8 => TypeType::U8,
-      Self::U16 => TypeType::U16,
-      Self::U32 => TypeType::U32,
-      Self::U64 => TypeType::U64,
-      Self::String => TypeType::String,
-      Self::Char => TypeType::Char,
-      Self::Array | Self::Struct | Self::Enum => return None,
-    })
+impl TypeRef {
+  pub fn new(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      symbol: None,
+      id: None,
+    }
   }
 }
 
-impl MatchString for DetectType {
-  fn get_string(&self) -> &'static str {
-    match self {
-      Self::Int => "int",
-      Self::I8 => "i8",
-      Self::I16 => "i16",
-      Self::I32 => "i32",
-      Self::I64 => "i64",
-      Self::UInt => "uint",
-      Self::U8 => "u8",
-      Self::U16 => "u16",
-      Self::U32 => "u32",
-      Self::U64 => "u64",
-      Self::String => "string",
-      Self::Char => "char",
-      Self::Array => "[]",
-      Self::Struct => "struct",
-      Self::Enum => "enum",
-    }
-  }
+/// Identifies a struct, enum or global type, references to the same type have the same id
+/// even if they use another name for it like `Foo` inside its module and `utils.Foo` outside of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeId {
+  /// The id of the file the type is declared in
+  pub file_id: usize,
+  /// The name of the type inside that file
+  pub name: String,
 }
 
-pub fn parse_type(t: &mut Tokenizer, go_back_one: bool) -> Result<Type, LocationError> {
-  if go_back_one {
-    t.index -= 1;
-  }
+/// The kind of item a type reference points to
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeSymbol {
+  Struct,
+  Enum,
+  /// A global type, both aliases and newtypes
+  Type,
+  /// A type of an imported module, this contains the alias of the import
+  Import(String),
+}
 
-  t.must_next_while_empty()?;
-  t.index -= 1;
-
-  match t.try_match(vec![
-    &DetectType::Int,
-    &DetectType::I8,
-    &DetectType::I16,
-    &DetectType::I32,
-    &DetectType::I64,
-    &DetectType::UInt,
-    &DetectType::U8,
-    &DetectType::U16,
-    &DetectType::U32,
-    &DetectType::U64,
-    &DetectType::String,
-    &DetectType::Char,
-    &DetectType::Struct,
-    &DetectType::Enum,
-    &DetectType::Array,
-  ]) {
-    Some(&DetectType::Array) => {
-      let res = parse_type(t, false)?;
-      return Ok(Type::here(t, TypeType::Array(Box::new(res))));
-    }
-    Some(matched_type) => {
-      let mut return_value: Option<TypeType> = None;
-
-      let add_to_substract = if let Some(c) = t.next_char().2 {
-        if let &DetectType::Struct = matched_type {
-          if c == '{' || c == ' ' || c == '\n' {
-            let res = parse_struct(t, true, c == '{')?;
-            return_value = Some(TypeType::Struct(res));
-          }
-        } else if let &DetectType::Enum = matched_type {
-          if c == '{' || c == ' ' || c == '\n' {
-            let res = parse_enum(t, true, c == '{')?;
-            return_value = Some(TypeType::Enum(res));
-          }
-        } else if !valid_name_char(c) {
-          if let Some(v) = matched_type.to_type() {
-            t.index -= 1;
-            return_value = Some(v);
-          }
-        }
-
-        if let Some(type_) = return_value {
-          return Ok(Type::here(t, type_));
-        }
-
-        1
-      } else {
-        0
-      };
-      t.index -= matched_type.get_string().len() + add_to_substract;
+/// Returns the built-in type written as name `int`
+fn builtin_type(name: &str) -> Option<TypeType> {
+  Some(match name {
+    "int" => TypeType::Int,
+    "i8" => TypeType::I8,
+    "i16" => TypeType::I16,
+    "i32" => TypeType::I32,
+    "i64" => TypeType::I64,
+    "uint" => TypeType::UInt,
+    "u8" => TypeType::U8,
+    "u16" => TypeType::U16,
+    "u32" => TypeType::U32,
+    "u64" => TypeType::U64,
+    "f32" => TypeType::F32,
+    "f64" => TypeType::F64,
+    "string" => TypeType::String,
+    "char" => TypeType::Char,
+    "bool" => TypeType::Bool,
+    _ => return None,
+  })
+}
+
+pub fn parse_type(t: &mut Tokenizer) -> Result<Type, LocationError> {
+  t.enter()?;
+  let res = parse_type_inner(t);
+  t.leave();
+  res
+}
+
+fn parse_type_inner(t: &mut Tokenizer) -> Result<Type, LocationError> {
+  let token = t.next();
+  let location = token.span.clone();
+  let type_ = match token.kind {
+    TokenKind::Punctuation("(") => return parse_tuple_type(t),
+    TokenKind::Punctuation("[") => {
+      t.expect("]")?;
+      TypeType::Array(Box::new(parse_type(t)?))
     }
-    _ => {}
+    TokenKind::Keyword(Keywords::Struct) => TypeType::Struct(parse_struct(t, true)?),
+    TokenKind::Keyword(Keywords::Enum) => TypeType::Enum(parse_enum(t, true)?),
+    TokenKind::Identifier(name) => match builtin_type(&name) {
+      Some(type_) => type_,
+      // A type of an imported module `utils.Foo`
+      None if t.eat_same_line(".") => {
+        TypeType::TypeRef(TypeRef::new(format!("{}.{}", name, t.expect_name()?)))
+      }
+      None => TypeType::TypeRef(TypeRef::new(name)),
+    },
+    _ => return t.unexpected(&token),
   };
 
-  let mut type_name = NameBuilder::new();
+  Ok(Type {
+    location: t.span_from(&location),
+    type_,
+  })
+}
+
+/// Parses a tuple type `(int, string)`, this expects the opening ( to be already parsed
+fn parse_tuple_type(t: &mut Tokenizer) -> Result<Type, LocationError> {
+  let location = t.last_location();
+  let mut types: Vec<Type> = vec![];
+
   loop {
-    match t.must_next_char()? {
-      c if valid_name_char(c) => type_name.push(c),
-      _ => {
-        t.index -= 1;
-        let type_string = type_name.to_string(t)?;
-        return Ok(Type::here(t, TypeType::TypeRef(type_string)));
-      }
+    if t.eat(")") {
+      break;
+    }
+    types.push(parse_type(t)?);
+
+    if !t.eat(",") {
+      t.expect(")")?;
+      break;
     }
   }
+
+  if types.len() < 2 {
+    return t.error(TokenizeError::Custom(
+      "A tuple requires at least 2 types for example: \"(int, string)\"",
+    ));
+  }
+
+  Ok(Type {
+    location: t.span_from(&location),
+    type_: TypeType::Tuple(types),
+  })
 }
 
 #[derive(Debug, Clone)]
 pub struct GlobalType {
   pub name: String,
   pub type_: Type,
-  pub location: CodeLocation,
+  /// True for transparent aliases `type A = B`, false for distinct newtypes `type A B`
+  pub alias: bool,
+  /// The attributes above the type `#[target(go)]`
+  pub attributes: Vec<Attribute>,
+  /// The `///` doc comment above the type
+  pub doc: Option<String>,
+  pub location: Span,
 }
 
 impl GetName for GlobalType {
@@ -202,48 +228,32 @@ impl GetName for GlobalType {
   }
 }
 
+impl GetAttributes for GlobalType {
+  fn attributes(&self) -> &[Attribute] {
+    &self.attributes
+  }
+}
+
 impl GetLocation for GlobalType {
-  fn location(&self) -> CodeLocation {
+  fn location(&self) -> Span {
     self.location.clone()
   }
 }
 
+/// Parses a global type `type foo = int` or `type foo int`, this expects the type keyword to be already parsed
 pub fn parse_global_type(t: &mut Tokenizer) -> Result<GlobalType, LocationError> {
-  let location = t.last_index_location();
-
-  // Parse the global type name
-  let first_name_char = match t.must_next_while_empty()? {
-    '{' => {
-      return t.error(TokenizeError::Custom(
-        "Struct requires name for example: \"struct foo {}\"",
-      ))
-    }
-    c if valid_name_char(c) => c,
-    c => return t.unexpected_char(c),
-  };
-  let mut struct_name = NameBuilder::new_with_char(first_name_char);
-  loop {
-    let c = t.must_next_char()?;
-    match c {
-      ' ' | '\t' | '\n' => {
-        if let Some('=') = t.next_while(" \t") {
-          break;
-        }
-        return t.unexpected_char(c);
-      }
-      '=' => break,
-      _ if valid_name_char(c) => struct_name.push(c),
-      _ => return t.unexpected_char(c),
-    }
-  }
-
-  let name = struct_name.to_string(t)?;
-  let type_ = parse_type(t, false)?;
+  let location = t.last_location();
+  let name = t.expect_name()?;
+  let alias = t.eat("=");
+  let type_ = parse_type(t)?;
 
   Ok(GlobalType {
-    location,
+    location: t.span_from(&location),
     name,
     type_,
+    alias,
+    attributes: vec![],
+    doc: None,
   })
 }
 
@@ -251,7 +261,11 @@ pub fn parse_global_type(t: &mut Tokenizer) -> Result<GlobalType, LocationError>
 pub struct Enum {
   pub name: Option<String>,
   pub fields: Vec<EnumField>,
-  pub location: CodeLocation,
+  /// The `///` doc comment above the enum
+  pub doc: Option<String>,
+  /// The attributes above the enum `#[target(go)]`
+  pub attributes: Vec<Attribute>,
+  pub location: Span,
 }
 
 impl GetName for Enum {
@@ -260,120 +274,119 @@ impl GetName for Enum {
   }
 }
 
+impl GetAttributes for Enum {
+  fn attributes(&self) -> &[Attribute] {
+    &self.attributes
+  }
+}
+
 impl GetLocation for Enum {
-  fn location(&self) -> CodeLocation {
+  fn location(&self) -> Span {
     self.location.clone()
   }
 }
 
+impl Enum {
+  /// Returns the field with name
+  pub fn field(&self, name: &str) -> Option<&EnumField> {
+    self.fields.iter().find(|field| field.name == name)
+  }
+  /// Returns true if one of the enum fields contains data
+  pub fn has_payload(&self) -> bool {
+    self.fields.iter().any(|field| !field.payload.is_empty())
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct EnumField {
   pub name: String,
   pub value: Option<Action>,
-  pub location: CodeLocation,
+  /// The data this field carries `circle(radius f64)`
+  pub payload: Vec<StructField>,
+  /// The `///` doc comment above the field
+  pub doc: Option<String>,
+  pub location: Span,
 }
 
-pub fn parse_enum(t: &mut Tokenizer, inline: bool, back_one: bool) -> Result<Enum, LocationError> {
-  if back_one {
-    t.index -= 1;
+/// Parses the data of an enum field, this expects the opening ( to be already parsed
+fn parse_enum_payload(t: &mut Tokenizer) -> Result<Vec<StructField>, LocationError> {
+  let mut res: Vec<StructField> = vec![];
+
+  loop {
+    if t.eat(")") {
+      break;
+    }
+
+    let location = t.location();
+    let name = t.expect_name()?;
+    let type_ = parse_type(t)?;
+    res.push(StructField {
+      name,
+      type_,
+      doc: None,
+      location: t.span_from(&location),
+    });
+
+    if !t.eat(",") {
+      t.expect(")")?;
+      break;
+    }
+  }
+
+  if res.is_empty() {
+    return t.error(TokenizeError::Custom(
+      "Enum field data requires at least 1 value for example: \"circle(radius f64)\"",
+    ));
   }
 
+  Ok(res)
+}
+
+/// Parses an enum, this expects the enum keyword to be already parsed
+pub fn parse_enum(t: &mut Tokenizer, inline: bool) -> Result<Enum, LocationError> {
   let mut res = Enum {
     name: None,
     fields: vec![],
-    location: t.last_index_location(),
+    doc: None,
+    attributes: vec![],
+    location: t.last_location(),
   };
 
-  if inline {
-    // Find the enum opening
-    match t.must_next_while(" \t")? {
-      '{' => {}
-      c => return t.unexpected_char(c),
-    }
-  } else {
-    // Parse the enum name
-    let first_name_char = match t.must_next_while_empty()? {
-      '{' => {
-        return t.error(TokenizeError::Custom(
-          "Struct requires name for example: \"struct foo {}\"",
-        ))
-      }
-      c if !valid_name_char(c) => return t.unexpected_char(c),
-      c => c,
-    };
-    let mut struct_name = NameBuilder::new_with_char(first_name_char);
-    loop {
-      let c = t.must_next_char()?;
-      match c {
-        ' ' | '\t' | '\n' => {
-          if let Some('{') = t.next_while(" \t") {
-            break;
-          }
-          return t.unexpected_char(c);
-        }
-        '{' => break,
-        _ if valid_name_char(c) => struct_name.push(c),
-        _ => return t.unexpected_char(c),
-      }
+  // Parse the enum name, inline enums don't have a name
+  if !inline {
+    if t.peek_is("{") {
+      return t.error(TokenizeError::Custom(
+        "Enum requires name for example: \"enum foo {}\"",
+      ));
     }
-
-    res.name = Some(struct_name.to_string(t)?);
+    res.name = Some(t.expect_name()?);
   }
+  t.expect("{")?;
 
   // Parse the enum fields
-  loop {
-    // Parse field name
-    let first_name_char = match t.must_next_while_empty()? {
-      '}' => break, // end of enum
-      c if !valid_name_char(c) => return t.unexpected_char(c),
-      c => c,
-    };
-
-    let location = t.last_index_location();
-
-    let mut field_name_builder = NameBuilder::new_with_char(first_name_char);
-    while let Some(c) = t.next_char().2 {
-      match c {
-        _ if valid_name_char(c) => field_name_builder.push(c),
-        ' ' | '\t' => break,
-        '\n' => {
-          t.index -= 1;
-          break;
-        }
-        _ => return t.unexpected_char(c),
-      }
-    }
+  while !t.eat("}") {
+    let location = t.location();
+    let doc = t.doc_comment();
+    let name = t.expect_name()?;
     let mut to_add = EnumField {
-      name: field_name_builder.to_string(t)?,
+      name,
       value: None,
+      payload: vec![],
+      doc,
       location,
     };
 
-    // Parse the = symbol
-    match t.must_next_while(" \t")? {
-      '=' => {
-        let action = ParseAction::start(t, false, ActionToExpect::Assignment(","))?;
-        match t.must_next_while(" \t")? {
-          '}' => {
-            res.fields.push(to_add);
-            break;
-          }
-          '\n' => {}
-          c => return t.unexpected_char(c),
-        }
-        to_add.value = Some(action);
-      }
-      '}' => {
-        res.fields.push(to_add);
-        break;
-      }
-      '\n' => {}
-      c => return t.unexpected_char(c),
-    };
+    if t.eat_same_line("(") {
+      to_add.payload = parse_enum_payload(t)?;
+    } else if t.eat_same_line("=") {
+      to_add.value = Some(ParseAction::start(t, ActionToExpect::Assignment)?);
+    }
 
+    to_add.location = t.span_from(&to_add.location);
     res.fields.push(to_add);
   }
 
+  res.location = t.span_from(&res.location);
   Ok(res)
 }
 
@@ -383,15 +396,21 @@ pub struct Struct {
   pub name: Option<String>,
   /// The struct fields
   pub fields: Vec<StructField>,
+  /// The `///` doc comment above the struct
+  pub doc: Option<String>,
+  /// The attributes above the struct `#[target(go)]`
+  pub attributes: Vec<Attribute>,
   /// The code location of the struct
-  pub location: CodeLocation,
+  pub location: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct StructField {
   pub name: String,
   pub type_: Type,
-  pub location: CodeLocation,
+  /// The `///` doc comment above the field
+  pub doc: Option<String>,
+  pub location: Span,
 }
 
 impl GetName for Struct {
@@ -400,93 +419,54 @@ impl GetName for Struct {
   }
 }
 
-impl GetLocation for Struct {
-  fn location(&self) -> CodeLocation {
-    self.location.clone()
+impl GetAttributes for Struct {
+  fn attributes(&self) -> &[Attribute] {
+    &self.attributes
   }
 }
 
-pub fn parse_struct(
-  t: &mut Tokenizer,
-  inline: bool,
-  back_one: bool,
-) -> Result<Struct, LocationError> {
-  if back_one {
-    t.index -= 1;
+impl GetLocation for Struct {
+  fn location(&self) -> Span {
+    self.location.clone()
   }
+}
 
+/// Parses a struct, this expects the struct keyword to be already parsed
+pub fn parse_struct(t: &mut Tokenizer, inline: bool) -> Result<Struct, LocationError> {
   let mut res = Struct {
     name: None,
     fields: vec![],
-    location: t.last_index_location(),
+    doc: None,
+    attributes: vec![],
+    location: t.last_location(),
   };
 
-  if inline {
-    // Find the struct opening
-    match t.must_next_while(" \t")? {
-      '{' => {}
-      c => return t.unexpected_char(c),
-    }
-  } else {
-    // Parse the struct name
-    let first_name_char = match t.must_next_while_empty()? {
-      '{' => {
-        return t.error(TokenizeError::Custom(
-          "Struct requires name for example: \"struct foo {}\"",
-        ))
-      }
-      c if valid_name_char(c) => c,
-      c => return t.unexpected_char(c),
-    };
-    let mut struct_name = NameBuilder::new_with_char(first_name_char);
-    while let Some(c) = t.next_char().2 {
-      match c {
-        ' ' | '\t' | '\n' => {
-          if let Some('{') = t.next_while(" \t") {
-            break;
-          }
-          return t.unexpected_char(c);
-        }
-        '{' => break,
-        _ if valid_name_char(c) => struct_name.push(c),
-        _ => return t.unexpected_char(c),
-      }
+  // Parse the struct name, inline structs don't have a name
+  if !inline {
+    if t.peek_is("{") {
+      return t.error(TokenizeError::Custom(
+        "Struct requires name for example: \"struct foo {}\"",
+      ));
     }
-
-    res.name = Some(struct_name.to_string(t)?);
+    res.name = Some(t.expect_name()?);
   }
+  t.expect("{")?;
 
   // Parse struct fields
-  loop {
-    // Parse field name
-    let first_name_char = match t.must_next_while_empty()? {
-      '}' => break, // end of struct
-      c if !valid_name_char(c) => return t.unexpected_char(c),
-      c => c,
-    };
-
-    let location = t.last_index_location();
-
-    let mut field_name_builder = NameBuilder::new_with_char(first_name_char);
-    while let Some(c) = t.next_char().2 {
-      match c {
-        _ if valid_name_char(c) => field_name_builder.push(c),
-        ' ' | '\t' => break,
-        _ => return t.unexpected_char(c),
-      }
-    }
-    let field_name = field_name_builder.to_string(t)?;
-
-    // Parse field type
-    t.must_next_while(" \t")?;
-    let parsed_type = parse_type(t, true)?;
+  while !t.eat("}") {
+    let location = t.location();
+    let doc = t.doc_comment();
+    let name = t.expect_name()?;
+    let type_ = parse_type(t)?;
 
     res.fields.push(StructField {
-      name: field_name,
-      type_: parsed_type,
-      location,
+      name,
+      type_,
+      doc,
+      location: t.span_from(&location),
     });
   }
 
+  res.location = t.span_from(&res.location);
   Ok(res)
 }
@@ -1,8 +1,7 @@
 use super::*;
 use action::{ActionToExpect, ParseAction};
-use errors::LocationError;
-use files::CodeLocation;
-use statics::{valid_name_char, NameBuilder};
+use errors::{LocationError, TokenizeError};
+use files::Span;
 use types::parse_type;
 
 #[derive(Debug, Clone)]
@@ -17,7 +16,11 @@ pub struct Variable {
   pub data_type: Option<Type>,
   pub name: String,
   pub action: Box<Action>,
-  pub location: CodeLocation,
+  /// The `///` doc comment above a global constant
+  pub doc: Option<String>,
+  /// The attributes above a global constant `#[target(go)]`
+  pub attributes: Vec<Attribute>,
+  pub location: Span,
 }
 
 impl GetName for Variable {
@@ -26,78 +29,102 @@ impl GetName for Variable {
   }
 }
 
+impl GetAttributes for Variable {
+  fn attributes(&self) -> &[Attribute] {
+    &self.attributes
+  }
+}
+
 impl GetLocation for Variable {
-  fn location(&self) -> CodeLocation {
+  fn location(&self) -> Span {
     self.location.clone()
   }
 }
 
-impl Into<ActionType> for Variable {
-  fn into(self) -> ActionType {
-    ActionType::Variable(self)
+impl From<Variable> for ActionType {
+  fn from(val: Variable) -> Self {
+    ActionType::Variable(val)
   }
 }
 
-pub fn parse_var(
-  t: &mut Tokenizer,
-  var_type_option: Option<VarType>,
-) -> Result<Variable, LocationError> {
-  let location = t.last_index_location();
-  let mut name = NameBuilder::new();
-  let mut data_type: Option<Type> = None;
-
-  let var_type = if let Some(type_) = var_type_option {
-    type_
-  } else {
-    let to_match = vec![&Keywords::Const, &Keywords::Let];
-    let match_result = t.try_match(to_match);
-    if let None = match_result {
-      return t.unexpected_char(t.last_char());
-    }
+/// A variable that destructures a tuple `let (foo, bar) = baz()`
+#[derive(Debug, Clone)]
+pub struct VariableTuple {
+  pub var_type: VarType,
+  pub data_type: Option<Type>,
+  pub names: Vec<String>,
+  pub action: Box<Action>,
+  pub location: Span,
+}
 
-    if let Keywords::Const = match_result.unwrap() {
-      VarType::Const
-    } else {
-      VarType::Let
-    }
-  };
+impl From<VariableTuple> for ActionType {
+  fn from(val: VariableTuple) -> Self {
+    ActionType::VariableTuple(val)
+  }
+}
+
+/// Parses a variable, this expects the const or let keyword to be already parsed
+pub fn parse_var(t: &mut Tokenizer, var_type: VarType) -> Result<Variable, LocationError> {
+  let location = t.last_location();
+  let name = t.expect_name()?;
+  let (data_type, action) = parse_var_type_and_action(t)?;
+
+  Ok(Variable {
+    location: t.span_from(&location),
+    var_type,
+    data_type,
+    name,
+    action: Box::new(action),
+    doc: None,
+    attributes: vec![],
+  })
+}
 
-  // Parse name
-  let mut next_char = t.must_next_while_empty()?;
+pub fn parse_var_tuple(
+  t: &mut Tokenizer,
+  var_type: VarType,
+) -> Result<VariableTuple, LocationError> {
+  let location = t.last_location();
+  t.expect("(")?;
+
+  // Parse the names
+  let mut names: Vec<String> = vec![];
   loop {
-    match next_char {
-      c if valid_name_char(c) => name.push(c),
-      ' ' | '\t' | '\n' => break,
-      ':' | '=' => {
-        t.index -= 1;
-        break;
-      }
-      c => return t.unexpected_char(c),
+    names.push(t.expect_name()?);
+    if !t.eat(",") {
+      t.expect(")")?;
+      break;
     }
-    next_char = t.must_next_char()?;
-  }
-
-  // Parse the variable type if set
-  next_char = t.must_next_while_empty()?;
-  if next_char == ':' {
-    data_type = Some(parse_type(t, true)?);
-    next_char = t.must_next_while_empty()?;
   }
 
-  // Check for the = symbol
-  match next_char {
-    '=' => {}
-    c => return t.unexpected_char(c),
+  if names.len() < 2 {
+    return t.error(TokenizeError::Custom(
+      "Destructuring requires at least 2 names for example: \"let (a, b) = foo()\"",
+    ));
   }
 
-  // Parse the action after the action after the =
-  let action = ParseAction::start(t, false, ActionToExpect::Assignment(""))?;
+  let (data_type, action) = parse_var_type_and_action(t)?;
 
-  Ok(Variable {
-    location,
+  Ok(VariableTuple {
+    location: t.span_from(&location),
     var_type,
     data_type,
-    name: name.to_string(t)?,
+    names,
     action: Box::new(action),
   })
 }
+
+/// Parses the optional type and the value of a variable `: int = foo`
+fn parse_var_type_and_action(t: &mut Tokenizer) -> Result<(Option<Type>, Action), LocationError> {
+  // Parse the variable type if set
+  let data_type = if t.eat(":") {
+    Some(parse_type(t)?)
+  } else {
+    None
+  };
+
+  t.expect("=")?;
+  let action = ParseAction::start(t, ActionToExpect::Assignment)?;
+
+  Ok((data_type, action))
+}
@@ -2,32 +2,33 @@ mod compiler;
 
 use compiler::{AnilizedTokens, Compiler, CompilerProps, Lang, LocationError, Options};
 use std::cell::RefCell;
+use std::env;
 use std::fs;
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
 #[derive(Clone)]
-struct CLI {
+struct Cli {
     warnings: usize,
     errors: usize,
     options: Options,
 }
 
-impl Deref for CLI {
-    type Target = CLI;
+impl Deref for Cli {
+    type Target = Cli;
 
     fn deref(&self) -> &Self::Target {
-        &self
+        self
     }
 }
 
-impl DerefMut for CLI {
+impl DerefMut for Cli {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self
     }
 }
 
-impl CLI {
+impl Cli {
     fn new(options: Options) -> Self {
         Self {
             options,
@@ -37,7 +38,7 @@ impl CLI {
     }
 }
 
-impl CompilerProps for CLI {
+impl CompilerProps for Cli {
     fn open_file(&mut self, file_name: &str) -> Result<Vec<u8>, String> {
         match fs::read(file_name) {
             Err(err) => Err(format!("{}", err)),
@@ -66,9 +67,13 @@ impl CompilerProps for CLI {
 }
 
 fn main() {
-    let cli = Rc::new(RefCell::new(CLI::new(Options {
+    // `--deny-unused` reports unused code as errors instead of warnings
+    let deny_unused = env::args().skip(1).any(|arg| arg == "--deny-unused");
+
+    let cli = Rc::new(RefCell::new(Cli::new(Options {
         lang: Some(Lang::Go),
         debug: true,
+        deny_unused,
     })));
     let cli_clone = Rc::clone(&cli);
     Compiler::start("example.tp", cli);